@@ -2,7 +2,7 @@
 
 extern crate test;
 
-use calamine::{open_workbook, Ods, Reader, Xls, Xlsb, Xlsx};
+use calamine::{open_workbook, Ods, Reader, ReaderBuffers, Xls, Xlsb, Xlsx};
 use std::fs::File;
 use std::io::BufReader;
 use test::Bencher;
@@ -63,6 +63,24 @@ fn bench_xlsx_cells_reader(b: &mut Bencher) {
     b.iter(|| count::<Xlsx<_>>("tests/issues.xlsx"));
 }
 
+#[bench]
+fn bench_xlsx_reused_buffers(b: &mut Bencher) {
+    let path = format!("{}/tests/issues.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut buffers = Some(ReaderBuffers::new());
+    b.iter(|| {
+        let mut excel: Xlsx<_> = open_workbook(&path).expect("cannot open excel file");
+        excel.with_buffers(buffers.take().unwrap_or_default());
+
+        let sheets = excel.sheet_names();
+        let mut count = 0;
+        for s in sheets {
+            count += excel.worksheet_range(&s).unwrap().used_cells().count();
+        }
+        buffers = Some(excel.into_buffers());
+        count
+    });
+}
+
 #[bench]
 fn bench_xlsb_cells_reader(b: &mut Bencher) {
     fn count<R: Reader<BufReader<File>>>(path: &str) -> usize {
@@ -0,0 +1,17 @@
+//! An example of getting a column of a calamine `Range`.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let mut range = Range::new((1, 1), (3, 2));
+    range.set_value((1, 2), Data::Int(1));
+    range.set_value((3, 2), Data::Int(3));
+
+    assert_eq!(
+        range.column(2),
+        vec![&Data::Int(1), &Data::Empty, &Data::Int(3)]
+    );
+
+    // Columns outside the range are empty.
+    assert!(range.column(0).is_empty());
+}
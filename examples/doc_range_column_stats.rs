@@ -0,0 +1,19 @@
+//! An example of computing the statistics of a column.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![Data::String("Qty".to_string())],
+        vec![Data::Int(2)],
+        vec![Data::Empty],
+        vec![Data::Float(4.0)],
+    ]);
+
+    let stats = range.column_stats(0);
+
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.numeric_count, 2);
+    assert_eq!(stats.null_count, 1);
+    assert_eq!(stats.mean, Some(3.0));
+}
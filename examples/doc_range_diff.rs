@@ -0,0 +1,28 @@
+//! An example of comparing a range with an updated copy.
+
+use calamine::{CellDiff, Data, Range};
+
+fn main() {
+    let old = Range::from_2d_vec(vec![vec![Data::Int(1), Data::Int(2)]]);
+    let mut new = old.clone();
+    new.set_value((0, 1), Data::Int(3));
+    new.set_value((1, 0), Data::Int(4));
+
+    let diffs = old.diff(&new);
+
+    assert_eq!(
+        diffs,
+        [
+            CellDiff {
+                pos: (0, 1),
+                left: Data::Int(2),
+                right: Data::Int(3)
+            },
+            CellDiff {
+                pos: (1, 0),
+                left: Data::Empty,
+                right: Data::Int(4)
+            },
+        ]
+    );
+}
@@ -0,0 +1,18 @@
+//! An example of comparing ranges by their displayed text.
+
+use calamine::{CellStyle, Data, Range};
+
+fn main() {
+    let a = Range::from_2d_vec(vec![vec![Data::Float(3.0), Data::String("x".to_string())]]);
+    let b = Range::from_2d_vec(vec![vec![Data::Int(3), Data::String("x".to_string())]]);
+
+    assert_ne!(a.get((0, 0)), b.get((0, 0)));
+    assert!(a.eq_displayed(&b, &[], &[], false));
+
+    // Formatted with two decimals, 3 is displayed as "3.00".
+    let two_decimals = CellStyle {
+        number_format_id: Some(2),
+        ..CellStyle::default()
+    };
+    assert!(!a.eq_displayed(&b, &[two_decimals.format_code()], &[], false));
+}
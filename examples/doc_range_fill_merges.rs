@@ -0,0 +1,16 @@
+//! An example of repeating a merged header over its columns.
+
+use calamine::{Data, Dimensions, Range};
+
+fn main() {
+    let mut range = Range::new((0, 0), (1, 2));
+    range.set_value((0, 0), Data::String("Sales".to_string()));
+    range.set_value((1, 0), Data::String("Q1".to_string()));
+
+    // A1:C1 is merged.
+    range.fill_merges(&[Dimensions::new((0, 0), (0, 2))]);
+
+    let sales = Data::String("Sales".to_string());
+    assert_eq!(range[0], [sales.clone(), sales.clone(), sales]);
+    assert_eq!(range.get_value((1, 1)), Some(&Data::Empty));
+}
@@ -0,0 +1,18 @@
+//! An example of locating a table below title rows.
+
+use calamine::{Data, Dimensions, Range};
+
+fn main() {
+    let text = |s: &str| Data::String(s.to_string());
+    let range = Range::from_2d_vec(vec![
+        vec![text("Sales report"), Data::Empty, Data::Empty],
+        vec![Data::Empty, Data::Empty, Data::Empty],
+        vec![text("Date"), text("Region"), text("Amount")],
+        vec![text("2024-01-01"), text("North"), Data::Float(10.0)],
+        vec![text("2024-01-02"), text("South"), Data::Float(12.5)],
+    ]);
+
+    let table = range.find_table(&["date", "amount"]);
+
+    assert_eq!(table, Some(Dimensions::new((2, 0), (4, 2))));
+}
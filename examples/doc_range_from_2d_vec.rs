@@ -0,0 +1,18 @@
+//! An example of creating a calamine `Range` from a ragged 2D vector.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let rows = vec![
+        vec![Data::Int(1)],
+        vec![Data::Int(2), Data::Int(3), Data::Int(4)],
+    ];
+
+    let range = Range::from_2d_vec(rows);
+
+    assert_eq!(range.get_size(), (2, 3));
+    assert_eq!(range.end(), Some((1, 2)));
+
+    // The first row is padded with empty cells.
+    assert_eq!(range[0], [Data::Int(1), Data::Empty, Data::Empty]);
+}
@@ -0,0 +1,19 @@
+//! An example of creating a calamine `Range` from scattered cells.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let cells = vec![
+        ((4, 1), Data::Int(3)),
+        ((1, 3), Data::Int(1)),
+        ((2, 0), Data::Int(2)),
+    ];
+
+    let range = Range::from_cells(cells);
+
+    assert_eq!(range.start(), Some((1, 0)));
+    assert_eq!(range.end(), Some((4, 3)));
+    assert_eq!(range.get_value((2, 0)), Some(&Data::Int(2)));
+    assert_eq!(range.get_value((2, 1)), Some(&Data::Empty));
+    assert_eq!(range.used_cells().count(), 3);
+}
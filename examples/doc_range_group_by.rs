@@ -0,0 +1,21 @@
+//! An example of summing a column by key.
+
+use calamine::{AggregationFunction, Data, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![Data::String("tea".to_string()), Data::Int(2)],
+        vec![Data::String("coffee".to_string()), Data::Int(3)],
+        vec![Data::String("tea".to_string()), Data::Float(1.5)],
+    ]);
+
+    let totals = range.group_by(0, 1, AggregationFunction::Sum);
+
+    assert_eq!(
+        totals,
+        [
+            (Data::String("tea".to_string()), Data::Float(3.5)),
+            (Data::String("coffee".to_string()), Data::Float(3.0)),
+        ]
+    );
+}
@@ -0,0 +1,17 @@
+//! An example of converting a calamine `Range` into a 2D vector.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let mut range = Range::new((1, 1), (2, 2));
+    range.set_value((1, 1), Data::Int(1));
+    range.set_value((2, 2), Data::Int(4));
+
+    assert_eq!(
+        range.into_2d_vec(),
+        vec![
+            vec![Data::Int(1), Data::Empty],
+            vec![Data::Empty, Data::Int(4)],
+        ]
+    );
+}
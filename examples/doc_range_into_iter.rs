@@ -0,0 +1,19 @@
+//! An example of consuming a calamine `Range` row by row.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let mut range = Range::new((1, 1), (2, 2));
+    range.set_value((1, 1), Data::String("a".to_string()));
+    range.set_value((2, 2), Data::Int(1));
+
+    let rows: Vec<Vec<Data>> = range.into_iter().collect();
+
+    assert_eq!(
+        rows,
+        vec![
+            vec![Data::String("a".to_string()), Data::Empty],
+            vec![Data::Empty, Data::Int(1)],
+        ]
+    );
+}
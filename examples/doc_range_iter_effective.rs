@@ -0,0 +1,18 @@
+//! An example of reading a header merged over two columns.
+
+use calamine::{Data, Dimensions, Range};
+
+fn main() {
+    let mut range = Range::new((0, 0), (1, 1));
+    range.set_value((0, 0), Data::String("Sales".to_string()));
+    range.set_value((1, 0), Data::Int(1));
+
+    // A1:B1 is merged.
+    let merges = [Dimensions::new((0, 0), (0, 1))];
+    let cells: Vec<_> = range.iter_effective(&merges).collect();
+
+    let sales = Data::String("Sales".to_string());
+    assert_eq!(cells[0], (0, 0, sales.clone()));
+    assert_eq!(cells[1], (0, 1, sales));
+    assert_eq!(cells[3], (1, 1, Data::Empty));
+}
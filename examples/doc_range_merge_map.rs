@@ -0,0 +1,22 @@
+//! An example of finding the value displayed in a merged cell.
+
+use calamine::{Data, Dimensions, Range};
+
+fn main() {
+    let mut range = Range::new((0, 0), (2, 2));
+    range.set_value((1, 1), Data::String("merged".to_string()));
+
+    // B2:C3 is merged.
+    let merges = [Dimensions::new((1, 1), (2, 2))];
+    let merge_map = range.merge_map(&merges);
+
+    assert!(merge_map.is_merged((2, 2)));
+    assert!(!merge_map.is_merged((0, 0)));
+
+    let anchor = merge_map.anchor_of((2, 2)).unwrap();
+    assert_eq!(anchor, (1, 1));
+    assert_eq!(
+        range.get_value(anchor),
+        Some(&Data::String("merged".to_string()))
+    );
+}
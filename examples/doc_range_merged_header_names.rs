@@ -0,0 +1,23 @@
+//! An example of naming the columns of a two-level header.
+
+use calamine::{Data, Dimensions, Range};
+
+fn main() {
+    let text = |v: &str| Data::String(v.to_string());
+    let range = Range::from_2d_vec(vec![
+        vec![text("Region"), text("2023"), Data::Empty, text("2024")],
+        vec![Data::Empty, text("Q1"), text("Q2"), text("Q1")],
+        vec![text("North"), Data::Int(1), Data::Int(2), Data::Int(3)],
+    ]);
+
+    // A1:A2 and B1:C1 are merged.
+    let merges = [
+        Dimensions::new((0, 0), (1, 0)),
+        Dimensions::new((0, 1), (0, 2)),
+    ];
+
+    assert_eq!(
+        range.merged_header_names(0..2, &merges),
+        ["Region", "2023.Q1", "2023.Q2", "2024.Q1"]
+    );
+}
@@ -0,0 +1,14 @@
+//! An example of getting a row of a calamine `Range`.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let mut range = Range::new((1, 1), (2, 3));
+    range.set_value((2, 1), Data::Int(1));
+    range.set_value((2, 3), Data::Int(3));
+
+    assert_eq!(range.row(2), &[Data::Int(1), Data::Empty, Data::Int(3)]);
+
+    // Rows outside the range are empty.
+    assert!(range.row(0).is_empty());
+}
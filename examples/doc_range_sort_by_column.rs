@@ -0,0 +1,24 @@
+//! An example of sorting the rows of a range by a column of mixed values.
+
+use calamine::{CellErrorType, Data, Range};
+
+fn main() {
+    let mut range = Range::from_2d_vec(vec![
+        vec![Data::String("b".to_string()), Data::Int(1)],
+        vec![Data::Empty, Data::Int(2)],
+        vec![Data::Bool(true), Data::Int(3)],
+        vec![Data::Float(2.5), Data::Int(4)],
+        vec![Data::Error(CellErrorType::NA), Data::Int(5)],
+        vec![Data::String("A".to_string()), Data::Int(6)],
+        vec![Data::Int(10), Data::Int(7)],
+    ]);
+
+    range.sort_by_column(0, true);
+    let order: Vec<_> = range.column(1).into_iter().cloned().collect();
+    assert_eq!(order, [4, 7, 6, 1, 3, 5, 2].map(Data::Int).to_vec());
+
+    // Empty cells stay last when sorting in descending order.
+    range.sort_by_column(0, false);
+    let order: Vec<_> = range.column(1).into_iter().cloned().collect();
+    assert_eq!(order, [5, 3, 1, 6, 7, 4, 2].map(Data::Int).to_vec());
+}
@@ -0,0 +1,27 @@
+//! An example of converting a range to an HTML table.
+
+use calamine::{Data, HtmlOptions, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![
+            Data::String("Name".to_string()),
+            Data::String("Qty".to_string()),
+        ],
+        vec![Data::String("Fish & chips".to_string()), Data::Int(2)],
+    ]);
+
+    let opts = HtmlOptions {
+        header: true,
+        ..HtmlOptions::default()
+    };
+    let html = range.to_html(opts);
+
+    assert_eq!(
+        html,
+        "<table>\n\
+         <tr><th>Name</th><th>Qty</th></tr>\n\
+         <tr><td>Fish &amp; chips</td><td>2</td></tr>\n\
+         </table>\n"
+    );
+}
@@ -0,0 +1,18 @@
+//! An example of converting a range to JSON rows.
+
+use calamine::{CellErrorType, Data, Range};
+use serde_json::json;
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![Data::String("a".to_string()), Data::Float(1.5)],
+        vec![Data::Error(CellErrorType::Div0), Data::Empty],
+    ]);
+
+    let rows = range.to_json_rows();
+
+    assert_eq!(
+        rows,
+        [[json!("a"), json!(1.5)], [json!("#DIV/0!"), json!(null)]]
+    );
+}
@@ -0,0 +1,18 @@
+//! An example of converting a range to an `ndarray` matrix.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![Data::Float(1.5), Data::Int(2)],
+        vec![Data::Bool(true), Data::String("n/a".to_string())],
+    ]);
+
+    let matrix = range.to_ndarray_f64(f64::NAN);
+
+    assert_eq!(matrix.shape(), &[2, 2]);
+    assert_eq!(matrix[[0, 0]], 1.5);
+    assert_eq!(matrix[[0, 1]], 2.0);
+    assert_eq!(matrix[[1, 0]], 1.0);
+    assert!(matrix[[1, 1]].is_nan());
+}
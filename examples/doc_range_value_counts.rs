@@ -0,0 +1,18 @@
+//! An example of counting the distinct values of a column.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![Data::String("red".to_string())],
+        vec![Data::String("blue".to_string())],
+        vec![Data::String("red".to_string())],
+        vec![Data::Empty],
+    ]);
+
+    let counts = range.value_counts(0);
+
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&Data::String("red".to_string())], 2);
+    assert_eq!(counts[&Data::String("blue".to_string())], 1);
+}
@@ -0,0 +1,25 @@
+//! An example of iterating over 2-row windows of a calamine `Range`.
+
+use calamine::{Data, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![Data::Int(1)],
+        vec![Data::Int(2)],
+        vec![Data::Int(3)],
+    ]);
+
+    let windows: Vec<_> = range.windows(2, false).map(|w| w.into_2d_vec()).collect();
+    assert_eq!(
+        windows,
+        [
+            vec![vec![Data::Int(1)], vec![Data::Int(2)]],
+            vec![vec![Data::Int(2)], vec![Data::Int(3)]],
+        ]
+    );
+
+    // The last window only has the last row.
+    let last = range.windows(2, true).last().unwrap();
+    assert_eq!(last.start(), Some((2, 0)));
+    assert_eq!(last.height(), 1);
+}
@@ -0,0 +1,17 @@
+//! An example of filling a vertically merged cell.
+
+use calamine::{Data, Dimensions, Range};
+
+fn main() {
+    let mut range = Range::new((0, 0), (2, 0));
+    range.set_value((0, 0), Data::Int(7));
+
+    // A1:A2 is merged.
+    let filled = range.with_merges_filled(&[Dimensions::new((0, 0), (1, 0))]);
+
+    assert_eq!(filled.get_value((1, 0)), Some(&Data::Int(7)));
+    assert_eq!(filled.get_value((2, 0)), Some(&Data::Empty));
+
+    // The original range is unchanged.
+    assert_eq!(range.get_value((1, 0)), Some(&Data::Empty));
+}
@@ -0,0 +1,19 @@
+//! An example of writing a range as CSV.
+
+use calamine::{CsvOptions, Data, Range};
+
+fn main() {
+    let range = Range::from_2d_vec(vec![
+        vec![
+            Data::String("Name".to_string()),
+            Data::String("Qty".to_string()),
+        ],
+        vec![Data::String("Fish, chips".to_string()), Data::Int(2)],
+        vec![Data::String("Tea".to_string()), Data::Empty],
+    ]);
+
+    let mut csv = Vec::new();
+    range.write_csv(&mut csv, CsvOptions::default()).unwrap();
+
+    assert_eq!(csv, b"Name,Qty\r\n\"Fish, chips\",2\r\nTea,\r\n");
+}
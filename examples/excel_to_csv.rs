@@ -1,4 +1,4 @@
-use calamine::{open_workbook_auto, Data, DataWithFormatting, Range, Reader};
+use calamine::{open_workbook_auto, Data, DataWithFormatting, FloatFormat, Range, Reader, Sheets};
 use std::env;
 use std::fs::File;
 use std::io::{BufWriter, Write};
@@ -14,6 +14,15 @@ fn main() {
     let sheet = env::args()
         .nth(2)
         .expect("Expecting a sheet name as second argument");
+    // optional float format: `shortest` (default), `fixed:<decimals>` or `excel`
+    let float_format = match env::args().nth(3).as_deref() {
+        None | Some("shortest") => FloatFormat::Shortest,
+        Some("excel") => FloatFormat::Excel,
+        Some(f) => match f.strip_prefix("fixed:").and_then(|d| d.parse().ok()) {
+            Some(decimals) => FloatFormat::Fixed(decimals),
+            None => panic!("Unknown float format '{f}'"),
+        },
+    };
 
     let sce = PathBuf::from(file);
     match sce.extension().and_then(|s| s.to_str()) {
@@ -25,11 +34,17 @@ fn main() {
     let mut dest = BufWriter::new(File::create(dest).unwrap());
     let mut xl = open_workbook_auto(&sce).unwrap();
     let range = xl.worksheet_range(&sheet).unwrap();
+    let is_1904 = matches!(&xl, Sheets::Xlsx(xlsx) if xlsx.is_1904());
 
-    write_range(&mut dest, &range).unwrap();
+    write_range(&mut dest, &range, float_format, is_1904).unwrap();
 }
 
-fn write_range<W: Write>(dest: &mut W, range: &Range<DataWithFormatting>) -> std::io::Result<()> {
+fn write_range<W: Write>(
+    dest: &mut W,
+    range: &Range<DataWithFormatting>,
+    float_format: FloatFormat,
+    is_1904: bool,
+) -> std::io::Result<()> {
     let n = range.get_size().1 - 1;
     for r in range.rows() {
         for (i, c) in r.iter().enumerate() {
@@ -38,7 +53,10 @@ fn write_range<W: Write>(dest: &mut W, range: &Range<DataWithFormatting>) -> std
                 Data::String(ref s) | Data::DateTimeIso(ref s) | Data::DurationIso(ref s) => {
                     write!(dest, "{s}")
                 }
-                Data::Float(ref f) => write!(dest, "{f}"),
+                Data::Float(f) => {
+                    let style = c.formatting.as_ref();
+                    write!(dest, "{}", float_format.format(f, style, is_1904))
+                }
                 Data::DateTime(ref d) => write!(dest, "{}", d.as_f64()),
                 Data::Int(ref i) => write!(dest, "{i}"),
                 Data::Error(ref e) => write!(dest, "{e:?}"),
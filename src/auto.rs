@@ -1,16 +1,21 @@
 //! A module to convert file extension to reader
 
+use crate::cfb::Cfb;
 use crate::errors::Error;
 use crate::vba::VbaProject;
 use crate::{
     open_workbook, open_workbook_from_rs, CellStyle, ColumnWidths, DataRef, DataWithFormatting,
-    HeaderRow, Metadata, Ods, Range, Reader, ReaderRef, RowDefinitions, Theme, Xls, Xlsb, Xlsx,
+    HeaderRow, Metadata, Ods, Range, Reader, ReaderRef, RowDefinitions, Theme, Xls, Xlsb,
+    XlsbError, Xlsx, XlsxError,
 };
 use std::borrow::Cow;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
+/// Magic number of compound documents (OLE)
+const OLE_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
 /// A wrapper over all sheets when the file type is not known at static time
 pub enum Sheets<RS> {
     /// Xls reader
@@ -23,15 +28,72 @@ pub enum Sheets<RS> {
     Ods(Ods<RS>),
 }
 
+/// Format of a workbook, detected from its content
+enum Format {
+    /// Zip archive: xlsx, xlsb or ods
+    Zip,
+    /// Compound document holding a legacy workbook: xls
+    Xls,
+    /// Compound document holding an encrypted package: xlsx or xlsb
+    Encrypted,
+    /// Anything else
+    Unknown,
+}
+
+/// Detect the format of a workbook from its magic number and, for compound
+/// documents, from their streams
+///
+/// The reader is rewound to the start.
+fn detect_format<R: Read + Seek>(reader: &mut R) -> Result<Format, Error> {
+    let len = reader.seek(SeekFrom::End(0))? as usize;
+    reader.seek(SeekFrom::Start(0))?;
+    let mut magic = Vec::with_capacity(OLE_MAGIC.len());
+    reader
+        .take(OLE_MAGIC.len() as u64)
+        .read_to_end(&mut magic)?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let format = if magic.starts_with(b"PK") {
+        Format::Zip
+    } else if magic == OLE_MAGIC {
+        match Cfb::new(reader, len) {
+            Ok(cfb)
+                if cfb.has_directory("EncryptionInfo") || cfb.has_directory("EncryptedPackage") =>
+            {
+                Format::Encrypted
+            }
+            Ok(cfb) if cfb.has_directory("Workbook") || cfb.has_directory("Book") => Format::Xls,
+            _ => Format::Unknown,
+        }
+    } else {
+        Format::Unknown
+    };
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(format)
+}
+
 /// Opens a workbook and define the file type at runtime.
 ///
+/// The file type is given by the extension of the path, or detected from the
+/// content of the file if the extension is unknown. Encrypted workbooks,
+/// which are compound documents like xls files, fail with a `Password` error
+/// whatever their extension.
+///
 /// Whenever possible use the statically known `open_workbook` function instead
 pub fn open_workbook_auto<P>(path: P) -> Result<Sheets<BufReader<File>>, Error>
 where
     P: AsRef<Path>,
 {
     let path = path.as_ref();
-    Ok(match path.extension().and_then(|e| e.to_str()) {
+    let extension = path.extension().and_then(|e| e.to_str());
+    let format = detect_format(&mut File::open(path)?)?;
+    if let Format::Encrypted = format {
+        return Err(match extension {
+            Some("xlsb") => Error::Xlsb(XlsbError::Password),
+            _ => Error::Xlsx(XlsxError::Password),
+        });
+    }
+    Ok(match extension {
         Some("xls") | Some("xla") => Sheets::Xls(open_workbook(path).map_err(Error::Xls)?),
         Some("xlsx") | Some("xlsm") | Some("xlam") => {
             Sheets::Xlsx(open_workbook(path).map_err(Error::Xlsx)?)
@@ -39,9 +101,16 @@ where
         Some("xlsb") => Sheets::Xlsb(open_workbook(path).map_err(Error::Xlsb)?),
         Some("ods") => Sheets::Ods(open_workbook(path).map_err(Error::Ods)?),
         _ => {
-            if let Ok(ret) = open_workbook::<Xls<_>, _>(path) {
-                return Ok(Sheets::Xls(ret));
-            } else if let Ok(ret) = open_workbook::<Xlsx<_>, _>(path) {
+            match format {
+                Format::Xls => return Ok(Sheets::Xls(open_workbook(path).map_err(Error::Xls)?)),
+                Format::Unknown => {
+                    if let Ok(ret) = open_workbook::<Xls<_>, _>(path) {
+                        return Ok(Sheets::Xls(ret));
+                    }
+                }
+                Format::Zip | Format::Encrypted => (),
+            }
+            if let Ok(ret) = open_workbook::<Xlsx<_>, _>(path) {
                 return Ok(Sheets::Xlsx(ret));
             } else if let Ok(ret) = open_workbook::<Xlsb<_>, _>(path) {
                 return Ok(Sheets::Xlsb(ret));
@@ -56,14 +125,29 @@ where
 
 /// Opens a workbook from the given bytes.
 ///
+/// The file type is detected from the content. Encrypted workbooks fail with
+/// an `XlsxError::Password` error.
+///
 /// Whenever possible use the statically known `open_workbook_from_rs` function instead
 pub fn open_workbook_auto_from_rs<RS>(data: RS) -> Result<Sheets<RS>, Error>
 where
     RS: std::io::Read + std::io::Seek + Clone,
 {
-    if let Ok(ret) = open_workbook_from_rs::<Xls<RS>, RS>(data.clone()) {
-        Ok(Sheets::Xls(ret))
-    } else if let Ok(ret) = open_workbook_from_rs::<Xlsx<RS>, RS>(data.clone()) {
+    match detect_format(&mut data.clone())? {
+        Format::Encrypted => return Err(Error::Xlsx(XlsxError::Password)),
+        Format::Xls => {
+            return Ok(Sheets::Xls(
+                open_workbook_from_rs(data).map_err(Error::Xls)?,
+            ))
+        }
+        Format::Zip => (),
+        Format::Unknown => {
+            if let Ok(ret) = open_workbook_from_rs::<Xls<RS>, RS>(data.clone()) {
+                return Ok(Sheets::Xls(ret));
+            }
+        }
+    }
+    if let Ok(ret) = open_workbook_from_rs::<Xlsx<RS>, RS>(data.clone()) {
         Ok(Sheets::Xlsx(ret))
     } else if let Ok(ret) = open_workbook_from_rs::<Xlsb<RS>, RS>(data.clone()) {
         Ok(Sheets::Xlsb(ret))
@@ -226,6 +226,8 @@ pub struct DataBar {
     pub direction: Option<BarDirection>,
     /// Only show bar (hide value)
     pub bar_only: bool,
+    /// Bar has a border (2010+ extension)
+    pub border: bool,
     /// Border color
     pub border_color: Option<Color>,
     /// Negative bar border color
@@ -537,6 +539,35 @@ pub struct DifferentialProtection {
     pub hidden: Option<bool>,
 }
 
+impl ConditionalFormatValue {
+    /// The threshold as a number
+    ///
+    /// Returns `None` for `min`/`max` thresholds, which have no value, and
+    /// for formulas that are not a plain number.
+    pub fn number(&self) -> Option<f64> {
+        self.value.as_deref()?.trim().parse().ok()
+    }
+}
+
+impl ColorScale {
+    /// The stops of the gradient, each threshold with its color
+    pub fn stops(&self) -> impl Iterator<Item = (&ConditionalFormatValue, &Color)> {
+        self.cfvos.iter().zip(&self.colors)
+    }
+}
+
+impl IconSet {
+    /// The thresholds of the icons, each type with its value as a number
+    ///
+    /// The first threshold is the lower bound of the first icon. Values are
+    /// `None` for thresholds without a number, such as formulas.
+    pub fn thresholds(&self) -> impl Iterator<Item = (CfvoType, Option<f64>)> + '_ {
+        self.cfvos
+            .iter()
+            .map(|cfvo| (cfvo.value_type, cfvo.number()))
+    }
+}
+
 impl fmt::Display for ComparisonOperator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use ComparisonOperator::*;
@@ -1,4 +1,6 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 #[cfg(feature = "dates")]
 use std::sync::OnceLock;
 
@@ -19,7 +21,30 @@ const MS_MULTIPLIER: f64 = 24f64 * 60f64 * 60f64 * 1e+3f64;
 
 /// An enum to represent all different data types that can appear as
 /// a value in a worksheet cell
-#[derive(Debug, Clone, PartialEq, Default)]
+///
+/// `Data` implements [`Eq`] and [`Hash`], so it can be used as a key of a
+/// `HashMap` or a `HashSet`. Floats, including the serial value of a
+/// `DateTime`, are compared by value with two exceptions that keep equality
+/// reflexive and consistent with hashing: all NaNs are equal to each other,
+/// and `-0.0` is equal to `0.0`. Values of different variants are never
+/// equal, e.g. `Int(1)` and `Float(1.0)`.
+///
+/// `Data` also implements [`Ord`], following the order Excel uses when it
+/// sorts a column in ascending order:
+///
+/// 1. numbers (`Int`, `Float` and `DateTime`, compared by value), with NaN
+///    after every other number
+/// 2. ISO 8601 dates and durations (`DateTimeIso` then `DurationIso`,
+///    compared as text)
+/// 3. text, compared case-insensitively
+/// 4. logical values, `false` before `true`
+/// 5. errors, in the declaration order of [`CellErrorType`]
+/// 6. blanks
+///
+/// Values that Excel considers tied, such as `Int(1)` and `Float(1.0)` or
+/// `"a"` and `"A"`, are ordered by variant and then case-sensitively so the
+/// ordering stays consistent with [`Eq`].
+#[derive(Debug, Clone, Default)]
 pub enum Data {
     /// Signed integer
     Int(i64),
@@ -29,7 +54,10 @@ pub enum Data {
     String(String),
     /// Boolean
     Bool(bool),
-    /// Date or Time
+    /// Date or Time, kept as its Excel serial number
+    ///
+    /// The serial is available with [`ExcelDateTime::as_f64`], while the
+    /// variant still tells the cell apart from a plain number.
     DateTime(ExcelDateTime),
     /// Date, Time or Date/Time in ISO 8601
     DateTimeIso(String),
@@ -170,6 +198,300 @@ impl DataType for Data {
     }
 }
 
+impl PartialEq for Data {
+    fn eq(&self, other: &Data) -> bool {
+        match (self, other) {
+            (Data::Int(a), Data::Int(b)) => a == b,
+            (Data::Float(a), Data::Float(b)) => float_key(*a) == float_key(*b),
+            (Data::String(a), Data::String(b)) => a == b,
+            (Data::Bool(a), Data::Bool(b)) => a == b,
+            (Data::DateTime(a), Data::DateTime(b)) => a == b,
+            (Data::DateTimeIso(a), Data::DateTimeIso(b)) => a == b,
+            (Data::DurationIso(a), Data::DurationIso(b)) => a == b,
+            (Data::Error(a), Data::Error(b)) => a == b,
+            (Data::Empty, Data::Empty) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Data {}
+
+impl Hash for Data {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Data::Int(v) => v.hash(state),
+            Data::Float(v) => float_key(*v).hash(state),
+            Data::String(v) | Data::DateTimeIso(v) | Data::DurationIso(v) => v.hash(state),
+            Data::Bool(v) => v.hash(state),
+            Data::DateTime(v) => v.hash(state),
+            Data::Error(v) => v.hash(state),
+            Data::Empty => (),
+        }
+    }
+}
+
+impl Ord for Data {
+    fn cmp(&self, other: &Data) -> Ordering {
+        match (self, other) {
+            (Data::Int(a), Data::Int(b)) => a.cmp(b),
+            (Data::Float(a), Data::Float(b)) => cmp_floats(*a, *b),
+            (Data::DateTime(a), Data::DateTime(b)) => cmp_floats(a.value, b.value)
+                .then_with(|| (a.datetime_type as u8).cmp(&(b.datetime_type as u8)))
+                .then_with(|| a.is_1904.cmp(&b.is_1904)),
+            (Data::DateTimeIso(a), Data::DateTimeIso(b))
+            | (Data::DurationIso(a), Data::DurationIso(b)) => a.cmp(b),
+            (Data::String(a), Data::String(b)) => a
+                .chars()
+                .flat_map(char::to_lowercase)
+                .cmp(b.chars().flat_map(char::to_lowercase))
+                .then_with(|| a.cmp(b)),
+            (Data::Bool(a), Data::Bool(b)) => a.cmp(b),
+            (Data::Error(a), Data::Error(b)) => (a.clone() as u8).cmp(&(b.clone() as u8)),
+            (Data::Empty, Data::Empty) => Ordering::Equal,
+            _ => match (self.sort_number(), other.sort_number()) {
+                (Some(a), Some(b)) => cmp_floats(a, b),
+                _ => Ordering::Equal,
+            }
+            .then_with(|| self.sort_rank().cmp(&other.sort_rank())),
+        }
+    }
+}
+
+impl PartialOrd for Data {
+    fn partial_cmp(&self, other: &Data) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Data {
+    /// Position of the variant in the sort order, see [`Ord`] for `Data`
+    fn sort_rank(&self) -> u8 {
+        match self {
+            Data::Int(_) => 0,
+            Data::Float(_) => 1,
+            Data::DateTime(_) => 2,
+            Data::DateTimeIso(_) => 3,
+            Data::DurationIso(_) => 4,
+            Data::String(_) => 5,
+            Data::Bool(_) => 6,
+            Data::Error(_) => 7,
+            Data::Empty => 8,
+        }
+    }
+
+    /// Numeric value used to sort numbers of different variants together
+    fn sort_number(&self) -> Option<f64> {
+        match self {
+            Data::Int(v) => Some(*v as f64),
+            Data::Float(v) => Some(*v),
+            Data::DateTime(v) => Some(v.value),
+            _ => None,
+        }
+    }
+}
+
+/// Target type of [`Data::coerce_to`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataTypeKind {
+    /// [`Data::Int`]
+    Int,
+    /// [`Data::Float`]
+    Float,
+    /// [`Data::Bool`]
+    Bool,
+    /// [`Data::String`]
+    String,
+    /// [`Data::DateTime`]
+    DateTime,
+}
+
+/// Error returned when a value can't be coerced by [`Data::coerce_to`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoerceError {
+    /// The value that couldn't be coerced
+    pub value: Data,
+    /// The requested type
+    pub target: DataTypeKind,
+    /// Why the value can't be converted
+    pub reason: &'static str,
+}
+
+impl fmt::Display for CoerceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), fmt::Error> {
+        write!(
+            f,
+            "cannot coerce {:?} to {:?}: {}",
+            self.value, self.target, self.reason
+        )
+    }
+}
+
+impl std::error::Error for CoerceError {}
+
+impl Data {
+    /// Convert the value to another type with explicit rules
+    ///
+    /// - `Int`: floats without a fractional part, booleans as `0` or `1` and
+    ///   strings holding an integer, possibly written as a float like `"3.0"`.
+    /// - `Float`: integers, booleans as `0.0` or `1.0`, numeric strings and
+    ///   the serial value of dates.
+    /// - `Bool`: the numbers `0` and `1` and the strings `true`/`false`,
+    ///   `yes`/`no` and `1`/`0`, ignoring case.
+    /// - `String`: any value but errors, as written by its `Display`
+    ///   implementation, e.g. `"1.5"` for `Float(1.5)`.
+    /// - `DateTime`: numbers as a serial date of the 1900 date system and
+    ///   strings holding such a number. With the `dates` feature, ISO 8601
+    ///   dates and date times, as strings or `DateTimeIso`, are converted too.
+    ///
+    /// Strings are trimmed before being parsed. Values already of the target
+    /// type are returned as is, and empty cells stay empty whatever the
+    /// target, so that missing values can be told apart from invalid ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CoerceError`] for cell errors and for values that can't be
+    /// represented in the target type, e.g. `Float(1.5)` as an `Int`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use calamine::{Data, DataTypeKind};
+    ///
+    /// let value = Data::String(" 42 ".to_string());
+    /// assert_eq!(value.coerce_to(DataTypeKind::Int), Ok(Data::Int(42)));
+    ///
+    /// let value = Data::String("Yes".to_string());
+    /// assert_eq!(value.coerce_to(DataTypeKind::Bool), Ok(Data::Bool(true)));
+    ///
+    /// assert!(Data::Float(1.5).coerce_to(DataTypeKind::Int).is_err());
+    /// ```
+    pub fn coerce_to(&self, target: DataTypeKind) -> Result<Data, CoerceError> {
+        let error = |reason| CoerceError {
+            value: self.clone(),
+            target,
+            reason,
+        };
+        match self {
+            Data::Empty => return Ok(Data::Empty),
+            Data::Error(_) => return Err(error("the cell holds an error")),
+            _ => (),
+        }
+        match target {
+            DataTypeKind::Int => match self {
+                Data::Int(v) => Ok(Data::Int(*v)),
+                Data::Bool(v) => Ok(Data::Int(i64::from(*v))),
+                Data::Float(v) => float_to_int(*v).map(Data::Int).map_err(error),
+                Data::String(v) => {
+                    let v = v.trim();
+                    match v.parse() {
+                        Ok(i) => Ok(Data::Int(i)),
+                        Err(_) => match fast_float2::parse(v) {
+                            Ok(f) => float_to_int(f).map(Data::Int).map_err(error),
+                            Err(_) => Err(error("the string is not a number")),
+                        },
+                    }
+                }
+                _ => Err(error("dates can't be converted to integers")),
+            },
+            DataTypeKind::Float => match self {
+                Data::Int(v) => Ok(Data::Float(*v as f64)),
+                Data::Float(v) => Ok(Data::Float(*v)),
+                Data::Bool(v) => Ok(Data::Float(f64::from(u8::from(*v)))),
+                Data::DateTime(v) => Ok(Data::Float(v.value)),
+                Data::String(v) => fast_float2::parse(v.trim())
+                    .map(Data::Float)
+                    .map_err(|_| error("the string is not a number")),
+                _ => Err(error("ISO 8601 values can't be converted to floats")),
+            },
+            DataTypeKind::Bool => match self {
+                Data::Bool(v) => Ok(Data::Bool(*v)),
+                Data::Int(0) => Ok(Data::Bool(false)),
+                Data::Int(1) => Ok(Data::Bool(true)),
+                Data::Float(v) if *v == 0. => Ok(Data::Bool(false)),
+                Data::Float(v) if *v == 1. => Ok(Data::Bool(true)),
+                Data::Int(_) | Data::Float(_) => Err(error("only 0 and 1 are booleans")),
+                Data::String(v) => match v.trim().to_ascii_lowercase().as_str() {
+                    "true" | "yes" | "1" => Ok(Data::Bool(true)),
+                    "false" | "no" | "0" => Ok(Data::Bool(false)),
+                    _ => Err(error("the string is not a boolean")),
+                },
+                _ => Err(error("dates can't be converted to booleans")),
+            },
+            DataTypeKind::String => Ok(Data::String(self.to_string())),
+            DataTypeKind::DateTime => {
+                let date =
+                    |v| Data::DateTime(ExcelDateTime::new(v, ExcelDateTimeType::DateTime, false));
+                match self {
+                    Data::DateTime(v) => Ok(Data::DateTime(*v)),
+                    Data::Int(v) => Ok(date(*v as f64)),
+                    Data::Float(v) => Ok(date(*v)),
+                    Data::String(v) | Data::DateTimeIso(v) => parse_serial_date(v.trim())
+                        .map(date)
+                        .ok_or_else(|| error("the string is not a date")),
+                    _ => Err(error("the value is not a date")),
+                }
+            }
+        }
+    }
+}
+
+/// Integer value of a float, if it has no fractional part
+fn float_to_int(value: f64) -> Result<i64, &'static str> {
+    if value.fract() != 0. || !value.is_finite() {
+        Err("the number is not an integer")
+    } else if value < i64::MIN as f64 || value >= i64::MAX as f64 {
+        Err("the number is out of the integer range")
+    } else {
+        Ok(value as i64)
+    }
+}
+
+/// Serial value of a date written as a number or, with the `dates`
+/// feature, as an ISO 8601 date or date time
+fn parse_serial_date(value: &str) -> Option<f64> {
+    if let Ok(v) = fast_float2::parse(value) {
+        return Some(v);
+    }
+    #[cfg(feature = "dates")]
+    {
+        use std::str::FromStr;
+        let datetime = chrono::NaiveDateTime::from_str(value).ok().or_else(|| {
+            chrono::NaiveDate::from_str(value)
+                .ok()
+                .map(|d| d.and_time(chrono::NaiveTime::MIN))
+        })?;
+        let epoch = chrono::NaiveDate::from_ymd_opt(1899, 12, 30)?.and_time(chrono::NaiveTime::MIN);
+        let days = (datetime - epoch).num_milliseconds() as f64 / MS_MULTIPLIER;
+        // Excel counts the non-existent 1900-02-29, see `as_datetime`
+        Some(if days < 61. { days - 1. } else { days })
+    }
+    #[cfg(not(feature = "dates"))]
+    None
+}
+
+/// Total order of floats consistent with [`float_key`]: NaN is greater than
+/// any other value and `-0.0` is equal to `0.0`
+fn cmp_floats(a: f64, b: f64) -> Ordering {
+    match a.partial_cmp(&b) {
+        Some(o) => o,
+        None => a.is_nan().cmp(&b.is_nan()),
+    }
+}
+
+/// Bits used to compare and hash floats: all NaNs are the same value and
+/// `-0.0` is the same as `0.0`
+fn float_key(value: f64) -> u64 {
+    if value.is_nan() {
+        f64::NAN.to_bits()
+    } else if value == 0. {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
 impl PartialEq<&str> for Data {
     fn eq(&self, other: &&str) -> bool {
         matches!(*self, Data::String(ref s) if s == other)
@@ -329,7 +651,9 @@ where
 
 /// An enum to represent all different data types that can appear as
 /// a value in a worksheet cell
-#[derive(Debug, Clone, PartialEq, Default)]
+///
+/// Equality and hashing follow the same rules as [`Data`].
+#[derive(Debug, Clone, Default)]
 pub enum DataRef<'a> {
     /// Signed integer
     Int(i64),
@@ -490,6 +814,42 @@ impl DataType for DataRef<'_> {
     }
 }
 
+impl PartialEq for DataRef<'_> {
+    fn eq(&self, other: &DataRef<'_>) -> bool {
+        match (self, other) {
+            (DataRef::Int(a), DataRef::Int(b)) => a == b,
+            (DataRef::Float(a), DataRef::Float(b)) => float_key(*a) == float_key(*b),
+            (DataRef::String(a), DataRef::String(b)) => a == b,
+            (DataRef::SharedString(a), DataRef::SharedString(b)) => a == b,
+            (DataRef::Bool(a), DataRef::Bool(b)) => a == b,
+            (DataRef::DateTime(a), DataRef::DateTime(b)) => a == b,
+            (DataRef::DateTimeIso(a), DataRef::DateTimeIso(b)) => a == b,
+            (DataRef::DurationIso(a), DataRef::DurationIso(b)) => a == b,
+            (DataRef::Error(a), DataRef::Error(b)) => a == b,
+            (DataRef::Empty, DataRef::Empty) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DataRef<'_> {}
+
+impl Hash for DataRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            DataRef::Int(v) => v.hash(state),
+            DataRef::Float(v) => float_key(*v).hash(state),
+            DataRef::String(v) | DataRef::DateTimeIso(v) | DataRef::DurationIso(v) => v.hash(state),
+            DataRef::SharedString(v) => v.hash(state),
+            DataRef::Bool(v) => v.hash(state),
+            DataRef::DateTime(v) => v.hash(state),
+            DataRef::Error(v) => v.hash(state),
+            DataRef::Empty => (),
+        }
+    }
+}
+
 impl PartialEq<&str> for DataRef<'_> {
     fn eq(&self, other: &&str) -> bool {
         matches!(*self, DataRef::String(ref s) if s == other)
@@ -580,6 +940,29 @@ pub trait DataType {
     /// Try getting Error value
     fn get_error(&self) -> Option<&CellErrorType>;
 
+    /// Try getting the kind of Error value
+    fn error_kind(&self) -> Option<CellErrorType> {
+        self.get_error().cloned()
+    }
+
+    /// Assess if a float value needs more than the 15 significant digits
+    /// Excel keeps
+    ///
+    /// Such a value, like a long identifier stored as a number, cannot have
+    /// been typed in Excel as is and doesn't round-trip. Always `false` for
+    /// values that aren't floats.
+    fn precision_warning(&self) -> bool {
+        match self.get_float() {
+            Some(f) if f.is_finite() => {
+                // `{:e}` gives the shortest representation that round-trips
+                let repr = format!("{:e}", f.abs());
+                let mantissa = repr.split('e').next().unwrap_or_default();
+                mantissa.bytes().filter(u8::is_ascii_digit).count() > 15
+            }
+            _ => false,
+        }
+    }
+
     /// Try converting data type into a string
     fn as_string(&self) -> Option<String>;
 
@@ -679,7 +1062,7 @@ impl<'a> From<DataRef<'a>> for Data {
 
 /// Excel datetime type. Possible: date, time, datetime, duration.
 /// At this time we can only determine datetime (date and time are datetime too) and duration.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ExcelDateTimeType {
     /// `DateTime`
     DateTime,
@@ -688,13 +1071,31 @@ pub enum ExcelDateTimeType {
 }
 
 /// Structure for Excel date and time representation.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy)]
 pub struct ExcelDateTime {
     value: f64,
     datetime_type: ExcelDateTimeType,
     is_1904: bool,
 }
 
+impl PartialEq for ExcelDateTime {
+    fn eq(&self, other: &ExcelDateTime) -> bool {
+        float_key(self.value) == float_key(other.value)
+            && self.datetime_type == other.datetime_type
+            && self.is_1904 == other.is_1904
+    }
+}
+
+impl Eq for ExcelDateTime {}
+
+impl Hash for ExcelDateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        float_key(self.value).hash(state);
+        self.datetime_type.hash(state);
+        self.is_1904.hash(state);
+    }
+}
+
 impl ExcelDateTime {
     /// Creates a new `ExcelDateTime`
     pub fn new(value: f64, datetime_type: ExcelDateTimeType, is_1904: bool) -> Self {
@@ -714,6 +1115,11 @@ impl ExcelDateTime {
         }
     }
 
+    /// True if the value is a serial date of the 1904 date system
+    pub(crate) fn is_1904(&self) -> bool {
+        self.is_1904
+    }
+
     /// True if excel datetime has duration format ([hh]:mm:ss, for example)
     #[cfg(feature = "dates")]
     pub fn is_duration(&self) -> bool {
@@ -756,6 +1162,28 @@ impl ExcelDateTime {
         let excel_duration = chrono::Duration::milliseconds(ms.round() as i64);
         excel_epoch.checked_add_signed(excel_duration)
     }
+
+    /// Converts the value to JSON
+    ///
+    /// With the `dates` feature, dates are ISO 8601 text such as
+    /// `"2021-01-01T00:00:00"` and durations such as `"PT3600S"`. Without it,
+    /// or if the value is out of the date range, this is the serial number.
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn to_json(self) -> serde_json::Value {
+        #[cfg(feature = "dates")]
+        {
+            let text = if self.is_duration() {
+                self.as_duration().map(|d| d.to_string())
+            } else {
+                // the `Debug` output of chrono dates is their ISO 8601 text
+                self.as_datetime().map(|dt| format!("{dt:?}"))
+            };
+            if let Some(text) = text {
+                return serde_json::Value::String(text);
+            }
+        }
+        serde_json::Number::from_f64(self.value).map_or(serde_json::Value::Null, Into::into)
+    }
 }
 
 impl Default for ExcelDateTime {
@@ -845,6 +1273,25 @@ mod date_tests {
             ))
         );
     }
+
+    #[test]
+    fn test_coerce_to_datetime_iso() {
+        let expected = Data::Float(45000.5);
+        for value in [
+            Data::String("2023-03-15T12:00:00".to_string()),
+            Data::DateTimeIso("2023-03-15T12:00:00".to_string()),
+        ] {
+            let date = value.coerce_to(DataTypeKind::DateTime).unwrap();
+            assert_eq!(date.coerce_to(DataTypeKind::Float), Ok(expected.clone()));
+        }
+        let date = Data::String("1900-01-01".to_string())
+            .coerce_to(DataTypeKind::DateTime)
+            .unwrap();
+        assert_eq!(date.coerce_to(DataTypeKind::Float), Ok(Data::Float(1.)));
+        assert!(Data::String("15/03/2023".to_string())
+            .coerce_to(DataTypeKind::DateTime)
+            .is_err());
+    }
 }
 
 #[cfg(test)]
@@ -860,6 +1307,20 @@ mod tests {
         assert_eq!(Data::Int(100), 100i64);
     }
 
+    #[test]
+    fn test_float_equality() {
+        assert_eq!(Data::Float(f64::NAN), Data::Float(f64::NAN));
+        assert_eq!(Data::Float(f64::NAN), Data::Float(-f64::NAN));
+        assert_eq!(Data::Float(-0.0), Data::Float(0.0));
+        assert_ne!(Data::Float(1.0), Data::Float(f64::NAN));
+        assert_ne!(Data::Int(1), Data::Float(1.0));
+        assert_eq!(Data::Float(-0.0).cmp(&Data::Float(0.0)), Ordering::Equal);
+
+        assert_eq!(DataRef::Float(f64::NAN), DataRef::Float(f64::NAN));
+        assert_eq!(DataRef::Float(-0.0), DataRef::Float(0.0));
+        assert_ne!(DataRef::Float(1.0), DataRef::Float(f64::NAN));
+    }
+
     #[test]
     fn test_as_i64_with_bools() {
         assert_eq!(Data::Bool(true).as_i64(), Some(1));
@@ -875,4 +1336,113 @@ mod tests {
         assert_eq!(DataRef::Bool(true).as_f64(), Some(1.0));
         assert_eq!(DataRef::Bool(false).as_f64(), Some(0.0));
     }
+
+    #[test]
+    fn test_hash_set() {
+        use std::collections::HashSet;
+
+        let date = |v| Data::DateTime(ExcelDateTime::new(v, ExcelDateTimeType::DateTime, false));
+        let mut set = HashSet::new();
+        for data in [
+            Data::Int(1),
+            Data::Int(1),
+            Data::Float(1.0),
+            Data::Float(0.0),
+            Data::Float(-0.0),
+            Data::Float(f64::NAN),
+            Data::Float(-f64::NAN),
+            Data::String("1".to_string()),
+            Data::String("1".to_string()),
+            Data::DateTimeIso("1".to_string()),
+            date(45000.5),
+            date(45000.5),
+            date(45001.),
+            Data::Error(CellErrorType::NA),
+            Data::Error(CellErrorType::NA),
+            Data::Empty,
+            Data::Empty,
+        ] {
+            set.insert(data);
+        }
+        assert_eq!(set.len(), 10);
+        assert!(set.contains(&Data::Float(-0.0)));
+        assert!(set.contains(&Data::Float(f64::NAN)));
+        assert!(set.contains(&date(45001.)));
+
+        let mut set = HashSet::new();
+        for data in [
+            DataRef::SharedString("a"),
+            DataRef::SharedString("a"),
+            DataRef::String("a".to_string()),
+            DataRef::Float(f64::NAN),
+            DataRef::Float(f64::NAN),
+        ] {
+            set.insert(data);
+        }
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_coerce_to() {
+        use DataTypeKind::*;
+
+        let s = |v: &str| Data::String(v.to_string());
+        let date = |v| Data::DateTime(ExcelDateTime::new(v, ExcelDateTimeType::DateTime, false));
+
+        assert_eq!(Data::Float(3.0).coerce_to(Int), Ok(Data::Int(3)));
+        assert_eq!(Data::Bool(true).coerce_to(Int), Ok(Data::Int(1)));
+        assert_eq!(s(" -12 ").coerce_to(Int), Ok(Data::Int(-12)));
+        assert_eq!(s("1e3").coerce_to(Int), Ok(Data::Int(1000)));
+
+        assert_eq!(Data::Int(2).coerce_to(Float), Ok(Data::Float(2.0)));
+        assert_eq!(Data::Bool(false).coerce_to(Float), Ok(Data::Float(0.0)));
+        assert_eq!(s("2.5").coerce_to(Float), Ok(Data::Float(2.5)));
+        assert_eq!(date(45000.5).coerce_to(Float), Ok(Data::Float(45000.5)));
+
+        for (value, expected) in [
+            (s("TRUE"), true),
+            (s("yes"), true),
+            (s("1"), true),
+            (s("False"), false),
+            (s("NO"), false),
+            (Data::Int(0), false),
+            (Data::Float(1.0), true),
+        ] {
+            assert_eq!(value.coerce_to(Bool), Ok(Data::Bool(expected)));
+        }
+
+        assert_eq!(Data::Float(1.5).coerce_to(String), Ok(s("1.5")));
+        assert_eq!(Data::Int(7).coerce_to(String), Ok(s("7")));
+        assert_eq!(Data::Bool(true).coerce_to(String), Ok(s("true")));
+
+        assert_eq!(Data::Int(45000).coerce_to(DateTime), Ok(date(45000.)));
+        assert_eq!(s("45000.25").coerce_to(DateTime), Ok(date(45000.25)));
+
+        // values of the target type and empty cells are kept
+        assert_eq!(s("x").coerce_to(String), Ok(s("x")));
+        assert_eq!(Data::Empty.coerce_to(Int), Ok(Data::Empty));
+    }
+
+    #[test]
+    fn test_coerce_to_errors() {
+        use DataTypeKind::*;
+
+        let s = |v: &str| Data::String(v.to_string());
+
+        let err = Data::Float(1.5).coerce_to(Int).unwrap_err();
+        assert_eq!(err.value, Data::Float(1.5));
+        assert_eq!(err.target, Int);
+        assert_eq!(
+            err.to_string(),
+            "cannot coerce Float(1.5) to Int: the number is not an integer"
+        );
+        assert!(Data::Float(1e20).coerce_to(Int).is_err());
+        assert!(s("abc").coerce_to(Float).is_err());
+        assert!(s("maybe").coerce_to(Bool).is_err());
+        assert!(Data::Int(2).coerce_to(Bool).is_err());
+        assert!(Data::Error(CellErrorType::NA).coerce_to(String).is_err());
+        assert!(Data::DateTimeIso("2024-01-01".to_string())
+            .coerce_to(Float)
+            .is_err());
+    }
 }
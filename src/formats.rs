@@ -109,6 +109,35 @@ pub enum CellFormat {
     TimeDelta,
 }
 
+/// Display category of a number format
+///
+/// A finer grained classification than [`CellFormat`], for quick type
+/// decisions without parsing the format string. See
+/// [`CellStyle::format_category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatCategory {
+    /// Date without time, e.g. "yyyy-mm-dd"
+    Date,
+    /// Time of day or duration, e.g. "h:mm" or "[h]:mm:ss"
+    Time,
+    /// Date and time, e.g. "yyyy-mm-dd hh:mm"
+    DateTime,
+    /// Percentage, e.g. "0.00%"
+    Percent,
+    /// Currency or accounting format, e.g. "$#,##0.00"
+    Currency,
+    /// Scientific notation, e.g. "0.00E+00"
+    Scientific,
+    /// Fraction, e.g. "# ?/?"
+    Fraction,
+    /// Other number format, e.g. "#,##0.00"
+    Number,
+    /// Text, i.e. "@"
+    Text,
+    /// General format
+    General,
+}
+
 /// Comprehensive cell formatting information
 ///
 /// Contains all formatting information for a cell, including number format,
@@ -138,6 +167,11 @@ pub struct CellStyle {
     /// Contains the original format string from the Excel file, useful for
     /// applications that need to preserve exact formatting information.
     pub format_string: Option<Arc<str>>,
+    /// Id of the number format (`numFmtId`)
+    ///
+    /// Builtin formats (ids below 164) have no `format_string`, their code is
+    /// given by the id, e.g. `10` for `0.00%`.
+    pub number_format_id: Option<u32>,
     /// Font information
     ///
     /// Contains font name, size, style (bold/italic), and color information.
@@ -156,11 +190,36 @@ pub struct CellStyle {
     pub alignment: Option<Arc<Alignment>>,
 }
 
+/// Which parts of a cell format are applied (`apply*` attributes of `<xf>`)
+///
+/// A part referenced by a cell format but not applied is inherited from its
+/// named cell style instead. Each flag is `None` when its attribute is missing.
+///
+/// # References
+///
+/// - ECMA-376 Part 1, Section 18.8.45 (xf - Format)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ApplyFlags {
+    /// The number format is applied (`applyNumberFormat`)
+    pub number_format: Option<bool>,
+    /// The font is applied (`applyFont`)
+    pub font: Option<bool>,
+    /// The fill is applied (`applyFill`)
+    pub fill: Option<bool>,
+    /// The border is applied (`applyBorder`)
+    pub border: Option<bool>,
+    /// The alignment is applied (`applyAlignment`)
+    pub alignment: Option<bool>,
+    /// The protection is applied (`applyProtection`)
+    pub protection: Option<bool>,
+}
+
 impl Default for CellStyle {
     fn default() -> Self {
         Self {
             number_format: CellFormat::Other,
             format_string: None,
+            number_format_id: None,
             font: None,
             fill: None,
             border: None,
@@ -174,6 +233,7 @@ impl CellStyle {
     pub fn is_default(&self) -> bool {
         self.number_format == CellFormat::Other
             && self.format_string.is_none()
+            && matches!(self.number_format_id, None | Some(0))
             && self.font.is_none()
             && self.fill.is_none()
             && self.border.is_none()
@@ -186,6 +246,58 @@ impl CellStyle {
     pub fn number_format(&self) -> &CellFormat {
         &self.number_format
     }
+
+    /// Get the number format code, e.g. `0.00%`
+    ///
+    /// This is the [`format_string`](Self::format_string) of custom formats
+    /// and the code of the [`number_format_id`](Self::number_format_id) of
+    /// builtin formats. Styles with neither fall back to `m/d/yyyy` and
+    /// `[h]:mm:ss` for date and duration formats and to `General` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use calamine::CellStyle;
+    ///
+    /// let style = CellStyle {
+    ///     number_format_id: Some(10),
+    ///     ..CellStyle::default()
+    /// };
+    /// assert_eq!(style.format_code(), "0.00%");
+    /// assert_eq!(CellStyle::default().format_code(), "General");
+    /// ```
+    pub fn format_code(&self) -> &str {
+        crate::number_format::style_format_code(self)
+    }
+
+    /// Get the display category of the number format.
+    ///
+    /// The category is derived from the format string of custom formats. The
+    /// format string of builtin formats isn't kept: builtin date and duration
+    /// formats are categorized as [`FormatCategory::DateTime`] and
+    /// [`FormatCategory::Time`], other builtin formats as
+    /// [`FormatCategory::General`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use calamine::{CellStyle, FormatCategory};
+    ///
+    /// let style = CellStyle {
+    ///     format_string: Some("0.00%".into()),
+    ///     ..CellStyle::default()
+    /// };
+    /// assert_eq!(style.format_category(), FormatCategory::Percent);
+    /// assert_eq!(CellStyle::default().format_category(), FormatCategory::General);
+    /// ```
+    pub fn format_category(&self) -> FormatCategory {
+        match (&self.format_string, &self.number_format) {
+            (Some(format), _) => format_category(format),
+            (None, CellFormat::DateTime) => FormatCategory::DateTime,
+            (None, CellFormat::TimeDelta) => FormatCategory::Time,
+            (None, CellFormat::Other) => FormatCategory::General,
+        }
+    }
 }
 
 /// Font formatting information
@@ -379,7 +491,9 @@ pub enum Color {
     },
     /// Indexed color
     ///
-    /// References a color from Excel's built-in color palette (0-based index).
+    /// References a color from the workbook's indexed color palette (0-based index).
+    /// Workbooks can redefine the palette, otherwise Excel's built-in palette is used,
+    /// see [`builtin_indexed_color`] and [`Xlsx::indexed_color`](crate::Xlsx::indexed_color).
     Indexed(u32),
     /// Automatic color
     ///
@@ -387,6 +501,92 @@ pub enum Color {
     Auto,
 }
 
+/// Excel's default indexed color palette.
+///
+/// Used to resolve [`Color::Indexed`] values when a workbook does not define
+/// a custom palette with `<colors><indexedColors>` in `styles.xml`. Its
+/// entries are opaque [`Color::Argb`] colors, like the custom palette ones.
+///
+/// # References
+///
+/// - ECMA-376 Part 1, Section 18.8.27 (indexedColors)
+pub(crate) static BUILTIN_INDEXED_COLORS: [Color; 64] = [
+    opaque(0x000000),
+    opaque(0xFFFFFF),
+    opaque(0xFF0000),
+    opaque(0x00FF00),
+    opaque(0x0000FF),
+    opaque(0xFFFF00),
+    opaque(0xFF00FF),
+    opaque(0x00FFFF),
+    opaque(0x000000),
+    opaque(0xFFFFFF),
+    opaque(0xFF0000),
+    opaque(0x00FF00),
+    opaque(0x0000FF),
+    opaque(0xFFFF00),
+    opaque(0xFF00FF),
+    opaque(0x00FFFF),
+    opaque(0x800000),
+    opaque(0x008000),
+    opaque(0x000080),
+    opaque(0x808000),
+    opaque(0x800080),
+    opaque(0x008080),
+    opaque(0xC0C0C0),
+    opaque(0x808080),
+    opaque(0x9999FF),
+    opaque(0x993366),
+    opaque(0xFFFFCC),
+    opaque(0xCCFFFF),
+    opaque(0x660066),
+    opaque(0xFF8080),
+    opaque(0x0066CC),
+    opaque(0xCCCCFF),
+    opaque(0x000080),
+    opaque(0xFF00FF),
+    opaque(0xFFFF00),
+    opaque(0x00FFFF),
+    opaque(0x800080),
+    opaque(0x800000),
+    opaque(0x008080),
+    opaque(0x0000FF),
+    opaque(0x00CCFF),
+    opaque(0xCCFFFF),
+    opaque(0xCCFFCC),
+    opaque(0xFFFF99),
+    opaque(0x99CCFF),
+    opaque(0xFF99CC),
+    opaque(0xCC99FF),
+    opaque(0xFFCC99),
+    opaque(0x3366FF),
+    opaque(0x33CCCC),
+    opaque(0x99CC00),
+    opaque(0xFFCC00),
+    opaque(0xFF9900),
+    opaque(0xFF6600),
+    opaque(0x666699),
+    opaque(0x969696),
+    opaque(0x003366),
+    opaque(0x339966),
+    opaque(0x003300),
+    opaque(0x333300),
+    opaque(0x993300),
+    opaque(0x993366),
+    opaque(0x333399),
+    opaque(0x333333),
+];
+
+/// Opaque ARGB color, as custom `<indexedColors>` palette entries are read
+const fn opaque(value: u32) -> Color {
+    Color::Argb {
+        a: 0xFF,
+        r: (value >> 16) as u8,
+        g: (value >> 8) as u8,
+        b: value as u8,
+    }
+}
+
 /// Detect the number format type from a custom format string
 ///
 /// Analyzes an Excel format string to determine its category (DateTime, Currency, etc.).
@@ -453,6 +653,110 @@ pub fn detect_custom_number_format(format: &str) -> CellFormat {
     CellFormat::Other
 }
 
+/// Classify a number format string into a [`FormatCategory`]
+///
+/// Only the first section of the format is considered. Dates and durations
+/// are detected with [`detect_custom_number_format`].
+fn format_category(format: &str) -> FormatCategory {
+    let section = format.split(';').next().unwrap_or_default();
+    if section.trim().eq_ignore_ascii_case("general") {
+        return FormatCategory::General;
+    }
+
+    let mut escaped = false;
+    let mut is_quote = false;
+    let mut in_brackets = false;
+    let mut bracket = String::new();
+    let (mut date, mut time, mut minutes) = (false, false, false);
+    let (mut percent, mut currency, mut scientific, mut slash) = (false, false, false, false);
+    let (mut text, mut digits) = (false, false);
+    let mut chars = section.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            _ if escaped => {
+                escaped = false;
+                currency |= is_currency_symbol(c);
+            }
+            '"' => is_quote = !is_quote,
+            _ if is_quote => currency |= is_currency_symbol(c),
+            '\\' => escaped = true,
+            // the next character is a padding or a repeated fill character
+            '_' | '*' => {
+                chars.next();
+            }
+            '[' => {
+                in_brackets = true;
+                bracket.clear();
+            }
+            ']' if in_brackets => {
+                in_brackets = false;
+                // `[$€-407]` is a currency, `[$-409]` only a locale
+                if let Some(symbol) = bracket.strip_prefix('$') {
+                    currency |= !symbol.starts_with('-') && !symbol.is_empty();
+                }
+            }
+            _ if in_brackets => bracket.push(c),
+            'd' | 'D' | 'y' | 'Y' => date = true,
+            'h' | 'H' | 's' | 'S' => time = true,
+            'm' | 'M' => minutes = true,
+            'a' | 'A' => {
+                let rest = section[i..].to_ascii_uppercase();
+                if let Some(len) = ["AM/PM", "A/P"]
+                    .iter()
+                    .find(|ampm| rest.starts_with(*ampm))
+                    .map(|ampm| ampm.len())
+                {
+                    time = true;
+                    chars.nth(len - 2);
+                }
+            }
+            '%' => percent = true,
+            'e' | 'E' if matches!(chars.peek(), Some((_, '+' | '-'))) => scientific = true,
+            '/' => slash = true,
+            '@' => text = true,
+            '0' | '#' | '?' => digits = true,
+            _ => currency |= is_currency_symbol(c),
+        }
+    }
+
+    match detect_custom_number_format(format) {
+        CellFormat::TimeDelta => return FormatCategory::Time,
+        CellFormat::DateTime => {
+            // `m` is a month in a date and minutes next to hours or seconds
+            let date = date || (minutes && !time);
+            return match (date, time) {
+                (true, true) => FormatCategory::DateTime,
+                (false, true) => FormatCategory::Time,
+                _ => FormatCategory::Date,
+            };
+        }
+        CellFormat::Other => (),
+    }
+    if text {
+        FormatCategory::Text
+    } else if percent {
+        FormatCategory::Percent
+    } else if scientific {
+        FormatCategory::Scientific
+    } else if slash && digits {
+        FormatCategory::Fraction
+    } else if currency {
+        FormatCategory::Currency
+    } else if digits {
+        FormatCategory::Number
+    } else {
+        FormatCategory::General
+    }
+}
+
+/// Check if a character is a currency symbol
+fn is_currency_symbol(c: char) -> bool {
+    matches!(
+        c,
+        '$' | '€' | '£' | '¥' | '₹' | '₩' | '₽' | '¢' | '₣' | '₺' | '₪'
+    )
+}
+
 /// Check excel number format type from format string and create appropriate CellFormat
 /// with interned format string for custom formats
 pub fn detect_custom_number_format_with_interner(
@@ -511,6 +815,26 @@ pub fn builtin_format_by_code(code: u16) -> CellFormat {
     }
 }
 
+/// Get a color of Excel's default indexed color palette
+///
+/// Returns `None` for indexes outside of the 64 palette entries, including the
+/// system foreground (64) and background (65) colors.
+///
+/// # Examples
+///
+/// ```
+/// use calamine::{builtin_indexed_color, Color};
+///
+/// assert_eq!(
+///     builtin_indexed_color(2),
+///     Some(Color::Argb { a: 255, r: 255, g: 0, b: 0 })
+/// );
+/// assert_eq!(builtin_indexed_color(64), None);
+/// ```
+pub fn builtin_indexed_color(index: u32) -> Option<Color> {
+    BUILTIN_INDEXED_COLORS.get(index as usize).cloned()
+}
+
 // convert i64 to date, if format == Date
 pub fn format_excel_i64(value: i64, format: Option<&CellFormat>, is_1904: bool) -> Data {
     match format {
@@ -65,6 +65,7 @@ mod cfb;
 mod conditional_formatting;
 mod datatype;
 mod formats;
+mod number_format;
 mod ods;
 mod xls;
 mod xlsb;
@@ -77,10 +78,12 @@ pub mod vba;
 
 use serde::de::{Deserialize, DeserializeOwned, Deserializer};
 use std::borrow::Cow;
-use std::cmp::{max, min};
+use std::cmp::{max, min, Ordering};
+use std::collections::HashMap;
 use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek};
+use std::hash::Hash;
+use std::io::{BufReader, Read, Seek, Write};
 use std::ops::{Index, IndexMut};
 use std::path::Path;
 
@@ -92,14 +95,18 @@ pub use crate::conditional_formatting::{
     DifferentialFont, DifferentialFormat, DifferentialNumberFormat, DifferentialProtection,
     IconSet, IconSetType, PatternFill, RuleScope, TimePeriod,
 };
-pub use crate::datatype::{Data, DataRef, DataType, ExcelDateTime, ExcelDateTimeType};
+pub use crate::datatype::{
+    CoerceError, Data, DataRef, DataType, DataTypeKind, ExcelDateTime, ExcelDateTimeType,
+};
 pub use crate::de::{DeError, RangeDeserializer, RangeDeserializerBuilder, ToCellDeserializer};
 pub use crate::errors::Error;
 pub use crate::formats::{
-    builtin_format_by_code, builtin_format_by_id, detect_custom_number_format,
-    detect_custom_number_format_with_interner, Alignment, Border, BorderSide, CellFormat,
-    CellStyle, Color, Fill, Font, FormatStringInterner, PatternType, UnderlineStyle,
+    builtin_format_by_code, builtin_format_by_id, builtin_indexed_color,
+    detect_custom_number_format, detect_custom_number_format_with_interner, Alignment, ApplyFlags,
+    Border, BorderSide, CellFormat, CellStyle, Color, Fill, Font, FormatCategory,
+    FormatStringInterner, PatternType, UnderlineStyle,
 };
+pub use crate::number_format::{format_value, FloatFormat};
 pub use crate::ods::{Ods, OdsError};
 pub use crate::theme::{
     ColorScheme, EffectStyle, FillStyle, FontScheme, FormatScheme, LineStyle, Theme, ThemeFont,
@@ -107,8 +114,12 @@ pub use crate::theme::{
 pub use crate::xls::{Xls, XlsError, XlsOptions};
 pub use crate::xlsb::{Xlsb, XlsbError};
 pub use crate::xlsx::{
-    ColumnDefinition, ColumnWidths, RowDefinition, RowDefinitions, SheetFormatProperties, Xlsx,
-    XlsxError,
+    parse_sqref, AnchorKind, CellResultType, ChartInfo, ChartType, ColumnDefinition, ColumnProfile,
+    ColumnWidths, CustomSheetView, DefinedName, ErrorPolicy, NamedStyle, PaneState, ProtectedRange,
+    ReaderBuffers, Relationship, RowDefinition, RowDefinitions, SeriesRef, SheetEvent, SheetEvents,
+    SheetFormatProperties, SheetLayout, SheetPane, SheetView, SheetViewType, Sparkline,
+    SparklineGroup, SparklineType, TableStyle, TableStyleElement, TableStyleInfo, ThreadedComment,
+    WorkbookDiff, WorkbookProtection, WorksheetPicture, Xlsx, XlsxError, XlsxOptions,
 };
 
 use crate::vba::VbaProject;
@@ -116,7 +127,7 @@ use crate::vba::VbaProject;
 // https://msdn.microsoft.com/en-us/library/office/ff839168.aspx
 /// An enum to represent all different errors that can appear as
 /// a value in a worksheet cell
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CellErrorType {
     /// Division by 0 error
     Div0,
@@ -134,23 +145,45 @@ pub enum CellErrorType {
     Value,
     /// Getting data
     GettingData,
+    /// Dynamic array formula cannot spill into its range
+    Spill,
+    /// Calculation engine error, e.g. an empty array
+    Calc,
 }
 
-impl fmt::Display for CellErrorType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+impl CellErrorType {
+    /// Get the error value as written by Excel, e.g. `#DIV/0!`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use calamine::CellErrorType;
+    ///
+    /// assert_eq!(CellErrorType::Div0.as_excel_string(), "#DIV/0!");
+    /// assert_eq!(CellErrorType::Spill.as_excel_string(), "#SPILL!");
+    /// ```
+    pub fn as_excel_string(&self) -> &'static str {
         match *self {
-            CellErrorType::Div0 => write!(f, "#DIV/0!"),
-            CellErrorType::NA => write!(f, "#N/A"),
-            CellErrorType::Name => write!(f, "#NAME?"),
-            CellErrorType::Null => write!(f, "#NULL!"),
-            CellErrorType::Num => write!(f, "#NUM!"),
-            CellErrorType::Ref => write!(f, "#REF!"),
-            CellErrorType::Value => write!(f, "#VALUE!"),
-            CellErrorType::GettingData => write!(f, "#DATA!"),
+            CellErrorType::Div0 => "#DIV/0!",
+            CellErrorType::NA => "#N/A",
+            CellErrorType::Name => "#NAME?",
+            CellErrorType::Null => "#NULL!",
+            CellErrorType::Num => "#NUM!",
+            CellErrorType::Ref => "#REF!",
+            CellErrorType::Value => "#VALUE!",
+            CellErrorType::GettingData => "#DATA!",
+            CellErrorType::Spill => "#SPILL!",
+            CellErrorType::Calc => "#CALC!",
         }
     }
 }
 
+impl fmt::Display for CellErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_excel_string())
+    }
+}
+
 /// A struct that combines cell data with its formatting information
 #[derive(Debug, Clone)]
 pub struct DataWithFormatting {
@@ -405,6 +438,34 @@ impl Dimensions {
     }
 }
 
+/// Merged regions overlapping a [`Range`], see [`Range::merge_map()`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MergeMap {
+    merges: Vec<Dimensions>,
+}
+
+impl MergeMap {
+    /// Get the top-left (anchor) cell of the merged region containing `pos`
+    ///
+    /// Returns `None` if `pos` is not part of any merged region.
+    pub fn anchor_of(&self, pos: (u32, u32)) -> Option<(u32, u32)> {
+        self.merges
+            .iter()
+            .find(|m| m.contains(pos.0, pos.1))
+            .map(|m| m.start)
+    }
+
+    /// Check if `pos` is part of a merged region, including its anchor cell
+    pub fn is_merged(&self, pos: (u32, u32)) -> bool {
+        self.anchor_of(pos).is_some()
+    }
+
+    /// The merged regions overlapping the range
+    pub fn merges(&self) -> &[Dimensions] {
+        &self.merges
+    }
+}
+
 /// Common file metadata
 ///
 /// Depending on file type, some extra information may be stored
@@ -493,6 +554,17 @@ pub enum HeaderRow {
     Row(u32),
 }
 
+impl HeaderRow {
+    /// Whether cells of `row` are part of the range, i.e. are not above the
+    /// header row
+    pub(crate) fn includes(self, row: u32) -> bool {
+        match self {
+            HeaderRow::FirstNonEmptyRow => true,
+            HeaderRow::Row(header_row) => row >= header_row,
+        }
+    }
+}
+
 // FIXME `Reader` must only be seek `Seek` for `Xls::xls`. Because of the present API this limits
 // the kinds of readers (other) data in formats can be read from.
 /// A trait to share spreadsheets reader functions across different `FileType`s
@@ -728,6 +800,22 @@ where
     R::new(rs)
 }
 
+/// Open a password protected xlsx file.
+///
+/// Workbooks encrypted with the agile encryption of Excel 2010 and later are
+/// decrypted in memory, then read as any other xlsx file. Workbooks which are
+/// not encrypted are read as is, ignoring the password.
+///
+/// Fails with [`XlsxError::Password`] if the password is wrong.
+#[cfg(feature = "encryption")]
+pub fn open_workbook_with_password<P: AsRef<Path>>(
+    path: P,
+    password: &str,
+) -> Result<Xlsx<std::io::Cursor<Vec<u8>>>, XlsxError> {
+    let data = xlsx::decrypt_workbook(std::fs::read(path)?, password)?;
+    Xlsx::new(std::io::Cursor::new(data))
+}
+
 /// A trait to constrain cells
 pub trait CellType: Default + Clone + PartialEq {}
 
@@ -736,6 +824,8 @@ impl<'a> CellType for DataRef<'a> {}
 impl CellType for String {}
 impl CellType for usize {} // for tests
 impl CellType for CellStyle {}
+impl CellType for (String, CellResultType) {}
+impl CellType for (Data, String) {}
 
 /// A struct to hold cell position and value
 #[derive(Debug, Clone)]
@@ -766,6 +856,144 @@ impl<T: CellType> Cell<T> {
     }
 }
 
+/// A cell whose value differs between two ranges, see [`Range::diff()`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellDiff<T> {
+    /// Absolute position of the cell (row, column)
+    pub pos: (u32, u32),
+    /// Value of the cell in the first range
+    pub left: T,
+    /// Value of the cell in the second range
+    pub right: T,
+}
+
+/// Summary statistics of a column of a range, see [`Range::column_stats()`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ColumnStats {
+    /// Number of non-empty cells
+    pub count: usize,
+    /// Number of integer and float cells
+    pub numeric_count: usize,
+    /// Smallest numeric value, `None` without numeric cells
+    pub min: Option<f64>,
+    /// Largest numeric value, `None` without numeric cells
+    pub max: Option<f64>,
+    /// Sum of the numeric values
+    pub sum: f64,
+    /// Mean of the numeric values, `None` without numeric cells
+    pub mean: Option<f64>,
+    /// Number of empty cells
+    pub null_count: usize,
+}
+
+impl ColumnStats {
+    /// Add a cell to the statistics
+    fn add(&mut self, value: &Data) {
+        let value = match *value {
+            Data::Empty => {
+                self.null_count += 1;
+                return;
+            }
+            Data::Int(v) => v as f64,
+            Data::Float(v) => v,
+            _ => {
+                self.count += 1;
+                return;
+            }
+        };
+        self.count += 1;
+        self.numeric_count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self.mean = Some(self.sum / self.numeric_count as f64);
+    }
+}
+
+/// Aggregation of the values of a group, see [`Range::group_by()`]
+///
+/// Only [`Data::Int`] and [`Data::Float`] values are aggregated, except by
+/// [`AggregationFunction::Count`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregationFunction {
+    /// Sum of the numbers, as a [`Data::Float`]
+    Sum,
+    /// Number of non-empty values, as a [`Data::Int`]
+    Count,
+    /// Number of numbers, as a [`Data::Int`]
+    CountNums,
+    /// Mean of the numbers, as a [`Data::Float`], or `#DIV/0!` without numbers
+    Average,
+    /// Smallest number, as a [`Data::Float`], or [`Data::Empty`] without numbers
+    Min,
+    /// Largest number, as a [`Data::Float`], or [`Data::Empty`] without numbers
+    Max,
+}
+
+impl AggregationFunction {
+    /// Aggregate values from their statistics
+    fn apply(self, stats: &ColumnStats) -> Data {
+        let float_or = |value: Option<f64>, default| value.map_or(default, Data::Float);
+        match self {
+            AggregationFunction::Sum => Data::Float(stats.sum),
+            AggregationFunction::Count => Data::Int(stats.count as i64),
+            AggregationFunction::CountNums => Data::Int(stats.numeric_count as i64),
+            AggregationFunction::Average => float_or(stats.mean, Data::Error(CellErrorType::Div0)),
+            AggregationFunction::Min => float_or(stats.min, Data::Empty),
+            AggregationFunction::Max => float_or(stats.max, Data::Empty),
+        }
+    }
+}
+
+/// Options of the CSV output of [`Range::write_csv()`] and
+/// [`Xlsx::worksheet_to_csv()`]
+///
+/// Fields holding the delimiter, a double quote or a line break are quoted,
+/// as per RFC 4180.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvOptions {
+    /// Field delimiter, `,` by default
+    pub delimiter: char,
+    /// End lines with `\r\n`, as per RFC 4180, rather than `\n`, `true` by
+    /// default
+    pub crlf: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            crlf: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Write a field, quoted if needed
+    pub(crate) fn write_field<W: Write>(&self, w: &mut W, field: &str) -> std::io::Result<()> {
+        if field.contains([self.delimiter, '"', '\r', '\n']) {
+            write!(w, "\"{}\"", field.replace('"', "\"\""))
+        } else {
+            w.write_all(field.as_bytes())
+        }
+    }
+
+    /// Write the end of a line
+    pub(crate) fn write_line_end<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        w.write_all(if self.crlf { b"\r\n" } else { b"\n" })
+    }
+}
+
+/// Options of [`Range::to_html()`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlOptions<'a> {
+    /// Render the first row as header cells (`<th>`)
+    pub header: bool,
+    /// Styles of the cells, in the row-major order of [`Range::cells()`],
+    /// whose fill and font colors are applied as inline styles
+    pub formats: &'a [CellStyle],
+}
+
 /// A struct which represents an area of cells and the data within it.
 ///
 /// Ranges are used by `calamine` to represent an area of data in a worksheet. A
@@ -1123,6 +1351,67 @@ impl<T: CellType> Range<T> {
         }
     }
 
+    /// Creates a `Range` from `(position, value)` pairs in any order.
+    ///
+    /// The range is the bounding box of the positions, allocated once. Cells
+    /// without a value are filled with default values of type `T`, i.e.
+    /// [`Data::Empty`] for a `Range<Data>`. If a position appears more than
+    /// once, the last value wins.
+    ///
+    /// Unlike [`Range::from_sparse()`], the cells don't need to be sorted by
+    /// row.
+    ///
+    /// # Parameters
+    ///
+    /// - `cells`: The absolute `(row, column)` positions and their values.
+    ///
+    /// # Examples
+    ///
+    /// An example of creating a calamine `Range` from scattered cells.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_from_cells.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let cells = vec![
+    ///     ((4, 1), Data::Int(3)),
+    ///     ((1, 3), Data::Int(1)),
+    ///     ((2, 0), Data::Int(2)),
+    /// ];
+    ///
+    /// let range = Range::from_cells(cells);
+    ///
+    /// assert_eq!(range.start(), Some((1, 0)));
+    /// assert_eq!(range.end(), Some((4, 3)));
+    /// assert_eq!(range.get_value((2, 0)), Some(&Data::Int(2)));
+    /// assert_eq!(range.get_value((2, 1)), Some(&Data::Empty));
+    /// assert_eq!(range.used_cells().count(), 3);
+    /// ```
+    ///
+    pub fn from_cells<I>(cells: I) -> Range<T>
+    where
+        I: IntoIterator<Item = ((u32, u32), T)>,
+    {
+        let cells: Vec<_> = cells.into_iter().collect();
+        let Some(&((row, col), _)) = cells.first() else {
+            return Range::empty();
+        };
+        let (mut start, mut end) = ((row, col), (row, col));
+        for &((row, col), _) in &cells {
+            start = (min(row, start.0), min(col, start.1));
+            end = (max(row, end.0), max(col, end.1));
+        }
+        let cols = (end.1 - start.1 + 1) as usize;
+        let rows = (end.0 - start.0 + 1) as usize;
+        let mut inner = vec![T::default(); rows.saturating_mul(cols)];
+        for ((row, col), val) in cells {
+            let idx = ((row - start.0) as usize).saturating_mul(cols) + (col - start.1) as usize;
+            inner[idx] = val;
+        }
+        Range { start, end, inner }
+    }
+
     /// Set a value at an absolute position in a `Range`.
     ///
     /// This method sets a value in the range at the given absolute position
@@ -1297,6 +1586,90 @@ impl<T: CellType> Range<T> {
         }
     }
 
+    /// Get a row of a `Range` as a slice, using an absolute row index.
+    ///
+    /// The row index is relative to `A1`, like the positions used by
+    /// [`Range::get_value()`]. Cells without a value in the row hold the
+    /// default value of the type. If the row is outside the range an empty
+    /// slice is returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The absolute row index, zero indexed from `A1`.
+    ///
+    /// # Examples
+    ///
+    /// An example of getting a row of a calamine `Range`.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_row.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let mut range = Range::new((1, 1), (2, 3));
+    /// range.set_value((2, 1), Data::Int(1));
+    /// range.set_value((2, 3), Data::Int(3));
+    ///
+    /// assert_eq!(range.row(2), &[Data::Int(1), Data::Empty, Data::Int(3)]);
+    ///
+    /// // Rows outside the range are empty.
+    /// assert!(range.row(0).is_empty());
+    /// ```
+    ///
+    pub fn row(&self, row: u32) -> &[T] {
+        if self.inner.is_empty() || row < self.start.0 || row > self.end.0 {
+            return &[];
+        }
+        let width = self.width();
+        let start = (row - self.start.0) as usize * width;
+        &self.inner[start..start + width]
+    }
+
+    /// Get a column of a `Range`, using an absolute column index.
+    ///
+    /// The column index is relative to `A1`, like the positions used by
+    /// [`Range::get_value()`]. Cells without a value in the column hold the
+    /// default value of the type. If the column is outside the range an
+    /// empty vector is returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `col`: The absolute column index, zero indexed from `A1`.
+    ///
+    /// # Examples
+    ///
+    /// An example of getting a column of a calamine `Range`.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_column.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let mut range = Range::new((1, 1), (3, 2));
+    /// range.set_value((1, 2), Data::Int(1));
+    /// range.set_value((3, 2), Data::Int(3));
+    ///
+    /// assert_eq!(
+    ///     range.column(2),
+    ///     vec![&Data::Int(1), &Data::Empty, &Data::Int(3)]
+    /// );
+    ///
+    /// // Columns outside the range are empty.
+    /// assert!(range.column(0).is_empty());
+    /// ```
+    ///
+    pub fn column(&self, col: u32) -> Vec<&T> {
+        if self.inner.is_empty() || col < self.start.1 || col > self.end.1 {
+            return Vec::new();
+        }
+        let width = self.width();
+        self.inner
+            .iter()
+            .skip((col - self.start.1) as usize)
+            .step_by(width)
+            .collect()
+    }
+
     /// Get an iterator over the rows of a `Range`.
     ///
     /// # Examples
@@ -1600,117 +1973,1295 @@ impl<T: CellType> Range<T> {
 
         other
     }
-}
 
-impl<T: CellType + fmt::Display> Range<T> {
-    /// Get headers for a `Range`.
+    /// Get an iterator over sliding windows of rows of the range.
     ///
-    /// This method returns the first row of the range as an optional vector of
-    /// strings. The data type `T` in the range must support the [`ToString`]
-    /// trait.
+    /// Each window is a new `Range` with cloned data, `rows` rows high and as
+    /// wide as the range. Successive windows start one row apart, like
+    /// [`slice::windows()`], so a 5 rows range has three windows of 3 rows.
+    ///
+    /// # Parameters
+    ///
+    /// - `rows`: The height of the windows. No window is returned if it is 0.
+    /// - `partial`: Also return the windows starting in the last `rows - 1`
+    ///   rows, which are cut short by the end of the range. If `false`, only
+    ///   windows of `rows` rows are returned, none if the range is smaller.
     ///
     /// # Examples
     ///
-    /// An example of getting the header row of a calamine `Range`.
+    /// An example of iterating over 2-row windows of a calamine `Range`.
     ///
     /// ```
-    /// # // This code is available in examples/doc_range_headers.rs
+    /// # // This code is available in examples/doc_range_windows.rs
     /// #
     /// use calamine::{Data, Range};
     ///
-    /// // Create a range with some values.
-    /// let mut range = Range::new((0, 0), (5, 2));
-    /// range.set_value((0, 0), Data::String(String::from("a")));
-    /// range.set_value((0, 1), Data::Int(1));
-    /// range.set_value((0, 2), Data::Bool(true));
-    ///
-    /// // Get the headers of the range.
-    /// let headers = range.headers();
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::Int(1)],
+    ///     vec![Data::Int(2)],
+    ///     vec![Data::Int(3)],
+    /// ]);
     ///
+    /// let windows: Vec<_> = range.windows(2, false).map(|w| w.into_2d_vec()).collect();
     /// assert_eq!(
-    ///     headers,
-    ///     Some(vec![
-    ///         String::from("a"),
-    ///         String::from("1"),
-    ///         String::from("true")
-    ///     ])
+    ///     windows,
+    ///     [
+    ///         vec![vec![Data::Int(1)], vec![Data::Int(2)]],
+    ///         vec![vec![Data::Int(2)], vec![Data::Int(3)]],
+    ///     ]
     /// );
+    ///
+    /// // The last window only has the last row.
+    /// let last = range.windows(2, true).last().unwrap();
+    /// assert_eq!(last.start(), Some((2, 0)));
+    /// assert_eq!(last.height(), 1);
     /// ```
     ///
-    pub fn headers(&self) -> Option<Vec<String>> {
-        self.rows()
-            .next()
-            .map(|row| row.iter().map(ToString::to_string).collect())
-    }
-}
-
-/// Implementation of the `Index` trait for `Range` rows.
-///
-/// # Examples
-///
-/// An example of row indexing for a calamine `Range`.
-///
-/// ```
-/// # // This code is available in examples/doc_range_index_row.rs
-/// #
-/// use calamine::{Data, Range};
-///
-/// // Create a range with a value.
-/// let mut range = Range::new((1, 1), (3, 3));
-/// range.set_value((2, 2), Data::Int(123));
-///
-/// // Get the second row via indexing.
-/// assert_eq!(range[1], [Data::Empty, Data::Int(123), Data::Empty]);
-/// ```
-///
-impl<T: CellType> Index<usize> for Range<T> {
-    type Output = [T];
-    fn index(&self, index: usize) -> &[T] {
-        let width = self.width();
-        &self.inner[index * width..(index + 1) * width]
-    }
-}
-
-/// Implementation of the `Index` trait for `Range` cells.
-///
-/// # Examples
-///
-/// An example of cell indexing for a calamine `Range`.
-///
-/// ```
-/// # // This code is available in examples/doc_range_index_cell.rs
-/// #
-/// use calamine::{Data, Range};
-///
-/// // Create a range with a value.
-/// let mut range = Range::new((1, 1), (3, 3));
-/// range.set_value((2, 2), Data::Int(123));
-///
-/// // Get the value via cell indexing.
-/// assert_eq!(range[(1, 1)], Data::Int(123));
-/// ```
-///
-impl<T: CellType> Index<(usize, usize)> for Range<T> {
-    type Output = T;
-    fn index(&self, index: (usize, usize)) -> &T {
-        let (height, width) = self.get_size();
-        assert!(index.1 < width && index.0 < height, "index out of bounds");
-        &self.inner[index.0 * width + index.1]
+    pub fn windows(&self, rows: u32, partial: bool) -> impl Iterator<Item = Range<T>> + '_ {
+        let height = self.height() as u32;
+        let count = match rows {
+            0 => 0,
+            _ if partial => height,
+            _ => height.saturating_sub(rows - 1),
+        };
+        (0..count).map(move |i| {
+            let first = self.start.0 + i;
+            let last = first.saturating_add(rows - 1).min(self.end.0);
+            self.range((first, self.start.1), (last, self.end.1))
+        })
     }
-}
 
-/// Implementation of the `IndexMut` trait for `Range` rows.
-impl<T: CellType> IndexMut<usize> for Range<T> {
-    fn index_mut(&mut self, index: usize) -> &mut [T] {
+    /// Convert a `Range` into a vector of rows.
+    ///
+    /// Each inner vector holds the cells of one row of the range, in column
+    /// order. Since a `Range` is always rectangular every row has the same
+    /// length, which is the [`Range::width()`] of the range. The start
+    /// position of the range is not kept. An empty range returns an empty
+    /// vector.
+    ///
+    /// # Examples
+    ///
+    /// An example of converting a calamine `Range` into a 2D vector.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_into_2d_vec.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let mut range = Range::new((1, 1), (2, 2));
+    /// range.set_value((1, 1), Data::Int(1));
+    /// range.set_value((2, 2), Data::Int(4));
+    ///
+    /// assert_eq!(
+    ///     range.into_2d_vec(),
+    ///     vec![
+    ///         vec![Data::Int(1), Data::Empty],
+    ///         vec![Data::Empty, Data::Int(4)],
+    ///     ]
+    /// );
+    /// ```
+    ///
+    pub fn into_2d_vec(self) -> Vec<Vec<T>> {
         let width = self.width();
-        &mut self.inner[index * width..(index + 1) * width]
+        if width == 0 {
+            return Vec::new();
+        }
+        let mut rows = Vec::with_capacity(self.height());
+        let mut inner = self.inner.into_iter();
+        loop {
+            let row: Vec<T> = inner.by_ref().take(width).collect();
+            if row.is_empty() {
+                break;
+            }
+            rows.push(row);
+        }
+        rows
     }
-}
 
-/// Implementation of the `IndexMut` trait for `Range` cells.
-///
-/// # Examples
-///
+    /// Creates a `Range` from a vector of rows.
+    ///
+    /// The range starts at `(0, 0)`, i.e. cell `A1`. Its height is the number
+    /// of rows and its width is the length of the longest row. Shorter rows
+    /// are padded on the right with default values of type `T`, i.e.
+    /// [`Data::Empty`] for a `Range<Data>`, so ragged input always produces a
+    /// rectangular range.
+    ///
+    /// Returns an empty range if there are no rows or if all the rows are
+    /// empty.
+    ///
+    /// # Parameters
+    ///
+    /// - `rows`: The cell values, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// An example of creating a calamine `Range` from a ragged 2D vector.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_from_2d_vec.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let rows = vec![
+    ///     vec![Data::Int(1)],
+    ///     vec![Data::Int(2), Data::Int(3), Data::Int(4)],
+    /// ];
+    ///
+    /// let range = Range::from_2d_vec(rows);
+    ///
+    /// assert_eq!(range.get_size(), (2, 3));
+    /// assert_eq!(range.end(), Some((1, 2)));
+    ///
+    /// // The first row is padded with empty cells.
+    /// assert_eq!(range[0], [Data::Int(1), Data::Empty, Data::Empty]);
+    /// ```
+    ///
+    pub fn from_2d_vec(rows: Vec<Vec<T>>) -> Range<T> {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return Range::empty();
+        }
+        let height = rows.len();
+        let mut inner = Vec::with_capacity(width.saturating_mul(height));
+        for mut row in rows {
+            row.resize(width, T::default());
+            inner.extend(row);
+        }
+        Range {
+            start: (0, 0),
+            end: (height as u32 - 1, width as u32 - 1),
+            inner,
+        }
+    }
+
+    /// Build a [`MergeMap`] for the cells of this `Range`.
+    ///
+    /// Excel only stores the value of a merged region in its top-left
+    /// "anchor" cell, the other cells of the region being empty. The
+    /// returned map can be queried for any absolute position of the range to
+    /// know whether it belongs to a merged region, and where that region's
+    /// value is stored.
+    ///
+    /// The `merges` are usually obtained from the reader, for example with
+    /// [`Xlsx::worksheet_merge_cells()`]. Merged regions that don't overlap
+    /// the range are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `merges`: The merged regions of the worksheet the range was read
+    ///   from.
+    ///
+    /// # Examples
+    ///
+    /// An example of finding the value displayed in a merged cell.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_merge_map.rs
+    /// #
+    /// use calamine::{Data, Dimensions, Range};
+    ///
+    /// let mut range = Range::new((0, 0), (2, 2));
+    /// range.set_value((1, 1), Data::String("merged".to_string()));
+    ///
+    /// // B2:C3 is merged.
+    /// let merges = [Dimensions::new((1, 1), (2, 2))];
+    /// let merge_map = range.merge_map(&merges);
+    ///
+    /// assert!(merge_map.is_merged((2, 2)));
+    /// assert!(!merge_map.is_merged((0, 0)));
+    ///
+    /// let anchor = merge_map.anchor_of((2, 2)).unwrap();
+    /// assert_eq!(anchor, (1, 1));
+    /// assert_eq!(
+    ///     range.get_value(anchor),
+    ///     Some(&Data::String("merged".to_string()))
+    /// );
+    /// ```
+    ///
+    pub fn merge_map(&self, merges: &[Dimensions]) -> MergeMap {
+        let merges = match (self.start(), self.end()) {
+            (Some(start), Some(end)) => merges
+                .iter()
+                .filter(|m| {
+                    m.start.0 <= end.0
+                        && m.end.0 >= start.0
+                        && m.start.1 <= end.1
+                        && m.end.1 >= start.1
+                })
+                .copied()
+                .collect(),
+            _ => Vec::new(),
+        };
+        MergeMap { merges }
+    }
+
+    /// Copy the value of each merged region into all the cells of the region.
+    ///
+    /// Excel only stores the value of a merged region in its top-left
+    /// "anchor" cell. This method copies that value into every other cell of
+    /// the region that is inside the range, so that, for example, a header
+    /// merged over several columns is repeated above each of them.
+    ///
+    /// Merged regions whose anchor cell is outside the range are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `merges`: The merged regions of the worksheet the range was read
+    ///   from, for example from [`Xlsx::worksheet_merge_cells()`].
+    ///
+    /// # Examples
+    ///
+    /// An example of repeating a merged header over its columns.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_fill_merges.rs
+    /// #
+    /// use calamine::{Data, Dimensions, Range};
+    ///
+    /// let mut range = Range::new((0, 0), (1, 2));
+    /// range.set_value((0, 0), Data::String("Sales".to_string()));
+    /// range.set_value((1, 0), Data::String("Q1".to_string()));
+    ///
+    /// // A1:C1 is merged.
+    /// range.fill_merges(&[Dimensions::new((0, 0), (0, 2))]);
+    ///
+    /// let sales = Data::String("Sales".to_string());
+    /// assert_eq!(range[0], [sales.clone(), sales.clone(), sales]);
+    /// assert_eq!(range.get_value((1, 1)), Some(&Data::Empty));
+    /// ```
+    ///
+    pub fn fill_merges(&mut self, merges: &[Dimensions]) {
+        let (Some(start), Some(end)) = (self.start(), self.end()) else {
+            return;
+        };
+        let width = self.width();
+        for merge in merges {
+            let Some(value) = self.get_value(merge.start).cloned() else {
+                continue;
+            };
+            for row in merge.start.0..=merge.end.0.min(end.0) {
+                for col in merge.start.1..=merge.end.1.min(end.1) {
+                    let idx = (row - start.0) as usize * width + (col - start.1) as usize;
+                    self.inner[idx] = value.clone();
+                }
+            }
+        }
+    }
+
+    /// Get a copy of the range with the merged regions filled.
+    ///
+    /// This is the non-mutating version of [`Range::fill_merges()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `merges`: The merged regions of the worksheet the range was read
+    ///   from.
+    ///
+    /// # Examples
+    ///
+    /// An example of filling a vertically merged cell.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_with_merges_filled.rs
+    /// #
+    /// use calamine::{Data, Dimensions, Range};
+    ///
+    /// let mut range = Range::new((0, 0), (2, 0));
+    /// range.set_value((0, 0), Data::Int(7));
+    ///
+    /// // A1:A2 is merged.
+    /// let filled = range.with_merges_filled(&[Dimensions::new((0, 0), (1, 0))]);
+    ///
+    /// assert_eq!(filled.get_value((1, 0)), Some(&Data::Int(7)));
+    /// assert_eq!(filled.get_value((2, 0)), Some(&Data::Empty));
+    ///
+    /// // The original range is unchanged.
+    /// assert_eq!(range.get_value((1, 0)), Some(&Data::Empty));
+    /// ```
+    ///
+    pub fn with_merges_filled(&self, merges: &[Dimensions]) -> Range<T> {
+        let mut range = self.clone();
+        range.fill_merges(merges);
+        range
+    }
+
+    /// Get an iterator over all the cells of the range, with the merged
+    /// regions filled.
+    ///
+    /// This iterates like [`Range::cells()`], with relative row and column
+    /// indices, except that the cells of a merged region yield a copy of the
+    /// value of its anchor cell. It gives the same values as
+    /// [`Range::with_merges_filled()`] without copying the range.
+    ///
+    /// Merged regions whose anchor cell is outside the range are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `merges`: The merged regions of the worksheet the range was read
+    ///   from.
+    ///
+    /// # Examples
+    ///
+    /// An example of reading a header merged over two columns.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_iter_effective.rs
+    /// #
+    /// use calamine::{Data, Dimensions, Range};
+    ///
+    /// let mut range = Range::new((0, 0), (1, 1));
+    /// range.set_value((0, 0), Data::String("Sales".to_string()));
+    /// range.set_value((1, 0), Data::Int(1));
+    ///
+    /// // A1:B1 is merged.
+    /// let merges = [Dimensions::new((0, 0), (0, 1))];
+    /// let cells: Vec<_> = range.iter_effective(&merges).collect();
+    ///
+    /// let sales = Data::String("Sales".to_string());
+    /// assert_eq!(cells[0], (0, 0, sales.clone()));
+    /// assert_eq!(cells[1], (0, 1, sales));
+    /// assert_eq!(cells[3], (1, 1, Data::Empty));
+    /// ```
+    ///
+    pub fn iter_effective(
+        &self,
+        merges: &[Dimensions],
+    ) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        // index of the cell each cell takes its value from
+        let mut sources: Vec<usize> = (0..self.inner.len()).collect();
+        if let (Some(start), Some(end)) = (self.start(), self.end()) {
+            let width = self.width();
+            let index =
+                |row: u32, col: u32| (row - start.0) as usize * width + (col - start.1) as usize;
+            for merge in merges {
+                if self.get_value(merge.start).is_none() {
+                    continue;
+                }
+                let anchor = index(merge.start.0, merge.start.1);
+                for row in merge.start.0..=merge.end.0.min(end.0) {
+                    for col in merge.start.1..=merge.end.1.min(end.1) {
+                        sources[index(row, col)] = anchor;
+                    }
+                }
+            }
+        }
+        let width = self.width();
+        sources
+            .into_iter()
+            .enumerate()
+            .map(move |(i, source)| (i / width, i % width, self.inner[source].clone()))
+    }
+
+    /// Compare the values of two ranges cell by cell.
+    ///
+    /// Cells are matched by absolute position over the area covering both
+    /// ranges. Cells outside a range compare as the default value of type
+    /// `T`, such as [`Data::Empty`], so ranges that only differ by empty
+    /// rows or columns have no differences.
+    ///
+    /// Returns the differing cells in row-major order.
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: The range to compare with.
+    ///
+    /// # Examples
+    ///
+    /// An example of comparing a range with an updated copy.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_diff.rs
+    /// #
+    /// use calamine::{CellDiff, Data, Range};
+    ///
+    /// let old = Range::from_2d_vec(vec![vec![Data::Int(1), Data::Int(2)]]);
+    /// let mut new = old.clone();
+    /// new.set_value((0, 1), Data::Int(3));
+    /// new.set_value((1, 0), Data::Int(4));
+    ///
+    /// let diffs = old.diff(&new);
+    ///
+    /// assert_eq!(
+    ///     diffs,
+    ///     [
+    ///         CellDiff {
+    ///             pos: (0, 1),
+    ///             left: Data::Int(2),
+    ///             right: Data::Int(3)
+    ///         },
+    ///         CellDiff {
+    ///             pos: (1, 0),
+    ///             left: Data::Empty,
+    ///             right: Data::Int(4)
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    ///
+    pub fn diff(&self, other: &Range<T>) -> Vec<CellDiff<T>> {
+        let bounds = [self, other]
+            .into_iter()
+            .filter_map(|range| Some((range.start()?, range.end()?)))
+            .reduce(|(start, end), (other_start, other_end)| {
+                (
+                    (start.0.min(other_start.0), start.1.min(other_start.1)),
+                    (end.0.max(other_end.0), end.1.max(other_end.1)),
+                )
+            });
+        let Some((start, end)) = bounds else {
+            return Vec::new();
+        };
+        let empty = T::default();
+        let mut diffs = Vec::new();
+        for row in start.0..=end.0 {
+            for col in start.1..=end.1 {
+                let left = self.get_value((row, col)).unwrap_or(&empty);
+                let right = other.get_value((row, col)).unwrap_or(&empty);
+                if left != right {
+                    diffs.push(CellDiff {
+                        pos: (row, col),
+                        left: left.clone(),
+                        right: right.clone(),
+                    });
+                }
+            }
+        }
+        diffs
+    }
+}
+
+impl<T: CellType + Eq + Hash> Range<T> {
+    /// Count the occurrences of each distinct value of a column.
+    ///
+    /// The column index is absolute, as in [`Range::column()`]. Cells without
+    /// a value, i.e. holding the default value of type `T` such as
+    /// [`Data::Empty`], are not counted. For a `Range<Data>`, `Int(1)` and
+    /// `Float(1.0)` are different values.
+    ///
+    /// # Parameters
+    ///
+    /// - `col`: The absolute column index, zero indexed from `A1`.
+    ///
+    /// # Examples
+    ///
+    /// An example of counting the distinct values of a column.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_value_counts.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("red".to_string())],
+    ///     vec![Data::String("blue".to_string())],
+    ///     vec![Data::String("red".to_string())],
+    ///     vec![Data::Empty],
+    /// ]);
+    ///
+    /// let counts = range.value_counts(0);
+    ///
+    /// assert_eq!(counts.len(), 2);
+    /// assert_eq!(counts[&Data::String("red".to_string())], 2);
+    /// assert_eq!(counts[&Data::String("blue".to_string())], 1);
+    /// ```
+    ///
+    pub fn value_counts(&self, col: u32) -> HashMap<T, usize> {
+        let empty = T::default();
+        let mut counts = HashMap::new();
+        for value in self.column(col) {
+            if *value != empty {
+                *counts.entry(value.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+
+impl Range<Data> {
+    /// Compare two ranges by the text Excel displays for their cells.
+    ///
+    /// Each value is formatted with the number format code of its cell, so
+    /// cells holding different types or representations of the same value,
+    /// such as `Float(3.0)` and `Int(3)`, compare equal when they display the
+    /// same text. Cells are matched by absolute position and cells displaying
+    /// an empty string are ignored, so ranges that only differ by trailing
+    /// empty rows or columns are equal too.
+    ///
+    /// The code of a cell style, custom or builtin, is given by
+    /// [`CellStyle::format_code`].
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: The range to compare with.
+    /// - `codes_self`: The number format codes of the cells of `self`, in the
+    ///   row-major order of [`Range::cells()`]. Cells without a code use
+    ///   `General`.
+    /// - `codes_other`: The number format codes of the cells of `other`, in
+    ///   the same order.
+    /// - `is_1904`: The date system of the workbook, used by date formats.
+    ///
+    /// # Examples
+    ///
+    /// An example of comparing ranges by their displayed text.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_eq_displayed.rs
+    /// #
+    /// use calamine::{CellStyle, Data, Range};
+    ///
+    /// let a = Range::from_2d_vec(vec![vec![Data::Float(3.0), Data::String("x".to_string())]]);
+    /// let b = Range::from_2d_vec(vec![vec![Data::Int(3), Data::String("x".to_string())]]);
+    ///
+    /// assert_ne!(a.get((0, 0)), b.get((0, 0)));
+    /// assert!(a.eq_displayed(&b, &[], &[], false));
+    ///
+    /// // Formatted with two decimals, 3 is displayed as "3.00".
+    /// let two_decimals = CellStyle {
+    ///     number_format_id: Some(2),
+    ///     ..CellStyle::default()
+    /// };
+    /// assert!(!a.eq_displayed(&b, &[two_decimals.format_code()], &[], false));
+    /// ```
+    ///
+    pub fn eq_displayed(
+        &self,
+        other: &Range<Data>,
+        codes_self: &[&str],
+        codes_other: &[&str],
+        is_1904: bool,
+    ) -> bool {
+        let displayed = |range: &Range<Data>, codes: &[&str]| {
+            let (row_start, col_start) = range.start;
+            range
+                .cells()
+                .enumerate()
+                .filter_map(|(i, (row, col, value))| {
+                    let code = codes.get(i).copied().unwrap_or("General");
+                    let text = number_format::format_value(value, code, is_1904);
+                    let pos = (row_start + row as u32, col_start + col as u32);
+                    (!text.is_empty()).then_some((pos, text))
+                })
+                .collect::<HashMap<_, _>>()
+        };
+        displayed(self, codes_self) == displayed(other, codes_other)
+    }
+
+    /// Locate a table by the keywords of its header row.
+    ///
+    /// The header row is the first row whose string cells contain all the
+    /// keywords, ignoring ASCII case. The table starts at this row, between
+    /// the first and last matching columns, and is extended down and right,
+    /// and left along its header, until it is bounded by empty rows and
+    /// columns. Rows above the header, such as titles, are never included.
+    ///
+    /// Returns `None` if no row contains all the keywords, or if there are no
+    /// keywords.
+    ///
+    /// # Parameters
+    ///
+    /// - `header_keywords`: The text the header cells must contain.
+    ///
+    /// # Examples
+    ///
+    /// An example of locating a table below title rows.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_find_table.rs
+    /// #
+    /// use calamine::{Data, Dimensions, Range};
+    ///
+    /// let text = |s: &str| Data::String(s.to_string());
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![text("Sales report"), Data::Empty, Data::Empty],
+    ///     vec![Data::Empty, Data::Empty, Data::Empty],
+    ///     vec![text("Date"), text("Region"), text("Amount")],
+    ///     vec![text("2024-01-01"), text("North"), Data::Float(10.0)],
+    ///     vec![text("2024-01-02"), text("South"), Data::Float(12.5)],
+    /// ]);
+    ///
+    /// let table = range.find_table(&["date", "amount"]);
+    ///
+    /// assert_eq!(table, Some(Dimensions::new((2, 0), (4, 2))));
+    /// ```
+    ///
+    pub fn find_table(&self, header_keywords: &[&str]) -> Option<Dimensions> {
+        if header_keywords.is_empty() || self.is_empty() {
+            return None;
+        }
+        let keywords = header_keywords
+            .iter()
+            .map(|k| k.to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        let (header, first_col, last_col) = self.rows().enumerate().find_map(|(row, cells)| {
+            let mut matched = (usize::MAX, 0);
+            for keyword in &keywords {
+                let col = cells.iter().position(|cell| {
+                    cell.get_string()
+                        .is_some_and(|s| s.to_ascii_lowercase().contains(keyword.as_str()))
+                })?;
+                matched = (matched.0.min(col), matched.1.max(col));
+            }
+            Some((row, matched.0, matched.1))
+        })?;
+
+        let (height, width) = self.get_size();
+        let (mut last_row, mut first_col, mut last_col) = (header, first_col, last_col);
+        let is_used = |row: usize, col: usize| self.inner[row * width + col] != Data::Empty;
+        loop {
+            if first_col > 0 && is_used(header, first_col - 1) {
+                first_col -= 1;
+            } else if last_col + 1 < width && (header..=last_row).any(|r| is_used(r, last_col + 1))
+            {
+                last_col += 1;
+            } else if last_row + 1 < height
+                && (first_col..=last_col).any(|c| is_used(last_row + 1, c))
+            {
+                last_row += 1;
+            } else {
+                break;
+            }
+        }
+
+        let (row_start, col_start) = self.start;
+        Some(Dimensions::new(
+            (row_start + header as u32, col_start + first_col as u32),
+            (row_start + last_row as u32, col_start + last_col as u32),
+        ))
+    }
+
+    /// Sort the rows of the range by the values of a column.
+    ///
+    /// Values are compared with the [`Ord`] implementation of [`Data`], which
+    /// follows Excel's sort order: numbers, then text (case-insensitive), then
+    /// logical values, then errors. As in Excel, empty cells are always sorted
+    /// last, in both ascending and descending order. The sort is stable so
+    /// rows with equal values keep their relative order.
+    ///
+    /// The column index is absolute, as in [`Range::column()`]. The range is
+    /// left unchanged if the column is outside of it.
+    ///
+    /// # Parameters
+    ///
+    /// - `col`: The absolute column index, zero indexed from `A1`.
+    /// - `ascending`: Sort from the smallest to the largest value if `true`,
+    ///   from the largest to the smallest otherwise.
+    ///
+    /// # Examples
+    ///
+    /// An example of sorting the rows of a range by a column of mixed values.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_sort_by_column.rs
+    /// #
+    /// use calamine::{CellErrorType, Data, Range};
+    ///
+    /// let mut range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("b".to_string()), Data::Int(1)],
+    ///     vec![Data::Empty, Data::Int(2)],
+    ///     vec![Data::Bool(true), Data::Int(3)],
+    ///     vec![Data::Float(2.5), Data::Int(4)],
+    ///     vec![Data::Error(CellErrorType::NA), Data::Int(5)],
+    ///     vec![Data::String("A".to_string()), Data::Int(6)],
+    ///     vec![Data::Int(10), Data::Int(7)],
+    /// ]);
+    ///
+    /// range.sort_by_column(0, true);
+    /// let order: Vec<_> = range.column(1).into_iter().cloned().collect();
+    /// assert_eq!(order, [4, 7, 6, 1, 3, 5, 2].map(Data::Int).to_vec());
+    ///
+    /// // Empty cells stay last when sorting in descending order.
+    /// range.sort_by_column(0, false);
+    /// let order: Vec<_> = range.column(1).into_iter().cloned().collect();
+    /// assert_eq!(order, [5, 3, 1, 6, 7, 4, 2].map(Data::Int).to_vec());
+    /// ```
+    ///
+    pub fn sort_by_column(&mut self, col: u32, ascending: bool) {
+        if self.inner.is_empty() || col < self.start.1 || col > self.end.1 {
+            return;
+        }
+        let width = self.width();
+        let index = (col - self.start.1) as usize;
+        let mut rows: Vec<Vec<Data>> = self.inner.chunks(width).map(|row| row.to_vec()).collect();
+        rows.sort_by(|a, b| match (&a[index], &b[index]) {
+            (Data::Empty, Data::Empty) => Ordering::Equal,
+            (Data::Empty, _) => Ordering::Greater,
+            (_, Data::Empty) => Ordering::Less,
+            (a, b) if ascending => a.cmp(b),
+            (a, b) => b.cmp(a),
+        });
+        self.inner = rows.into_iter().flatten().collect();
+    }
+
+    /// Build column names from one or more header rows with merged cells.
+    ///
+    /// Multi-level headers usually merge a top-level label across the columns
+    /// it groups, for example a `2023` header merged over `Q1` to `Q4`
+    /// subheaders. Each column name joins the non-empty header values of the
+    /// column from top to bottom with a `.`, e.g. `2023.Q1`, using the value
+    /// of the anchor cell for cells inside a merged region. A region merged
+    /// over several header rows, e.g. a `Region` header next to the two
+    /// levels, is only used once.
+    ///
+    /// The names can be used to match the fields of a struct, for example
+    /// with [`RangeDeserializerBuilder::with_headers()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `header_rows`: The absolute indices of the header rows, zero indexed
+    ///   from `A1`. Rows outside the range are ignored.
+    /// - `merges`: The merged regions of the worksheet the range was read
+    ///   from, for example from [`Xlsx::worksheet_merge_cells()`].
+    ///
+    /// # Examples
+    ///
+    /// An example of naming the columns of a two-level header.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_merged_header_names.rs
+    /// #
+    /// use calamine::{Data, Dimensions, Range};
+    ///
+    /// let text = |v: &str| Data::String(v.to_string());
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![text("Region"), text("2023"), Data::Empty, text("2024")],
+    ///     vec![Data::Empty, text("Q1"), text("Q2"), text("Q1")],
+    ///     vec![text("North"), Data::Int(1), Data::Int(2), Data::Int(3)],
+    /// ]);
+    ///
+    /// // A1:A2 and B1:C1 are merged.
+    /// let merges = [
+    ///     Dimensions::new((0, 0), (1, 0)),
+    ///     Dimensions::new((0, 1), (0, 2)),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     range.merged_header_names(0..2, &merges),
+    ///     ["Region", "2023.Q1", "2023.Q2", "2024.Q1"]
+    /// );
+    /// ```
+    ///
+    pub fn merged_header_names(
+        &self,
+        header_rows: std::ops::Range<u32>,
+        merges: &[Dimensions],
+    ) -> Vec<String> {
+        let (Some(start), Some(end)) = (self.start(), self.end()) else {
+            return Vec::new();
+        };
+        let first_row = header_rows.start.max(start.0);
+        let last_row = header_rows.end.min(end.0.saturating_add(1));
+        let merge_map = self.merge_map(merges);
+        (start.1..=end.1)
+            .map(|col| {
+                let mut parts = Vec::new();
+                for row in first_row..last_row {
+                    let anchor = merge_map.anchor_of((row, col)).unwrap_or((row, col));
+                    // a vertical merge was already used by the row above
+                    if anchor.0 < row && row > first_row {
+                        continue;
+                    }
+                    match self.get_value(anchor) {
+                        Some(Data::Empty) | None => {}
+                        Some(value) => parts.push(value.to_string()),
+                    }
+                }
+                parts.join(".")
+            })
+            .collect()
+    }
+
+    /// Convert the range to a 2D array of floats.
+    ///
+    /// `Int`, `Float` and `Bool` cells are converted to `f64`, booleans as
+    /// `1.0` and `0.0`. Any other cell, including empty cells, strings and
+    /// dates, gets the `fill` value, typically `f64::NAN`. The array has the
+    /// shape `(height, width)` of the range.
+    ///
+    /// This method requires the `ndarray` feature.
+    ///
+    /// # Parameters
+    ///
+    /// - `fill`: The value of the cells that are not numeric.
+    ///
+    /// # Examples
+    ///
+    /// An example of converting a range to an `ndarray` matrix.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_to_ndarray_f64.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::Float(1.5), Data::Int(2)],
+    ///     vec![Data::Bool(true), Data::String("n/a".to_string())],
+    /// ]);
+    ///
+    /// let matrix = range.to_ndarray_f64(f64::NAN);
+    ///
+    /// assert_eq!(matrix.shape(), &[2, 2]);
+    /// assert_eq!(matrix[[0, 0]], 1.5);
+    /// assert_eq!(matrix[[0, 1]], 2.0);
+    /// assert_eq!(matrix[[1, 0]], 1.0);
+    /// assert!(matrix[[1, 1]].is_nan());
+    /// ```
+    ///
+    #[cfg(feature = "ndarray")]
+    pub fn to_ndarray_f64(&self, fill: f64) -> ndarray::Array2<f64> {
+        let (height, width) = self.get_size();
+        let values = self
+            .inner
+            .iter()
+            .map(|cell| match *cell {
+                Data::Int(v) => v as f64,
+                Data::Float(v) => v,
+                Data::Bool(v) => f64::from(u8::from(v)),
+                _ => fill,
+            })
+            .collect();
+        ndarray::Array2::from_shape_vec((height, width), values)
+            .expect("range cells should match its size")
+    }
+
+    /// Convert the range to rows of JSON values.
+    ///
+    /// Each cell becomes its natural JSON type: numbers become `Number`,
+    /// booleans `Bool`, strings `String` and empty cells `Null`. Errors are
+    /// written as their text, e.g. `"#DIV/0!"`. With the `dates` feature,
+    /// dates are ISO 8601 text, e.g. `"2021-01-01T00:00:00"`, else their serial
+    /// number. Non finite floats, which JSON can't represent, become `Null`.
+    ///
+    /// Unlike the header based deserialization, no row is treated as a header:
+    /// the first row of the range is the first row of the output.
+    ///
+    /// This method requires the `serde_json` feature.
+    ///
+    /// # Examples
+    ///
+    /// An example of converting a range to JSON rows.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_to_json_rows.rs
+    /// #
+    /// use calamine::{CellErrorType, Data, Range};
+    /// use serde_json::json;
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("a".to_string()), Data::Float(1.5)],
+    ///     vec![Data::Error(CellErrorType::Div0), Data::Empty],
+    /// ]);
+    ///
+    /// let rows = range.to_json_rows();
+    ///
+    /// assert_eq!(
+    ///     rows,
+    ///     [[json!("a"), json!(1.5)], [json!("#DIV/0!"), json!(null)]]
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "serde_json")]
+    pub fn to_json_rows(&self) -> Vec<Vec<serde_json::Value>> {
+        use serde_json::Value;
+
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Data::Int(v) => Value::from(*v),
+                        Data::Float(v) => {
+                            serde_json::Number::from_f64(*v).map_or(Value::Null, Value::Number)
+                        }
+                        Data::Bool(v) => Value::Bool(*v),
+                        Data::String(v) => Value::String(v.clone()),
+                        Data::Empty => Value::Null,
+                        Data::DateTime(v) => v.to_json(),
+                        Data::DateTimeIso(_) | Data::DurationIso(_) | Data::Error(_) => {
+                            Value::String(cell.to_string())
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Convert the range to an HTML table.
+    ///
+    /// Each row of the range becomes a `<tr>` and each cell a `<td>` holding
+    /// its text, with the HTML special characters escaped. Errors are written
+    /// as their Excel text, e.g. `#N/A`, and empty cells as empty `<td>`.
+    ///
+    /// With `opts.header`, the cells of the first row are `<th>`. With
+    /// `opts.formats`, the fill color and font color of each cell are applied
+    /// as inline `background-color` and `color` styles. Only RGB and indexed
+    /// colors are applied, as theme colors need the workbook theme.
+    ///
+    /// # Parameters
+    ///
+    /// - `opts`: The header and style options, see [`HtmlOptions`].
+    ///
+    /// # Examples
+    ///
+    /// An example of converting a range to an HTML table.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_to_html.rs
+    /// #
+    /// use calamine::{Data, HtmlOptions, Range};
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("Name".to_string()), Data::String("Qty".to_string())],
+    ///     vec![Data::String("Fish & chips".to_string()), Data::Int(2)],
+    /// ]);
+    ///
+    /// let opts = HtmlOptions {
+    ///     header: true,
+    ///     ..HtmlOptions::default()
+    /// };
+    /// let html = range.to_html(opts);
+    ///
+    /// assert_eq!(
+    ///     html,
+    ///     "<table>\n\
+    ///      <tr><th>Name</th><th>Qty</th></tr>\n\
+    ///      <tr><td>Fish &amp; chips</td><td>2</td></tr>\n\
+    ///      </table>\n"
+    /// );
+    /// ```
+    ///
+    pub fn to_html(&self, opts: HtmlOptions) -> String {
+        let css_color = |color: &Color| match *color {
+            Color::Rgb { r, g, b } | Color::Argb { r, g, b, .. } => {
+                Some(format!("#{r:02X}{g:02X}{b:02X}"))
+            }
+            Color::Indexed(index) => match formats::builtin_indexed_color(index)? {
+                Color::Argb { r, g, b, .. } => Some(format!("#{r:02X}{g:02X}{b:02X}")),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let mut html = String::from("<table>\n");
+        let width = self.width();
+        for (i, row) in self.rows().enumerate() {
+            let tag = if opts.header && i == 0 { "th" } else { "td" };
+            html.push_str("<tr>");
+            for (j, cell) in row.iter().enumerate() {
+                let style = opts.formats.get(i * width + j);
+                let background = style
+                    .and_then(|s| s.fill.as_ref())
+                    .filter(|fill| fill.pattern_type != PatternType::None)
+                    .and_then(|fill| fill.foreground_color.as_ref())
+                    .and_then(css_color);
+                let color = style
+                    .and_then(|s| s.font.as_ref())
+                    .and_then(|font| font.color.as_ref())
+                    .and_then(css_color);
+                let css: Vec<_> = [("background-color", background), ("color", color)]
+                    .into_iter()
+                    .filter_map(|(property, value)| Some(format!("{property}:{}", value?)))
+                    .collect();
+                html.push('<');
+                html.push_str(tag);
+                if !css.is_empty() {
+                    html.push_str(&format!(" style=\"{}\"", css.join(";")));
+                }
+                html.push('>');
+                for c in cell.to_string().chars() {
+                    match c {
+                        '&' => html.push_str("&amp;"),
+                        '<' => html.push_str("&lt;"),
+                        '>' => html.push_str("&gt;"),
+                        '"' => html.push_str("&quot;"),
+                        '\'' => html.push_str("&#39;"),
+                        c => html.push(c),
+                    }
+                }
+                html.push_str("</");
+                html.push_str(tag);
+                html.push('>');
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// Compute summary statistics of a column of the range.
+    ///
+    /// The numeric aggregates (`min`, `max`, `sum` and `mean`) only cover
+    /// [`Data::Int`] and [`Data::Float`] cells, other cells, such as strings
+    /// or dates, are only counted in `count`. A column outside of the range
+    /// has no cells.
+    ///
+    /// # Parameters
+    ///
+    /// - `col`: The index of the column, relative to the start of the range.
+    ///
+    /// # Examples
+    ///
+    /// An example of computing the statistics of a column.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_column_stats.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("Qty".to_string())],
+    ///     vec![Data::Int(2)],
+    ///     vec![Data::Empty],
+    ///     vec![Data::Float(4.0)],
+    /// ]);
+    ///
+    /// let stats = range.column_stats(0);
+    ///
+    /// assert_eq!(stats.count, 3);
+    /// assert_eq!(stats.numeric_count, 2);
+    /// assert_eq!(stats.null_count, 1);
+    /// assert_eq!(stats.mean, Some(3.0));
+    /// ```
+    ///
+    pub fn column_stats(&self, col: u32) -> ColumnStats {
+        let mut stats = ColumnStats::default();
+        if col as usize >= self.width() {
+            return stats;
+        }
+        for row in self.rows() {
+            stats.add(&row[col as usize]);
+        }
+        stats
+    }
+
+    /// Aggregate the values of a column, grouped by the values of a key column.
+    ///
+    /// This is the `GROUP BY` of SQL: rows are grouped by the value of their
+    /// key cell, and the value cells of each group are aggregated with `agg`.
+    /// Groups are returned in the order of the first row of each key. Rows
+    /// whose key cell is empty are skipped. As in [`Range::value_counts()`],
+    /// keys of different types are different, e.g. `Int(1)` and `Float(1.0)`.
+    ///
+    /// # Parameters
+    ///
+    /// - `key_col`: The index of the key column, relative to the start of the
+    ///   range.
+    /// - `value_col`: The index of the value column, relative to the start of
+    ///   the range.
+    /// - `agg`: The aggregation of the values of each group, see
+    ///   [`AggregationFunction`].
+    ///
+    /// # Examples
+    ///
+    /// An example of summing a column by key.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_group_by.rs
+    /// #
+    /// use calamine::{AggregationFunction, Data, Range};
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("tea".to_string()), Data::Int(2)],
+    ///     vec![Data::String("coffee".to_string()), Data::Int(3)],
+    ///     vec![Data::String("tea".to_string()), Data::Float(1.5)],
+    /// ]);
+    ///
+    /// let totals = range.group_by(0, 1, AggregationFunction::Sum);
+    ///
+    /// assert_eq!(
+    ///     totals,
+    ///     [
+    ///         (Data::String("tea".to_string()), Data::Float(3.5)),
+    ///         (Data::String("coffee".to_string()), Data::Float(3.0)),
+    ///     ]
+    /// );
+    /// ```
+    ///
+    pub fn group_by(
+        &self,
+        key_col: u32,
+        value_col: u32,
+        agg: AggregationFunction,
+    ) -> Vec<(Data, Data)> {
+        let (key_col, value_col) = (key_col as usize, value_col as usize);
+        if key_col >= self.width() || value_col >= self.width() {
+            return Vec::new();
+        }
+        let mut indexes = HashMap::new();
+        let mut groups: Vec<(Data, ColumnStats)> = Vec::new();
+        for row in self.rows() {
+            let key = &row[key_col];
+            if *key == Data::Empty {
+                continue;
+            }
+            let index = *indexes.entry(key.clone()).or_insert_with(|| {
+                groups.push((key.clone(), ColumnStats::default()));
+                groups.len() - 1
+            });
+            groups[index].1.add(&row[value_col]);
+        }
+        groups
+            .into_iter()
+            .map(|(key, stats)| (key, agg.apply(&stats)))
+            .collect()
+    }
+}
+
+impl<T: CellType + fmt::Display> Range<T> {
+    /// Get headers for a `Range`.
+    ///
+    /// This method returns the first row of the range as an optional vector of
+    /// strings. The data type `T` in the range must support the [`ToString`]
+    /// trait.
+    ///
+    /// # Examples
+    ///
+    /// An example of getting the header row of a calamine `Range`.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_headers.rs
+    /// #
+    /// use calamine::{Data, Range};
+    ///
+    /// // Create a range with some values.
+    /// let mut range = Range::new((0, 0), (5, 2));
+    /// range.set_value((0, 0), Data::String(String::from("a")));
+    /// range.set_value((0, 1), Data::Int(1));
+    /// range.set_value((0, 2), Data::Bool(true));
+    ///
+    /// // Get the headers of the range.
+    /// let headers = range.headers();
+    ///
+    /// assert_eq!(
+    ///     headers,
+    ///     Some(vec![
+    ///         String::from("a"),
+    ///         String::from("1"),
+    ///         String::from("true")
+    ///     ])
+    /// );
+    /// ```
+    ///
+    pub fn headers(&self) -> Option<Vec<String>> {
+        self.rows()
+            .next()
+            .map(|row| row.iter().map(ToString::to_string).collect())
+    }
+
+    /// Write the range as CSV.
+    ///
+    /// Each row of the range is written as a line, with the text of each cell
+    /// as a field. Empty cells are written as empty fields.
+    ///
+    /// # Parameters
+    ///
+    /// - `w`: The destination of the CSV text.
+    /// - `opts`: The delimiter and line ending, see [`CsvOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O errors of the destination.
+    ///
+    /// # Examples
+    ///
+    /// An example of writing a range as CSV.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_range_write_csv.rs
+    /// #
+    /// use calamine::{CsvOptions, Data, Range};
+    ///
+    /// let range = Range::from_2d_vec(vec![
+    ///     vec![Data::String("Name".to_string()), Data::String("Qty".to_string())],
+    ///     vec![Data::String("Fish, chips".to_string()), Data::Int(2)],
+    ///     vec![Data::String("Tea".to_string()), Data::Empty],
+    /// ]);
+    ///
+    /// let mut csv = Vec::new();
+    /// range.write_csv(&mut csv, CsvOptions::default()).unwrap();
+    ///
+    /// assert_eq!(csv, b"Name,Qty\r\n\"Fish, chips\",2\r\nTea,\r\n");
+    /// ```
+    ///
+    pub fn write_csv<W: Write>(&self, mut w: W, opts: CsvOptions) -> std::io::Result<()> {
+        for row in self.rows() {
+            for (i, value) in row.iter().enumerate() {
+                if i > 0 {
+                    write!(w, "{}", opts.delimiter)?;
+                }
+                opts.write_field(&mut w, &value.to_string())?;
+            }
+            opts.write_line_end(&mut w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Implementation of the `Index` trait for `Range` rows.
+///
+/// # Examples
+///
+/// An example of row indexing for a calamine `Range`.
+///
+/// ```
+/// # // This code is available in examples/doc_range_index_row.rs
+/// #
+/// use calamine::{Data, Range};
+///
+/// // Create a range with a value.
+/// let mut range = Range::new((1, 1), (3, 3));
+/// range.set_value((2, 2), Data::Int(123));
+///
+/// // Get the second row via indexing.
+/// assert_eq!(range[1], [Data::Empty, Data::Int(123), Data::Empty]);
+/// ```
+///
+impl<T: CellType> Index<usize> for Range<T> {
+    type Output = [T];
+    fn index(&self, index: usize) -> &[T] {
+        let width = self.width();
+        &self.inner[index * width..(index + 1) * width]
+    }
+}
+
+/// Implementation of the `Index` trait for `Range` cells.
+///
+/// # Examples
+///
+/// An example of cell indexing for a calamine `Range`.
+///
+/// ```
+/// # // This code is available in examples/doc_range_index_cell.rs
+/// #
+/// use calamine::{Data, Range};
+///
+/// // Create a range with a value.
+/// let mut range = Range::new((1, 1), (3, 3));
+/// range.set_value((2, 2), Data::Int(123));
+///
+/// // Get the value via cell indexing.
+/// assert_eq!(range[(1, 1)], Data::Int(123));
+/// ```
+///
+impl<T: CellType> Index<(usize, usize)> for Range<T> {
+    type Output = T;
+    fn index(&self, index: (usize, usize)) -> &T {
+        let (height, width) = self.get_size();
+        assert!(index.1 < width && index.0 < height, "index out of bounds");
+        &self.inner[index.0 * width + index.1]
+    }
+}
+
+/// Implementation of the `IndexMut` trait for `Range` rows.
+impl<T: CellType> IndexMut<usize> for Range<T> {
+    fn index_mut(&mut self, index: usize) -> &mut [T] {
+        let width = self.width();
+        &mut self.inner[index * width..(index + 1) * width]
+    }
+}
+
+/// Implementation of the `IndexMut` trait for `Range` cells.
+///
+/// # Examples
+///
 /// An example of mutable cell indexing for a calamine `Range`.
 ///
 /// ```
@@ -1835,12 +3386,89 @@ impl<'a, T: 'a + CellType> DoubleEndedIterator for Rows<'a, T> {
 
 impl<'a, T: 'a + CellType> ExactSizeIterator for Rows<'a, T> {}
 
+/// Implementation of the `IntoIterator` trait for `Range`, by owned rows.
+///
+/// Each row is a `Vec` of the width of the range, with empty cells holding
+/// the default value of `T`. Use [`Range::rows()`] to iterate over borrowed
+/// rows instead.
+///
+/// # Examples
+///
+/// An example of consuming a calamine `Range` row by row.
+///
+/// ```
+/// # // This code is available in examples/doc_range_into_iter.rs
+/// #
+/// use calamine::{Data, Range};
+///
+/// let mut range = Range::new((1, 1), (2, 2));
+/// range.set_value((1, 1), Data::String("a".to_string()));
+/// range.set_value((2, 2), Data::Int(1));
+///
+/// let rows: Vec<Vec<Data>> = range.into_iter().collect();
+///
+/// assert_eq!(
+///     rows,
+///     vec![
+///         vec![Data::String("a".to_string()), Data::Empty],
+///         vec![Data::Empty, Data::Int(1)],
+///     ]
+/// );
+/// ```
+///
+impl<T: CellType> IntoIterator for Range<T> {
+    type Item = Vec<T>;
+    type IntoIter = IntoRows<T>;
+
+    fn into_iter(self) -> IntoRows<T> {
+        IntoRows {
+            width: self.width(),
+            inner: self.inner.into_iter(),
+        }
+    }
+}
+
+/// An iterator consuming a `Range` row by row
+#[derive(Clone, Debug)]
+pub struct IntoRows<T: CellType> {
+    width: usize,
+    inner: std::vec::IntoIter<T>,
+}
+
+impl<T: CellType> Iterator for IntoRows<T> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.inner.len() == 0 {
+            return None;
+        }
+        Some(self.inner.by_ref().take(self.width).collect())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len().checked_div(self.width).unwrap_or(0);
+        (len, Some(len))
+    }
+}
+
+impl<T: CellType> DoubleEndedIterator for IntoRows<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.inner.len() == 0 {
+            return None;
+        }
+        let mut row: Vec<T> = self.inner.by_ref().rev().take(self.width).collect();
+        row.reverse();
+        Some(row)
+    }
+}
+
+impl<T: CellType> ExactSizeIterator for IntoRows<T> {}
+
 /// Struct with the key elements of a table
 pub struct Table<T> {
     pub(crate) name: String,
     pub(crate) sheet_name: String,
     pub(crate) columns: Vec<String>,
     pub(crate) data: Range<T>,
+    pub(crate) style: Option<TableStyleInfo>,
 }
 impl<T> Table<T> {
     /// Get the name of the table
@@ -1859,6 +3487,14 @@ impl<T> Table<T> {
     pub fn data(&self) -> &Range<T> {
         &self.data
     }
+    /// Get the style applied to the table (`<tableStyleInfo>`), if any
+    pub fn style(&self) -> Option<&TableStyleInfo> {
+        self.style.as_ref()
+    }
+    /// Get the name of the style applied to the table, e.g. "TableStyleMedium2"
+    pub fn style_name(&self) -> Option<&str> {
+        self.style.as_ref()?.name.as_deref()
+    }
 }
 
 impl<T: CellType> From<Table<T>> for Range<T> {
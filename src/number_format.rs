@@ -0,0 +1,1192 @@
+//! Rendering of numeric cell values with their number format code
+//!
+//! This covers the subset of the Excel number format syntax needed to get the
+//! text displayed in a cell: sections and conditions, digit placeholders,
+//! thousands separators and scaling, percentages, scientific notation,
+//! literals, and date, time and elapsed time tokens. Names of months and days
+//! are always in English. Fraction formats are rendered as `General`.
+
+use crate::formats::{CellFormat, CellStyle};
+use crate::Data;
+
+/// Excel epoch weekday offsets, 0 being Sunday
+const WEEKDAY_OFFSET_1900: i64 = 6;
+const WEEKDAY_OFFSET_1904: i64 = 5;
+
+/// Largest serial date Excel can display (9999-12-31)
+const MAX_SERIAL_DATE: f64 = 2_958_465.;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Get the format code of a builtin number format id (en-US locale)
+pub(crate) fn builtin_format_code(id: u32) -> Option<&'static str> {
+    let code = match id {
+        0 => "General",
+        1 => "0",
+        2 => "0.00",
+        3 => "#,##0",
+        4 => "#,##0.00",
+        9 => "0%",
+        10 => "0.00%",
+        11 => "0.00E+00",
+        12 => "# ?/?",
+        13 => "# ??/??",
+        14 => "m/d/yyyy",
+        15 => "d-mmm-yy",
+        16 => "d-mmm",
+        17 => "mmm-yy",
+        18 => "h:mm AM/PM",
+        19 => "h:mm:ss AM/PM",
+        20 => "h:mm",
+        21 => "h:mm:ss",
+        22 => "m/d/yyyy h:mm",
+        37 => "#,##0 ;(#,##0)",
+        38 => "#,##0 ;[Red](#,##0)",
+        39 => "#,##0.00;(#,##0.00)",
+        40 => "#,##0.00;[Red](#,##0.00)",
+        45 => "mm:ss",
+        46 => "[h]:mm:ss",
+        47 => "mmss.0",
+        48 => "##0.0E+0",
+        49 => "@",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// How to write floats when exporting cell values as text
+///
+/// Floats read from a workbook often carry binary noise (e.g.
+/// `3.0000000001`) that the number format of the cell hides in Excel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Shortest text that reads back as the same float
+    #[default]
+    Shortest,
+    /// Fixed number of decimals
+    Fixed(usize),
+    /// Number format of the cell, as displayed by Excel
+    ///
+    /// Builtin formats are resolved from [`CellStyle::number_format_id`].
+    /// Cells without a style are written with the `General` format.
+    Excel,
+}
+
+impl FloatFormat {
+    /// Format a float of a cell with the style `style`
+    ///
+    /// `is_1904` is the date system of the workbook, used by date formats.
+    pub fn format(&self, value: f64, style: Option<&CellStyle>, is_1904: bool) -> String {
+        match self {
+            FloatFormat::Shortest => value.to_string(),
+            FloatFormat::Fixed(decimals) => format!("{value:.decimals$}"),
+            FloatFormat::Excel => {
+                let code = style.map_or("General", style_format_code);
+                format_number(value, code, is_1904)
+            }
+        }
+    }
+}
+
+/// Get the number format code of a cell style
+///
+/// Custom formats use their [`CellStyle::format_string`] and builtin formats
+/// the code of their [`CellStyle::number_format_id`]. Styles with neither,
+/// e.g. built by hand, fall back to `m/d/yyyy` and `[h]:mm:ss` for date and
+/// duration formats and to `General` otherwise.
+pub(crate) fn style_format_code(style: &CellStyle) -> &str {
+    let builtin = style.number_format_id.and_then(builtin_format_code);
+    match (&style.format_string, builtin, &style.number_format) {
+        (Some(code), ..) => code,
+        (None, Some(code), _) => code,
+        (None, None, CellFormat::DateTime) => "m/d/yyyy",
+        (None, None, CellFormat::TimeDelta) => "[h]:mm:ss",
+        (None, None, CellFormat::Other) => "General",
+    }
+}
+
+/// Get the text Excel displays for a cell value with the number format `code`
+///
+/// Numbers and dates are formatted, errors are rendered as in Excel (e.g.
+/// `#DIV/0!`), booleans as `TRUE` or `FALSE` and empty cells as `""`.
+/// `is_1904` is the date system of the workbook, used for ISO 8601 dates.
+/// Month and day names are in English and fraction formats fall back to
+/// `General`.
+///
+/// The format code of a cell is its [`CellStyle::format_string`], or the
+/// code of a builtin format, e.g. `0.00%`.
+///
+/// # Examples
+///
+/// ```
+/// use calamine::{format_value, Data};
+///
+/// assert_eq!(format_value(&Data::Float(0.256), "0.0%", false), "25.6%");
+/// assert_eq!(format_value(&Data::Float(45000.), "yyyy-mm-dd", false), "2023-03-15");
+/// ```
+pub fn format_value(value: &Data, code: &str, is_1904: bool) -> String {
+    match value {
+        Data::Int(v) => format_number(*v as f64, code, is_1904),
+        Data::Float(v) => format_number(*v, code, is_1904),
+        Data::DateTime(v) => format_number(v.as_f64(), code, v.is_1904()),
+        Data::DateTimeIso(v) => match iso_to_serial(v, is_1904) {
+            Some(serial) => format_number(serial, code, is_1904),
+            None => v.clone(),
+        },
+        Data::Bool(true) => "TRUE".to_string(),
+        Data::Bool(false) => "FALSE".to_string(),
+        Data::Error(e) => e.as_excel_string().to_string(),
+        Data::String(v) | Data::DurationIso(v) => v.clone(),
+        Data::Empty => String::new(),
+    }
+}
+
+/// Format `value` the way Excel displays it with the number format `code`
+pub(crate) fn format_number(value: f64, code: &str, is_1904: bool) -> String {
+    let sections = split_sections(code);
+    let (section, value, signed) = select_section(&sections, value);
+    let tokens = tokenize(section);
+
+    if tokens.iter().any(|t| matches!(t, Token::Date(_))) {
+        if !(0. ..=MAX_SERIAL_DATE).contains(&value) {
+            return format_general(value);
+        }
+        return format_date(&tokens, value, is_1904);
+    }
+    if tokens.iter().any(|t| matches!(t, Token::Slash)) {
+        return format_general(value);
+    }
+    if tokens.iter().any(|t| matches!(t, Token::Digit(_))) {
+        let text = format_digits(&tokens, value.abs());
+        return if signed && value < 0. {
+            format!("-{text}")
+        } else {
+            text
+        };
+    }
+
+    // literals, possibly around `General` or `@`
+    let mut out = String::new();
+    for token in &tokens {
+        match token {
+            Token::General | Token::Text if signed => out.push_str(&format_general(value)),
+            Token::General | Token::Text => out.push_str(&format_general(value.abs())),
+            Token::Literal(s) => out.push_str(s),
+            Token::Point => out.push('.'),
+            Token::Comma => out.push(','),
+            Token::Percent => out.push('%'),
+            _ => (),
+        }
+    }
+    out
+}
+
+/// Format `value` with the `General` number format
+pub(crate) fn format_general(value: f64) -> String {
+    if value == 0. || !value.is_finite() {
+        return "0".to_string();
+    }
+    let abs = value.abs();
+    let sign = if value < 0. { "-" } else { "" };
+    if !(1e-9..1e11).contains(&abs) {
+        let (digits, exponent) = significant_digits(abs, 6);
+        let mantissa = format!("{}.{}", &digits[..1], &digits[1..]);
+        let mantissa = trim_fraction(&mantissa);
+        let exp_sign = if exponent < 0 { '-' } else { '+' };
+        return format!("{sign}{mantissa}E{exp_sign}{:02}", exponent.abs());
+    }
+    let int_digits = if abs < 1. {
+        1
+    } else {
+        abs.log10().floor() as usize + 1
+    };
+    let (int, frac) = fixed(abs, 10usize.saturating_sub(int_digits));
+    let int = if int.is_empty() { "0" } else { &int };
+    let text = format!("{int}.{frac}");
+    format!("{sign}{}", trim_fraction(&text))
+}
+
+/// Convert an ISO 8601 date and time (as in `t="d"` cells) to a serial date
+pub(crate) fn iso_to_serial(iso: &str, is_1904: bool) -> Option<f64> {
+    let (date, time) = match iso.split_once('T') {
+        Some((d, t)) => (d, Some(t)),
+        None => (iso, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+    let mut serial = (days_from_civil(year, month, day)? - epoch_days(is_1904)) as f64;
+    if !is_1904 && serial >= 60. {
+        // Excel counts the non-existent 1900-02-29
+        serial += 1.;
+    }
+    if let Some(time) = time {
+        let time = time.trim_end_matches('Z');
+        let mut parts = time.splitn(3, ':');
+        let hours: f64 = parts.next()?.parse().ok()?;
+        let minutes: f64 = parts.next().unwrap_or("0").parse().ok()?;
+        let seconds: f64 = parts.next().unwrap_or("0").parse().ok()?;
+        serial += (hours * 3600. + minutes * 60. + seconds) / 86400.;
+    }
+    Some(serial)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    General,
+    Text,
+    Digit(char),
+    Point,
+    Comma,
+    Percent,
+    /// `E+` (true) or `E-` (false)
+    Exponent(bool),
+    Slash,
+    Date(DatePart),
+    /// `AM/PM` (true) or `A/P` (false)
+    AmPm(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DatePart {
+    Year(usize),
+    Month(usize),
+    Day(usize),
+    Hour(usize),
+    Minute(usize),
+    Second(usize),
+    SubSecond(usize),
+    ElapsedHours(usize),
+    ElapsedMinutes(usize),
+    ElapsedSeconds(usize),
+}
+
+/// Split a format code into its `;` separated sections
+fn split_sections(code: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    let mut chars = code.char_indices();
+    let mut in_quote = false;
+    let mut in_bracket = false;
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' if !in_bracket => in_quote = !in_quote,
+            '\\' | '_' | '*' if !in_quote && !in_bracket => {
+                chars.next();
+            }
+            '[' if !in_quote => in_bracket = true,
+            ']' if !in_quote => in_bracket = false,
+            ';' if !in_quote && !in_bracket => {
+                sections.push(&code[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    sections.push(&code[start..]);
+    sections
+}
+
+/// Parse a leading `[<op><number>]` condition
+fn section_condition(section: &str) -> Option<(&str, f64)> {
+    let rest = section.trim_start().strip_prefix('[')?;
+    let end = rest.find(']')?;
+    let cond = &rest[..end];
+    let op_len = cond
+        .find(|c: char| !matches!(c, '<' | '>' | '='))
+        .unwrap_or(cond.len());
+    if op_len == 0 {
+        return None;
+    }
+    let number = cond[op_len..].trim().parse().ok()?;
+    Some((&cond[..op_len], number))
+}
+
+/// Choose the section used to display `value`
+///
+/// Returns the section, the value to display and whether a minus sign must be
+/// added for negative values.
+fn select_section<'a>(sections: &[&'a str], value: f64) -> (&'a str, f64, bool) {
+    if sections.iter().any(|s| section_condition(s).is_some()) {
+        for section in sections {
+            match section_condition(section) {
+                Some((op, number)) => {
+                    let matches = match op {
+                        "<" => value < number,
+                        "<=" | "=<" => value <= number,
+                        ">" => value > number,
+                        ">=" | "=>" => value >= number,
+                        "=" => value == number,
+                        "<>" => value != number,
+                        _ => false,
+                    };
+                    if matches {
+                        return (section, value, true);
+                    }
+                }
+                None => return (section, value, true),
+            }
+        }
+        return ("General", value, true);
+    }
+    match sections {
+        [section] => (section, value, true),
+        [pos, neg] => {
+            if value < 0. {
+                (neg, value.abs(), false)
+            } else {
+                (pos, value, true)
+            }
+        }
+        [pos, neg, zero, ..] => {
+            if value > 0. {
+                (pos, value, true)
+            } else if value < 0. {
+                (neg, value.abs(), false)
+            } else {
+                (zero, value, true)
+            }
+        }
+        [] => ("General", value, true),
+    }
+}
+
+fn tokenize(section: &str) -> Vec<Token> {
+    let chars: Vec<char> = section.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let run = |i: usize, lower: char| {
+        chars[i..]
+            .iter()
+            .take_while(|c| c.to_ascii_lowercase() == lower)
+            .count()
+    };
+    let starts_with = |i: usize, s: &str| {
+        let len = s.chars().count();
+        i + len <= chars.len()
+            && chars[i..i + len]
+                .iter()
+                .zip(s.chars())
+                .all(|(a, b)| a.eq_ignore_ascii_case(&b))
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '"')
+                    .map_or(chars.len(), |p| i + 1 + p);
+                tokens.push(Token::Literal(chars[i + 1..end].iter().collect()));
+                i = end + 1;
+            }
+            '\\' => {
+                if let Some(&next) = chars.get(i + 1) {
+                    tokens.push(Token::Literal(next.to_string()));
+                }
+                i += 2;
+            }
+            '_' => {
+                tokens.push(Token::Literal(" ".to_string()));
+                i += 2;
+            }
+            '*' => i += 2,
+            '[' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == ']')
+                    .map_or(chars.len(), |p| i + 1 + p);
+                let content: String = chars[i + 1..end].iter().collect();
+                let lower = content.to_ascii_lowercase();
+                if let Some(currency) = content.strip_prefix('$') {
+                    let symbol = currency.split('-').next().unwrap_or_default();
+                    tokens.push(Token::Literal(symbol.to_string()));
+                } else if !lower.is_empty() && lower.chars().all(|c| c == 'h') {
+                    tokens.push(Token::Date(DatePart::ElapsedHours(lower.len())));
+                } else if !lower.is_empty() && lower.chars().all(|c| c == 'm') {
+                    tokens.push(Token::Date(DatePart::ElapsedMinutes(lower.len())));
+                } else if !lower.is_empty() && lower.chars().all(|c| c == 's') {
+                    tokens.push(Token::Date(DatePart::ElapsedSeconds(lower.len())));
+                }
+                // colors and conditions are not displayed
+                i = end + 1;
+            }
+            '0' | '#' | '?' => {
+                tokens.push(Token::Digit(c));
+                i += 1;
+            }
+            '.' => {
+                tokens.push(Token::Point);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '%' => {
+                tokens.push(Token::Percent);
+                i += 1;
+            }
+            '/' => {
+                tokens.push(Token::Slash);
+                i += 1;
+            }
+            '@' => {
+                tokens.push(Token::Text);
+                i += 1;
+            }
+            'E' | 'e' if matches!(chars.get(i + 1), Some('+' | '-')) => {
+                tokens.push(Token::Exponent(chars[i + 1] == '+'));
+                i += 2;
+            }
+            _ if starts_with(i, "general") => {
+                tokens.push(Token::General);
+                i += 7;
+            }
+            _ if starts_with(i, "am/pm") => {
+                tokens.push(Token::AmPm(true));
+                i += 5;
+            }
+            _ if starts_with(i, "a/p") => {
+                tokens.push(Token::AmPm(false));
+                i += 3;
+            }
+            'y' | 'Y' | 'm' | 'M' | 'd' | 'D' | 'h' | 'H' | 's' | 'S' => {
+                let lower = c.to_ascii_lowercase();
+                let n = run(i, lower);
+                let part = match lower {
+                    'y' => DatePart::Year(n),
+                    'm' => DatePart::Month(n),
+                    'd' => DatePart::Day(n),
+                    'h' => DatePart::Hour(n),
+                    _ => DatePart::Second(n),
+                };
+                tokens.push(Token::Date(part));
+                i += n;
+            }
+            _ => {
+                tokens.push(Token::Literal(c.to_string()));
+                i += 1;
+            }
+        }
+    }
+
+    if tokens.iter().any(|t| matches!(t, Token::Date(_))) {
+        resolve_date_tokens(&mut tokens);
+    }
+    tokens
+}
+
+/// Turn months into minutes next to hours or seconds, and `.0` after seconds
+/// into fractions of seconds
+fn resolve_date_tokens(tokens: &mut Vec<Token>) {
+    let dates: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| matches!(t, Token::Date(_)))
+        .map(|(i, _)| i)
+        .collect();
+    for (k, &i) in dates.iter().enumerate() {
+        if let Token::Date(DatePart::Month(n)) = tokens[i] {
+            let after_hour = k > 0
+                && matches!(
+                    tokens[dates[k - 1]],
+                    Token::Date(DatePart::Hour(_) | DatePart::ElapsedHours(_))
+                );
+            let before_second = matches!(
+                dates.get(k + 1).map(|&j| &tokens[j]),
+                Some(Token::Date(
+                    DatePart::Second(_) | DatePart::ElapsedSeconds(_)
+                ))
+            );
+            if (after_hour || before_second) && n <= 2 {
+                tokens[i] = Token::Date(DatePart::Minute(n));
+            }
+        }
+    }
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let after_second = i > 0
+            && matches!(
+                tokens[i - 1],
+                Token::Date(DatePart::Second(_) | DatePart::ElapsedSeconds(_))
+            );
+        if after_second && tokens[i] == Token::Point {
+            let n = tokens[i + 1..]
+                .iter()
+                .take_while(|t| **t == Token::Digit('0'))
+                .count();
+            if n > 0 {
+                tokens.splice(i..i + 1 + n, [Token::Date(DatePart::SubSecond(n))]);
+            }
+        }
+        i += 1;
+    }
+}
+
+fn format_date(tokens: &[Token], value: f64, is_1904: bool) -> String {
+    let precision = tokens
+        .iter()
+        .find_map(|t| match t {
+            Token::Date(DatePart::SubSecond(n)) => Some((*n).min(3)),
+            _ => None,
+        })
+        .unwrap_or(0);
+    let scale = 10i64.pow(precision as u32);
+    let ticks_per_day = 86_400 * scale;
+
+    let total_ticks = (value * ticks_per_day as f64).round() as i64;
+    let serial = total_ticks.div_euclid(ticks_per_day);
+    let day_ticks = total_ticks.rem_euclid(ticks_per_day);
+    let seconds = day_ticks / scale;
+    let sub_second = day_ticks % scale;
+    let (hour, minute, second) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    let total_seconds = total_ticks / scale;
+
+    let (year, month, day) = serial_to_civil(serial, is_1904);
+    let weekday = if is_1904 {
+        (serial + WEEKDAY_OFFSET_1904).rem_euclid(7)
+    } else {
+        (serial + WEEKDAY_OFFSET_1900).rem_euclid(7)
+    } as usize;
+    let has_am_pm = tokens.iter().any(|t| matches!(t, Token::AmPm(_)));
+    let elapsed_hours = tokens
+        .iter()
+        .any(|t| matches!(t, Token::Date(DatePart::ElapsedHours(_))));
+    let elapsed_minutes = tokens
+        .iter()
+        .any(|t| matches!(t, Token::Date(DatePart::ElapsedMinutes(_))));
+
+    let mut out = String::new();
+    for token in tokens {
+        match token {
+            Token::Date(part) => match *part {
+                DatePart::Year(n) if n <= 2 => out.push_str(&format!("{:02}", year % 100)),
+                DatePart::Year(_) => out.push_str(&format!("{year:04}")),
+                DatePart::Month(1) => out.push_str(&month.to_string()),
+                DatePart::Month(2) => out.push_str(&format!("{month:02}")),
+                DatePart::Month(n) => {
+                    let name = MONTHS[(month as usize + 11) % 12];
+                    match n {
+                        3 => out.push_str(&name[..3]),
+                        4 => out.push_str(name),
+                        _ => out.push_str(&name[..1]),
+                    }
+                }
+                DatePart::Day(1) => out.push_str(&day.to_string()),
+                DatePart::Day(2) => out.push_str(&format!("{day:02}")),
+                DatePart::Day(3) => out.push_str(&DAYS[weekday][..3]),
+                DatePart::Day(_) => out.push_str(DAYS[weekday]),
+                DatePart::Hour(n) => {
+                    let hour = if has_am_pm {
+                        (hour + 11) % 12 + 1
+                    } else {
+                        hour
+                    };
+                    out.push_str(&pad(hour, n));
+                }
+                DatePart::Minute(n) => out.push_str(&pad(minute, n)),
+                DatePart::Second(n) => out.push_str(&pad(second, n)),
+                DatePart::SubSecond(n) => {
+                    let digits = format!("{:0width$}", sub_second, width = precision);
+                    out.push('.');
+                    out.push_str(&digits[..n.min(digits.len())]);
+                    out.extend(std::iter::repeat('0').take(n.saturating_sub(digits.len())));
+                }
+                DatePart::ElapsedHours(n) => out.push_str(&pad(total_seconds / 3600, n)),
+                DatePart::ElapsedMinutes(n) if elapsed_hours => out.push_str(&pad(minute, n)),
+                DatePart::ElapsedMinutes(n) => out.push_str(&pad(total_seconds / 60, n)),
+                DatePart::ElapsedSeconds(n) if elapsed_hours || elapsed_minutes => {
+                    out.push_str(&pad(second, n))
+                }
+                DatePart::ElapsedSeconds(n) => out.push_str(&pad(total_seconds, n)),
+            },
+            Token::AmPm(full) => {
+                let pm = hour >= 12;
+                out.push_str(match (full, pm) {
+                    (true, false) => "AM",
+                    (true, true) => "PM",
+                    (false, false) => "A",
+                    (false, true) => "P",
+                });
+            }
+            Token::Literal(s) => out.push_str(s),
+            Token::Digit(c) => out.push(*c),
+            Token::Point => out.push('.'),
+            Token::Comma => out.push(','),
+            Token::Percent => out.push('%'),
+            Token::Slash => out.push('/'),
+            Token::Exponent(plus) => out.push_str(if *plus { "E+" } else { "E-" }),
+            Token::General | Token::Text => (),
+        }
+    }
+    out
+}
+
+fn pad(value: i64, width: usize) -> String {
+    if width >= 2 {
+        format!("{value:02}")
+    } else {
+        value.to_string()
+    }
+}
+
+/// Layout of the placeholders of a number format
+enum Layout {
+    Literal(String),
+    Digit(char),
+}
+
+fn format_digits(tokens: &[Token], value: f64) -> String {
+    let mut value = value;
+    let mut prefix = String::new();
+    let mut suffix = String::new();
+    let mut int_layout: Vec<Layout> = Vec::new();
+    let mut frac_layout: Vec<Layout> = Vec::new();
+    let mut exp_layout: Vec<Layout> = Vec::new();
+    let mut exp_plus = None;
+    let mut has_point = false;
+    let mut grouping = false;
+
+    let last_digit = tokens
+        .iter()
+        .rposition(|t| matches!(t, Token::Digit(_)))
+        .unwrap_or(0);
+    let first_digit = tokens
+        .iter()
+        .position(|t| matches!(t, Token::Digit(_) | Token::Point))
+        .unwrap_or(0);
+
+    for (i, token) in tokens.iter().enumerate() {
+        let text = match token {
+            Token::Literal(s) => s.clone(),
+            Token::Percent => {
+                value *= 100.;
+                "%".to_string()
+            }
+            Token::Digit(c) => {
+                let layout = if exp_plus.is_some() {
+                    &mut exp_layout
+                } else if has_point {
+                    &mut frac_layout
+                } else {
+                    &mut int_layout
+                };
+                layout.push(Layout::Digit(*c));
+                continue;
+            }
+            Token::Point if i < last_digit && exp_plus.is_none() => {
+                has_point = true;
+                continue;
+            }
+            Token::Point if i >= first_digit && exp_plus.is_none() => {
+                has_point = true;
+                continue;
+            }
+            Token::Comma if i < first_digit => ",".to_string(),
+            Token::Comma if i < last_digit && !has_point => {
+                grouping = true;
+                continue;
+            }
+            Token::Comma if i < last_digit => continue,
+            Token::Comma => {
+                // scaling by thousands after the last digit placeholder
+                if matches!(tokens.get(i - 1), Some(Token::Digit(_) | Token::Comma)) {
+                    value /= 1000.;
+                    continue;
+                }
+                ",".to_string()
+            }
+            Token::Exponent(plus) => {
+                exp_plus = Some(*plus);
+                continue;
+            }
+            Token::Point => ".".to_string(),
+            _ => continue,
+        };
+        if i < first_digit {
+            prefix.push_str(&text);
+        } else if i > last_digit {
+            suffix.push_str(&text);
+        } else if exp_plus.is_some() {
+            exp_layout.push(Layout::Literal(text));
+        } else if has_point {
+            frac_layout.push(Layout::Literal(text));
+        } else {
+            int_layout.push(Layout::Literal(text));
+        }
+    }
+
+    let int_count = count_digits(&int_layout);
+    let decimals = count_digits(&frac_layout);
+
+    let (int, frac, exponent) = match exp_plus {
+        Some(_) => {
+            let (mantissa, exponent) = scientific(value, int_count, &int_layout, decimals);
+            let (int, frac) = fixed(mantissa, decimals);
+            (int, frac, Some(exponent))
+        }
+        None => {
+            let (int, frac) = fixed(value, decimals);
+            (int, frac, None)
+        }
+    };
+
+    let mut out = prefix;
+    out.push_str(&render_int(&int_layout, &int, grouping));
+    if has_point {
+        out.push('.');
+    }
+    out.push_str(&render_frac(&frac_layout, &frac));
+    if let (Some(plus), Some(exponent)) = (exp_plus, exponent) {
+        out.push('E');
+        if exponent < 0 {
+            out.push('-');
+        } else if plus {
+            out.push('+');
+        }
+        let digits = exponent.abs().to_string();
+        out.push_str(&render_int(&exp_layout, &digits, false));
+    }
+    out.push_str(&suffix);
+    out
+}
+
+fn count_digits(layout: &[Layout]) -> usize {
+    layout
+        .iter()
+        .filter(|l| matches!(l, Layout::Digit(_)))
+        .count()
+}
+
+/// Get the mantissa and exponent of `value` for a scientific format
+fn scientific(value: f64, int_count: usize, int_layout: &[Layout], decimals: usize) -> (f64, i32) {
+    if value == 0. {
+        return (0., 0);
+    }
+    let engineering = int_count > 1
+        && int_layout
+            .iter()
+            .any(|l| matches!(l, Layout::Digit('#' | '?')));
+    let step = if engineering { int_count as i32 } else { 1 };
+    let int_digits = if engineering {
+        1
+    } else {
+        int_count.max(1) as i32
+    };
+
+    let mut exponent = value.log10().floor() as i32 - (int_digits - 1);
+    exponent = exponent.div_euclid(step) * step;
+    let mut mantissa = value / 10f64.powi(exponent);
+    // rounding may overflow the integer digits, e.g. 9.99 -> 10.0
+    let (int, _) = fixed(mantissa, decimals);
+    if int.len() as i32 > int_digits.max(step) {
+        exponent += step;
+        mantissa = value / 10f64.powi(exponent);
+    }
+    (mantissa, exponent)
+}
+
+/// Fill the integer placeholders, from right to left
+fn render_int(layout: &[Layout], digits: &str, grouping: bool) -> String {
+    let min_zeros = layout
+        .iter()
+        .filter(|l| matches!(l, Layout::Digit('0')))
+        .count();
+    let mut digits = digits.to_string();
+    if digits.len() < min_zeros {
+        digits = format!("{digits:0>min_zeros$}");
+    }
+    if grouping {
+        digits = group_thousands(&digits);
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let mut remaining = digits.as_str();
+    let first_digit = layout.iter().position(|l| matches!(l, Layout::Digit(_)));
+    for (i, l) in layout.iter().enumerate().rev() {
+        match l {
+            Layout::Literal(s) => out.push(s.clone()),
+            Layout::Digit(c) if Some(i) == first_digit => {
+                if remaining.is_empty() && *c == '?' {
+                    out.push(" ".to_string());
+                } else {
+                    out.push(remaining.to_string());
+                }
+                remaining = "";
+            }
+            Layout::Digit(c) => {
+                let mut chars = remaining.chars();
+                match chars.next_back() {
+                    Some(d) => {
+                        out.push(d.to_string());
+                        remaining = chars.as_str();
+                        if grouping && remaining.ends_with(',') {
+                            out.push(",".to_string());
+                            remaining = &remaining[..remaining.len() - 1];
+                        }
+                    }
+                    None if *c == '?' => out.push(" ".to_string()),
+                    None => (),
+                }
+            }
+        }
+    }
+    out.reverse();
+    out.concat()
+}
+
+/// Fill the decimal placeholders, from left to right
+fn render_frac(layout: &[Layout], digits: &str) -> String {
+    let mut digits = digits.chars().collect::<Vec<_>>();
+    // trailing zeros are dropped for `#` and replaced by spaces for `?`
+    let placeholders = layout
+        .iter()
+        .filter_map(|l| match l {
+            Layout::Digit(c) => Some(*c),
+            Layout::Literal(_) => None,
+        })
+        .collect::<Vec<_>>();
+    for (d, c) in digits.iter_mut().zip(&placeholders).rev() {
+        match (*d, c) {
+            ('0', '#') => *d = '\0',
+            ('0', '?') => *d = ' ',
+            _ => break,
+        }
+    }
+
+    let mut out = String::new();
+    let mut digits = digits.into_iter();
+    for l in layout {
+        match l {
+            Layout::Literal(s) => out.push_str(s),
+            Layout::Digit(_) => match digits.next() {
+                Some('\0') | None => (),
+                Some(d) => out.push(d),
+            },
+        }
+    }
+    out
+}
+
+fn group_thousands(digits: &str) -> String {
+    let len = digits.len();
+    let mut out = String::with_capacity(len + len / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (len - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Get the 15 significant decimal digits of a positive `value`, rounded to
+/// `precision` digits, and its base 10 exponent
+fn significant_digits(value: f64, precision: usize) -> (String, i32) {
+    let repr = format!("{value:.14e}");
+    let (mantissa, exponent) = repr.split_once('e').unwrap_or((&repr, "0"));
+    let mut exponent: i32 = exponent.parse().unwrap_or(0);
+    let digits: Vec<u8> = mantissa.bytes().filter(u8::is_ascii_digit).collect();
+    let mut digits = round_digits(digits, precision);
+    if digits.len() > precision {
+        digits.truncate(precision);
+        exponent += 1;
+    }
+    (String::from_utf8(digits).unwrap_or_default(), exponent)
+}
+
+/// Round ascii digits half away from zero to keep `len` digits
+///
+/// A carry adds a leading digit, so the result can have `len + 1` digits.
+fn round_digits(mut digits: Vec<u8>, len: usize) -> Vec<u8> {
+    if digits.len() <= len {
+        digits.resize(len, b'0');
+        return digits;
+    }
+    let round_up = digits[len] >= b'5';
+    digits.truncate(len);
+    if round_up {
+        let mut i = len;
+        loop {
+            if i == 0 {
+                digits.insert(0, b'1');
+                break;
+            }
+            i -= 1;
+            if digits[i] == b'9' {
+                digits[i] = b'0';
+            } else {
+                digits[i] += 1;
+                break;
+            }
+        }
+    }
+    digits
+}
+
+/// Round a positive `value` to `decimals` and split it into integer and
+/// decimal digits. A zero integer part is returned as an empty string.
+fn fixed(value: f64, decimals: usize) -> (String, String) {
+    if value == 0. || !value.is_finite() {
+        return (String::new(), "0".repeat(decimals));
+    }
+    let repr = format!("{value:.14e}");
+    let (mantissa, exponent) = repr.split_once('e').unwrap_or((&repr, "0"));
+    let exponent: i64 = exponent.parse().unwrap_or(0);
+    let mut digits: Vec<u8> = mantissa.bytes().filter(u8::is_ascii_digit).collect();
+
+    // make the digits start at the units position at least
+    let mut int_len = exponent + 1;
+    if int_len <= 0 {
+        let zeros = (-int_len + 1) as usize;
+        digits.splice(0..0, std::iter::repeat(b'0').take(zeros));
+        int_len = 1;
+    }
+    let keep = int_len as usize + decimals;
+    let digits = round_digits(digits, keep);
+    let int_len = int_len as usize + (digits.len() - keep);
+
+    let int = String::from_utf8_lossy(&digits[..int_len]);
+    let int = int.trim_start_matches('0').to_string();
+    let frac = String::from_utf8_lossy(&digits[int_len..]).into_owned();
+    (int, frac)
+}
+
+fn trim_fraction(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
+/// Days since 1970-01-01 of a civil date (proleptic Gregorian calendar)
+///
+/// Returns `None` if the month or the day is out of range, or on overflow.
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let year = if month <= 2 {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era.checked_mul(146_097)?.checked_add(doe - 719_468)
+}
+
+/// Civil date of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 of the day before serial date 1
+fn epoch_days(is_1904: bool) -> i64 {
+    if is_1904 {
+        days_from_civil(1904, 1, 1).unwrap()
+    } else {
+        days_from_civil(1899, 12, 31).unwrap()
+    }
+}
+
+/// Civil date of a serial date, including Excel's 1900-02-29
+fn serial_to_civil(serial: i64, is_1904: bool) -> (i64, u32, u32) {
+    if is_1904 {
+        civil_from_days(epoch_days(true) + serial)
+    } else if serial == 0 {
+        (1900, 1, 0)
+    } else if serial == 60 {
+        (1900, 2, 29)
+    } else if serial < 60 {
+        civil_from_days(epoch_days(false) + serial)
+    } else {
+        civil_from_days(epoch_days(false) + serial - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_general() {
+        assert_eq!(format_general(0.), "0");
+        assert_eq!(format_general(42.), "42");
+        assert_eq!(format_general(-1.5), "-1.5");
+        assert_eq!(format_general(1. / 3.), "0.333333333");
+        assert_eq!(format_general(123.456789012345), "123.456789");
+        assert_eq!(format_general(0.1 + 0.2), "0.3");
+        assert_eq!(format_general(123456789012.), "1.23457E+11");
+        assert_eq!(format_general(1.5e-10), "1.5E-10");
+    }
+
+    #[test]
+    fn test_format_numbers() {
+        assert_eq!(format_number(1234.5, "General", false), "1234.5");
+        assert_eq!(format_number(1234.5, "0", false), "1235");
+        assert_eq!(format_number(2.675, "0.00", false), "2.68");
+        assert_eq!(format_number(-2.5, "0", false), "-3");
+        assert_eq!(
+            format_number(1234567.891, "#,##0.00", false),
+            "1,234,567.89"
+        );
+        assert_eq!(format_number(0.5, "#.00", false), ".50");
+        assert_eq!(format_number(1.5, "0.0#", false), "1.5");
+        assert_eq!(format_number(0.256, "0%", false), "26%");
+        assert_eq!(format_number(0.256, "0.00%", false), "25.60%");
+        assert_eq!(format_number(12345.678, "0.00E+00", false), "1.23E+04");
+        assert_eq!(format_number(0.00012, "0.00E+00", false), "1.20E-04");
+        assert_eq!(format_number(12345.678, "##0.0E+0", false), "12.3E+3");
+        assert_eq!(format_number(1234567., "#,##0,", false), "1,235");
+        assert_eq!(format_number(5551234., "000-0000", false), "555-1234");
+        assert_eq!(format_number(12.5, "\"$\"#,##0.00", false), "$12.50");
+        assert_eq!(format_number(12.5, "[$€-407]#,##0.00", false), "€12.50");
+        assert_eq!(format_number(3., "0\" items\"", false), "3 items");
+    }
+
+    #[test]
+    fn test_format_sections() {
+        let code = "#,##0.00;[Red](#,##0.00);\"zero\"";
+        assert_eq!(format_number(1234.5, code, false), "1,234.50");
+        assert_eq!(format_number(-1234.5, code, false), "(1,234.50)");
+        assert_eq!(format_number(0., code, false), "zero");
+        assert_eq!(format_number(-5., "#,##0 ;(#,##0)", false), "(5)");
+        assert_eq!(format_number(5., "#,##0 ;(#,##0)", false), "5 ");
+
+        let code = "[>=1000]#,##0,\"K\";0";
+        assert_eq!(format_number(25000., code, false), "25K");
+        assert_eq!(format_number(250., code, false), "250");
+    }
+
+    #[test]
+    fn test_format_dates() {
+        // 2021-06-30 12:30:45
+        let serial = 44377. + (12. * 3600. + 30. * 60. + 45.) / 86400.;
+        assert_eq!(format_number(serial, "yyyy-mm-dd", false), "2021-06-30");
+        assert_eq!(format_number(serial, "m/d/yyyy", false), "6/30/2021");
+        assert_eq!(format_number(serial, "d-mmm-yy", false), "30-Jun-21");
+        assert_eq!(
+            format_number(serial, "dddd, mmmm d, yyyy", false),
+            "Wednesday, June 30, 2021"
+        );
+        assert_eq!(format_number(serial, "h:mm AM/PM", false), "12:30 PM");
+        assert_eq!(format_number(serial, "hh:mm:ss", false), "12:30:45");
+        assert_eq!(
+            format_number(serial, "m/d/yyyy h:mm", false),
+            "6/30/2021 12:30"
+        );
+        assert_eq!(
+            format_number(serial - 1462., "yyyy-mm-dd", true),
+            "2021-06-30"
+        );
+
+        assert_eq!(format_number(1.5, "[h]:mm:ss", false), "36:00:00");
+        assert_eq!(format_number(0.25 / 24., "[mm]:ss", false), "15:00");
+        assert_eq!(format_number(1.25 / 86400., "mm:ss.0", false), "00:01.3");
+        assert_eq!(format_number(0.999999, "h:mm:ss", false), "0:00:00");
+
+        assert_eq!(format_number(60., "yyyy-mm-dd", false), "1900-02-29");
+        assert_eq!(
+            format_number(61., "yyyy-mm-dd dddd", false),
+            "1900-03-01 Thursday"
+        );
+    }
+
+    #[test]
+    fn test_iso_to_serial() {
+        assert_eq!(iso_to_serial("2021-06-30", false), Some(44377.));
+        assert_eq!(iso_to_serial("2021-06-30T12:00:00", false), Some(44377.5));
+        assert_eq!(iso_to_serial("2021-06-30", true), Some(44377. - 1462.));
+        assert_eq!(iso_to_serial("1900-01-01", false), Some(1.));
+        assert_eq!(iso_to_serial("not a date", false), None);
+        // out of range dates, which must not overflow
+        assert_eq!(
+            iso_to_serial("9223372036854775807-12-31T00:00:00", false),
+            None
+        );
+        assert_eq!(iso_to_serial("-9223372036854775808-01-01", false), None);
+        assert_eq!(iso_to_serial("10000-01-01", false), None);
+        assert_eq!(iso_to_serial("2020-00-00", false), None);
+        assert_eq!(iso_to_serial("2020-13-01", false), None);
+        assert_eq!(iso_to_serial("2020-4294967295-01", false), None);
+        assert_eq!(iso_to_serial("2020-01-32", false), None);
+    }
+
+    #[test]
+    fn test_builtin_format_code() {
+        assert_eq!(builtin_format_code(14), Some("m/d/yyyy"));
+        assert_eq!(builtin_format_code(49), Some("@"));
+        assert_eq!(builtin_format_code(164), None);
+    }
+
+    #[test]
+    fn test_float_format_builtin() {
+        let builtin = |id| CellStyle {
+            number_format_id: Some(id),
+            ..CellStyle::default()
+        };
+        let excel = |value, id| FloatFormat::Excel.format(value, Some(&builtin(id)), false);
+        assert_eq!(excel(0.1234, 10), "12.34%");
+        assert_eq!(excel(0.25, 9), "25%");
+        assert_eq!(excel(1234.5, 4), "1,234.50");
+        assert_eq!(excel(2.5, 2), "2.50");
+        assert_eq!(excel(45292.5, 22), "1/1/2024 12:00");
+        assert_eq!(
+            FloatFormat::Excel.format(45292.5, Some(&builtin(22)), true),
+            "1/2/2028 12:00"
+        );
+        // the custom format code wins over the id
+        let custom = CellStyle {
+            format_string: Some("0.0".into()),
+            number_format_id: Some(164),
+            ..CellStyle::default()
+        };
+        assert_eq!(FloatFormat::Excel.format(2.25, Some(&custom), false), "2.3");
+    }
+}
@@ -1554,6 +1554,7 @@ fn parse_xf(
     Ok(CellStyle {
         number_format,
         format_string,
+        number_format_id: Some(u32::from(fmt_code)),
         font: fonts.get(font_id).cloned(),
         fill: fills.get(fill_id).cloned(),
         border: borders.get(border_id).cloned(),
@@ -9,18 +9,102 @@ use std::{
 };
 
 use super::{
-    get_attribute, get_dimension, get_row, get_row_column, read_string, replace_cell_names,
-    ColumnDefinition, ColumnWidths, Dimensions, RowDefinition, RowDefinitions, XlReader,
+    extend_dimensions, get_attribute, get_dimension, get_row, get_row_column, read_merge_cells,
+    read_string, replace_cell_names, ColumnDefinition, ColumnWidths, Dimensions, PaneState,
+    RowDefinition, RowDefinitions, SheetPane, SheetView, SheetViewType, XlReader,
 };
 use crate::{
     datatype::DataRef,
     formats::{format_excel_f64_ref, CellFormat, CellStyle},
-    Cell, XlsxError,
+    number_format::format_general,
+    Cell, CellErrorType, XlsxError,
 };
 
 type FormulaMap = HashMap<(u32, u32), (i64, i64)>;
 type CellWithFormatting<'a> = (Cell<DataRef<'a>>, Option<&'a CellStyle>);
 
+/// Scratch buffers used while reading the cells of a worksheet
+///
+/// Each worksheet read needs a few temporary vectors, including the buffer
+/// the worksheet part is decompressed through. They are kept by the
+/// [`Xlsx`](crate::Xlsx) reader between sheets and can be moved from one
+/// workbook to the next with [`Xlsx::into_buffers`](crate::Xlsx::into_buffers)
+/// and [`Xlsx::with_buffers`](crate::Xlsx::with_buffers), so that a process
+/// reading many files does not reallocate them every time. A worksheet
+/// reader gives them back when dropped, also after a failed read.
+#[derive(Debug, Default)]
+pub struct ReaderBuffers {
+    buf: Vec<u8>,
+    cell_buf: Vec<u8>,
+    formulas: Vec<Option<(String, FormulaMap)>>,
+    spill_sources: Vec<Dimensions>,
+    read_buf: Vec<u8>,
+}
+
+impl ReaderBuffers {
+    /// Creates a new set of empty buffers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates buffers with the capacities used by a fresh worksheet reader
+    fn with_default_capacity() -> Self {
+        Self {
+            buf: Vec::with_capacity(1024),
+            cell_buf: Vec::with_capacity(1024),
+            formulas: Vec::with_capacity(1024),
+            spill_sources: Vec::with_capacity(32),
+            read_buf: Vec::new(),
+        }
+    }
+
+    /// Clears the buffers, keeping their allocated capacity
+    fn clear(&mut self) {
+        self.buf.clear();
+        self.cell_buf.clear();
+        self.formulas.clear();
+        self.spill_sources.clear();
+    }
+
+    /// Buffer the worksheet part is decompressed into
+    pub(crate) fn read_buf_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.read_buf
+    }
+}
+
+/// Type of the cached result of a formula cell
+///
+/// It is read from the `t` attribute of the cell, so the formula doesn't need
+/// to be evaluated to know whether it yields text or a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellResultType {
+    /// Number (or date) result
+    Number,
+    /// Text result
+    Text,
+    /// Boolean result
+    Bool,
+    /// Error result
+    Error,
+    /// No cached result
+    #[default]
+    Empty,
+}
+
+/// How error cells (e.g. `#N/A`) are read
+///
+/// See [`Xlsx::with_errors_as`](crate::Xlsx::with_errors_as).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Read errors as [`Data::Error`](crate::Data::Error)
+    #[default]
+    Keep,
+    /// Read errors as empty cells
+    Empty,
+    /// Read errors as their Excel text, e.g. `"#DIV/0!"`
+    String,
+}
+
 /// An xlsx Cell Iterator
 pub struct XlsxCellReader<'a, RS>
 where
@@ -31,6 +115,8 @@ where
     formats: &'a [CellStyle],
     is_1904: bool,
     dimensions: Dimensions,
+    // Extent of the cells read so far
+    computed_dimensions: Option<Dimensions>,
     row_index: u32,
     col_index: u32,
     buf: Vec<u8>,
@@ -42,219 +128,136 @@ where
     spill_sources: Vec<Dimensions>,
     // Whether the last returned cell had its own <f> formula element
     last_cell_had_formula: bool,
+    // Style index (`s` attribute) of the last cell returned with its formatting
+    last_cell_style_index: Option<usize>,
+    // Type of the cached result of the last formula returned
+    last_formula_result_type: CellResultType,
+    // Maximum number of rows and columns of the worksheet, if limited
+    max_rows: Option<u32>,
+    max_cols: Option<u32>,
+    // How error cells are read
+    error_policy: ErrorPolicy,
+    // Cell formats whose number format is text ("@"), if read as strings
+    text_styles: Vec<bool>,
+    // Columns span declared by the `spans` attribute of the current row
+    row_spans: Option<(u32, u32)>,
+    // First sheet view, with its pane
+    view: SheetView,
+    // Buffers of the workbook, given back when the reader is dropped
+    pool: Option<&'a mut ReaderBuffers>,
 }
 
 impl<'a, RS> XlsxCellReader<'a, RS>
 where
     RS: Read + Seek,
 {
-    pub fn new(
-        mut xml: XlReader<'a, RS>,
+    pub(crate) fn new(
+        xml: XlReader<'a, RS>,
         strings: &'a [String],
         formats: &'a [CellStyle],
         is_1904: bool,
     ) -> Result<Self, XlsxError> {
-        let mut buf = Vec::with_capacity(1024);
-        let mut dimensions = Dimensions::default();
-        let mut column_widths = ColumnWidths::new();
-        let mut row_definitions = RowDefinitions::new();
-        let mut sh_type = None;
-        'xml: loop {
-            buf.clear();
-            match xml.read_event_into(&mut buf).map_err(XlsxError::Xml)? {
-                Event::Start(ref e) => match e.local_name().as_ref() {
-                    b"dimension" => {
-                        for a in e.attributes() {
-                            if let Attribute {
-                                key: QName(b"ref"),
-                                value: rdim,
-                            } = a.map_err(XlsxError::XmlAttr)?
-                            {
-                                dimensions = get_dimension(&rdim)?;
-                                continue 'xml;
-                            }
-                        }
-                        return Err(XlsxError::UnexpectedNode("dimension"));
-                    }
-                    b"sheetFormatPr" => {
-                        // Parse sheet format properties - store raw values
-                        for a in e.attributes() {
-                            match a.map_err(XlsxError::XmlAttr)? {
-                                Attribute {
-                                    key: QName(b"defaultColWidth"),
-                                    value: v,
-                                } => {
-                                    if let Ok(width_str) = xml.decoder().decode(&v) {
-                                        if let Ok(width) = width_str.parse::<f64>() {
-                                            column_widths.sheet_format.default_col_width =
-                                                Some(width);
-                                            row_definitions.sheet_format.default_col_width =
-                                                Some(width);
-                                        }
-                                    }
-                                }
-                                Attribute {
-                                    key: QName(b"baseColWidth"),
-                                    value: v,
-                                } => {
-                                    if let Ok(width_str) = xml.decoder().decode(&v) {
-                                        if let Ok(width) = width_str.parse::<u8>() {
-                                            column_widths.sheet_format.base_col_width = Some(width);
-                                            row_definitions.sheet_format.base_col_width =
-                                                Some(width);
-                                        }
-                                    }
-                                }
-                                Attribute {
-                                    key: QName(b"defaultRowHeight"),
-                                    value: v,
-                                } => {
-                                    if let Ok(height_str) = xml.decoder().decode(&v) {
-                                        if let Ok(height) = height_str.parse::<f64>() {
-                                            column_widths.sheet_format.default_row_height =
-                                                Some(height);
-                                            row_definitions.sheet_format.default_row_height =
-                                                Some(height);
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    b"cols" => {
-                        // Parse column definitions - store raw values
-                        let mut inner_buf = Vec::with_capacity(512);
-                        loop {
-                            inner_buf.clear();
-                            match xml
-                                .read_event_into(&mut inner_buf)
-                                .map_err(XlsxError::Xml)?
-                            {
-                                Event::Start(ref col) | Event::Empty(ref col)
-                                    if col.local_name().as_ref() == b"col" =>
-                                {
-                                    let mut def = ColumnDefinition {
-                                        min: 1,
-                                        max: 1,
-                                        width: None,
-                                        style: None,
-                                        custom_width: None,
-                                        best_fit: None,
-                                        hidden: None,
-                                        outline_level: None,
-                                        collapsed: None,
-                                    };
-
-                                    for a in col.attributes() {
-                                        match a.map_err(XlsxError::XmlAttr)? {
-                                            Attribute {
-                                                key: QName(b"min"),
-                                                value: v,
-                                            } => {
-                                                def.min = atoi_simd::parse::<u32>(&v).unwrap_or(1);
-                                            }
-                                            Attribute {
-                                                key: QName(b"max"),
-                                                value: v,
-                                            } => {
-                                                def.max = atoi_simd::parse::<u32>(&v).unwrap_or(1);
-                                            }
-                                            Attribute {
-                                                key: QName(b"width"),
-                                                value: v,
-                                            } => {
-                                                if let Ok(width_str) = xml.decoder().decode(&v) {
-                                                    if let Ok(width) = width_str.parse::<f64>() {
-                                                        def.width = Some(width);
-                                                    }
-                                                }
-                                            }
-                                            Attribute {
-                                                key: QName(b"style"),
-                                                value: v,
-                                            } => {
-                                                def.style = atoi_simd::parse::<u32>(&v).ok();
-                                            }
-                                            Attribute {
-                                                key: QName(b"customWidth"),
-                                                value: v,
-                                            } => {
-                                                def.custom_width =
-                                                    Some(&*v == b"1" || &*v == b"true");
-                                            }
-                                            Attribute {
-                                                key: QName(b"bestFit"),
-                                                value: v,
-                                            } => {
-                                                def.best_fit = Some(&*v == b"1" || &*v == b"true");
-                                            }
-                                            Attribute {
-                                                key: QName(b"hidden"),
-                                                value: v,
-                                            } => {
-                                                def.hidden = Some(&*v == b"1" || &*v == b"true");
-                                            }
-                                            Attribute {
-                                                key: QName(b"outlineLevel"),
-                                                value: v,
-                                            } => {
-                                                def.outline_level = atoi_simd::parse::<u8>(&v).ok();
-                                            }
-                                            Attribute {
-                                                key: QName(b"collapsed"),
-                                                value: v,
-                                            } => {
-                                                def.collapsed = Some(&*v == b"1" || &*v == b"true");
-                                            }
-                                            _ => {}
-                                        }
-                                    }
+        Self::from_buffers(
+            xml,
+            strings,
+            formats,
+            is_1904,
+            &mut ReaderBuffers::with_default_capacity(),
+        )
+    }
 
-                                    // Store raw column definition without conversion
-                                    column_widths.add_column_definition(def);
-                                }
-                                Event::End(ref e) if e.local_name().as_ref() == b"cols" => break,
-                                Event::Eof => return Err(XlsxError::XmlEof("cols")),
-                                _ => {}
-                            }
-                        }
-                    }
-                    b"sheetData" => break,
-                    typ => {
-                        if sh_type.is_none() {
-                            sh_type = Some(xml.decoder().decode(typ)?.to_string());
-                        }
-                    }
-                },
-                Event::Eof => {
-                    if let Some(typ) = sh_type {
-                        return Err(XlsxError::NotAWorksheet(typ));
-                    } else {
-                        return Err(XlsxError::XmlEof("worksheet"));
-                    }
+    /// Creates a new cell reader reusing the scratch buffers of `pool`
+    ///
+    /// The buffers are moved back into `pool` when the reader is dropped,
+    /// including when reading the worksheet fails part way.
+    pub(crate) fn with_buffers(
+        xml: XlReader<'a, RS>,
+        strings: &'a [String],
+        formats: &'a [CellStyle],
+        is_1904: bool,
+        pool: &'a mut ReaderBuffers,
+    ) -> Result<Self, XlsxError> {
+        let mut reader = Self::from_buffers(xml, strings, formats, is_1904, pool)?;
+        reader.pool = Some(pool);
+        Ok(reader)
+    }
+
+    fn from_buffers(
+        mut xml: XlReader<'a, RS>,
+        strings: &'a [String],
+        formats: &'a [CellStyle],
+        is_1904: bool,
+        buffers: &mut ReaderBuffers,
+    ) -> Result<Self, XlsxError> {
+        buffers.clear();
+        let (dimensions, column_widths, row_definitions, view) =
+            match read_sheet_header(&mut xml, &mut buffers.buf) {
+                Ok(header) => header,
+                Err(e) => {
+                    buffers.read_buf = xml.get_mut().take_buf();
+                    return Err(e);
                 }
-                _ => (),
-            }
-        }
+            };
         Ok(Self {
             xml,
             strings,
             formats,
             is_1904,
             dimensions,
+            computed_dimensions: None,
             row_index: 0,
             col_index: 0,
-            buf: Vec::with_capacity(1024),
-            cell_buf: Vec::with_capacity(1024),
-            formulas: Vec::with_capacity(1024),
+            buf: std::mem::take(&mut buffers.buf),
+            cell_buf: std::mem::take(&mut buffers.cell_buf),
+            formulas: std::mem::take(&mut buffers.formulas),
             column_widths,
             row_definitions,
-            spill_sources: Vec::with_capacity(32),
+            spill_sources: std::mem::take(&mut buffers.spill_sources),
             last_cell_had_formula: false,
+            last_cell_style_index: None,
+            last_formula_result_type: CellResultType::Empty,
+            max_rows: None,
+            max_cols: None,
+            error_policy: ErrorPolicy::Keep,
+            text_styles: Vec::new(),
+            row_spans: None,
+            view,
+            pool: None,
         })
     }
 
+    /// Sets how error cells are read
+    pub(crate) fn set_error_policy(&mut self, error_policy: ErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Reads numbers and booleans of the cell formats flagged as text as strings
+    pub(crate) fn set_text_styles(&mut self, text_styles: Vec<bool>) {
+        self.text_styles = text_styles;
+    }
+
+    /// Fails with [`XlsxError::LimitExceeded`] on cells beyond these limits
+    pub(crate) fn set_limits(&mut self, max_rows: Option<u32>, max_cols: Option<u32>) {
+        self.max_rows = max_rows;
+        self.max_cols = max_cols;
+    }
+
+    /// Consumes the reader, returning its scratch buffers for reuse
+    pub fn into_buffers(mut self) -> ReaderBuffers {
+        self.pool = None;
+        self.take_buffers()
+    }
+
+    fn take_buffers(&mut self) -> ReaderBuffers {
+        ReaderBuffers {
+            buf: std::mem::take(&mut self.buf),
+            cell_buf: std::mem::take(&mut self.cell_buf),
+            formulas: std::mem::take(&mut self.formulas),
+            spill_sources: std::mem::take(&mut self.spill_sources),
+            read_buf: self.xml.get_mut().take_buf(),
+        }
+    }
+
     /// Check if an absolute position is within any recorded spill source range
     pub fn is_in_spill(&self, pos: (u32, u32)) -> bool {
         let (row, col) = pos;
@@ -266,10 +269,63 @@ where
         self.last_cell_had_formula
     }
 
+    /// Columns (first, last) declared as populated by the current row
+    ///
+    /// The span is read from the optional `spans` attribute of the row of the
+    /// last cell returned, a hint written by Excel to size the row before
+    /// reading its cells. Columns are zero indexed and inclusive.
+    pub fn row_spans(&self) -> Option<(u32, u32)> {
+        self.row_spans
+    }
+
+    /// Style index of the last cell returned by `next_cell_with_formatting`
+    pub fn last_cell_style_index(&self) -> Option<usize> {
+        self.last_cell_style_index
+    }
+
+    /// Type of the cached result of the last formula returned by `next_formula`
+    pub fn last_formula_result_type(&self) -> CellResultType {
+        self.last_formula_result_type
+    }
+
+    /// Number of shared formulas read so far by `next_formula`
+    ///
+    /// A shared formula is only kept once its master cell, the cell holding
+    /// the formula text and its `ref` range, is read. Dependent cells read
+    /// before it, or outside its range, get an empty formula.
+    pub fn shared_formula_count(&self) -> usize {
+        self.formulas.iter().flatten().count()
+    }
+
+    /// Get the shared formulas read so far by `next_formula`
+    ///
+    /// Each item is the shared index (`si`) of the formula, the formula of
+    /// its master cell and the number of cells of its `ref` range.
+    pub fn shared_formulas(&self) -> impl Iterator<Item = (usize, &str, usize)> + '_ {
+        self.formulas
+            .iter()
+            .enumerate()
+            .filter_map(|(si, formula)| {
+                formula
+                    .as_ref()
+                    .map(|(formula, offsets)| (si, formula.as_str(), offsets.len()))
+            })
+    }
+
     pub fn dimensions(&self) -> Dimensions {
         self.dimensions
     }
 
+    /// Get the extent of the cells read so far, `None` before the first cell
+    ///
+    /// Unlike [`XlsxCellReader::dimensions`], declared by the optional
+    /// `<dimension>` element and `A1` if it is missing, this is computed from
+    /// the position of the cells as they are read, so it is the true extent
+    /// of the worksheet once all the cells are read.
+    pub fn computed_dimensions(&self) -> Option<Dimensions> {
+        self.computed_dimensions
+    }
+
     /// Get column widths information
     pub fn column_widths(&self) -> &ColumnWidths {
         &self.column_widths
@@ -280,6 +336,34 @@ where
         &self.row_definitions
     }
 
+    /// Get the split or frozen pane of the first sheet view, if any
+    pub fn pane(&self) -> Option<&SheetPane> {
+        self.view.pane.as_ref()
+    }
+
+    /// Get the display settings of the first sheet view
+    pub fn view(&self) -> &SheetView {
+        &self.view
+    }
+
+    /// Read the merged cells, which come after the cells
+    ///
+    /// Must be called once all the cells are read, it consumes the rest of
+    /// the worksheet.
+    pub fn merge_cells(&mut self) -> Result<Vec<Dimensions>, XlsxError> {
+        loop {
+            self.buf.clear();
+            match self.xml.read_event_into(&mut self.buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"mergeCells" => {
+                    return read_merge_cells(&mut self.xml);
+                }
+                Ok(Event::Eof) => return Ok(Vec::new()),
+                Err(e) => return Err(e.into()),
+                _ => (),
+            }
+        }
+    }
+
     pub fn next_cell(&mut self) -> Result<Option<Cell<DataRef<'a>>>, XlsxError> {
         self.next_cell_with_formatting()
             .map(|opt| opt.map(|(cell, _)| cell))
@@ -295,6 +379,8 @@ where
                 Ok(Event::Start(ref row_element))
                     if row_element.local_name().as_ref() == b"row" =>
                 {
+                    self.row_spans = get_attribute(row_element.attributes(), QName(b"spans"))?
+                        .and_then(parse_spans);
                     let attribute = get_attribute(row_element.attributes(), QName(b"r"))?;
                     if let Some(range) = attribute {
                         let row = get_row(range)?;
@@ -305,6 +391,7 @@ where
                             r: row,
                             height: None,
                             style: None,
+                            custom_format: None,
                             custom_height: None,
                             hidden: None,
                             outline_level: None,
@@ -332,6 +419,12 @@ where
                                 } => {
                                     row_def.style = atoi_simd::parse::<u32>(&v).ok();
                                 }
+                                Attribute {
+                                    key: QName(b"customFormat"),
+                                    value: v,
+                                } => {
+                                    row_def.custom_format = Some(&*v == b"1" || &*v == b"true");
+                                }
                                 Attribute {
                                     key: QName(b"customHeight"),
                                     value: v,
@@ -399,14 +492,20 @@ where
                     } else {
                         (self.row_index, self.col_index)
                     };
+                    check_limits(pos, self.max_rows, self.max_cols)?;
+                    extend_dimensions(&mut self.computed_dimensions, pos);
                     
                     // Extract formatting information from the cell element
                     let cell_formatting = match get_attribute(c_element.attributes(), QName(b"s")) {
                         Ok(Some(style)) => {
                             let id = atoi_simd::parse::<usize>(style).unwrap_or(0);
+                            self.last_cell_style_index = Some(id);
                             self.formats.get(id)
                         }
-                        _ => None,
+                        _ => {
+                            self.last_cell_style_index = None;
+                            None
+                        }
                     };
                     
                     let mut value = DataRef::Empty;
@@ -434,6 +533,7 @@ where
                                     self.strings,
                                     self.formats,
                                     self.is_1904,
+                                    self.error_policy,
                                     &mut self.xml,
                                     e,
                                     c_element,
@@ -457,19 +557,34 @@ where
                             }
                             Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c" => break,
                             Ok(Event::Eof) => return Err(XlsxError::XmlEof("c")),
-                            Err(e) => return Err(XlsxError::Xml(e)),
+                            Err(e) => return Err(e.into()),
                             _ => (),
                         }
                     }
                     self.col_index += 1;
                     self.last_cell_had_formula = had_formula;
+                    let is_text = self
+                        .last_cell_style_index
+                        .and_then(|id| self.text_styles.get(id))
+                        .copied()
+                        .unwrap_or(false);
+                    if is_text {
+                        value = match value {
+                            DataRef::Int(v) => DataRef::String(v.to_string()),
+                            DataRef::Float(v) => DataRef::String(format_general(v)),
+                            DataRef::Bool(v) => {
+                                DataRef::String(if v { "TRUE" } else { "FALSE" }.to_string())
+                            }
+                            v => v,
+                        };
+                    }
                     return Ok(Some((Cell::new(pos, value), cell_formatting)));
                 }
                 Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sheetData" => {
                     return Ok(None);
                 }
                 Ok(Event::Eof) => return Err(XlsxError::XmlEof("sheetData")),
-                Err(e) => return Err(XlsxError::Xml(e)),
+                Err(e) => return Err(e.into()),
                 _ => (),
             }
         }
@@ -495,6 +610,8 @@ where
                 Ok(Event::Start(ref row_element))
                     if row_element.local_name().as_ref() == b"row" =>
                 {
+                    self.row_spans = get_attribute(row_element.attributes(), QName(b"spans"))?
+                        .and_then(parse_spans);
                     let attribute = get_attribute(row_element.attributes(), QName(b"r"))?;
                     if let Some(range) = attribute {
                         let row = get_row(range)?;
@@ -514,6 +631,8 @@ where
                     } else {
                         (self.row_index, self.col_index)
                     };
+                    check_limits(pos, self.max_rows, self.max_cols)?;
+                    extend_dimensions(&mut self.computed_dimensions, pos);
 
                     // Extract formatting information from the cell element
                     let cell_formatting = match get_attribute(c_element.attributes(), QName(b"s")) {
@@ -523,12 +642,20 @@ where
                         }
                         _ => None,
                     };
+                    let result_type = match get_attribute(c_element.attributes(), QName(b"t"))? {
+                        Some(b"s" | b"str" | b"inlineStr") => CellResultType::Text,
+                        Some(b"b") => CellResultType::Bool,
+                        Some(b"e") => CellResultType::Error,
+                        _ => CellResultType::Number,
+                    };
 
                     let mut value = None;
+                    let mut has_value = false;
                     loop {
                         self.cell_buf.clear();
                         match self.xml.read_event_into(&mut self.cell_buf) {
                             Ok(Event::Start(ref e)) => {
+                                has_value |= matches!(e.local_name().as_ref(), b"v" | b"is");
                                 let formula = read_formula(&mut self.xml, e)?;
                                 if let Some(f) = formula.borrow() {
                                     value = Some(f.clone());
@@ -616,11 +743,16 @@ where
                             }
                             Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c" => break,
                             Ok(Event::Eof) => return Err(XlsxError::XmlEof("c")),
-                            Err(e) => return Err(XlsxError::Xml(e)),
+                            Err(e) => return Err(e.into()),
                             _ => (),
                         }
                     }
                     self.col_index += 1;
+                    self.last_formula_result_type = if has_value {
+                        result_type
+                    } else {
+                        CellResultType::Empty
+                    };
                     return Ok(Some((
                         Cell::new(pos, value.unwrap_or_default()),
                         cell_formatting,
@@ -630,7 +762,7 @@ where
                     return Ok(None);
                 }
                 Ok(Event::Eof) => return Err(XlsxError::XmlEof("sheetData")),
-                Err(e) => return Err(XlsxError::Xml(e)),
+                Err(e) => return Err(e.into()),
                 _ => (),
             }
         }
@@ -641,6 +773,7 @@ fn read_value_with_formatting<'s, 'f, RS>(
     strings: &'s [String],
     formats: &'f [CellStyle],
     is_1904: bool,
+    error_policy: ErrorPolicy,
     xml: &mut XlReader<'_, RS>,
     e: &BytesStart<'_>,
     c_element: &BytesStart<'_>,
@@ -681,6 +814,7 @@ where
                 cell_formatting.map(|f| &f.number_format),
                 c_element,
                 is_1904,
+                error_policy,
             )?
         }
         b"f" => {
@@ -700,6 +834,7 @@ fn read_v<'s>(
     cell_format: Option<&CellFormat>,
     c_element: &BytesStart<'_>,
     is_1904: bool,
+    error_policy: ErrorPolicy,
 ) -> Result<DataRef<'s>, XlsxError> {
     match get_attribute(c_element.attributes(), QName(b"t"))? {
         Some(b"s") => {
@@ -713,7 +848,13 @@ fn read_v<'s>(
         }
         Some(b"e") => {
             // error
-            Ok(DataRef::Error(v.parse()?))
+            let error: CellErrorType = v.parse()?;
+            Ok(match error_policy {
+                ErrorPolicy::Keep => DataRef::Error(error),
+                ErrorPolicy::Empty => DataRef::Empty,
+                // The text of numeric error codes is the Excel one
+                ErrorPolicy::String => DataRef::String(error.to_string()),
+            })
         }
         Some(b"d") => {
             // date
@@ -754,6 +895,301 @@ fn read_v<'s>(
     }
 }
 
+/// Parse the `spans` attribute of a row, e.g. `1:8` or `1:3 5:8`, into its
+/// first and last columns (zero indexed)
+fn parse_spans(spans: &[u8]) -> Option<(u32, u32)> {
+    let spans = std::str::from_utf8(spans).ok()?;
+    let (first, _) = spans.split_whitespace().next()?.split_once(':')?;
+    let (_, last) = spans.split_whitespace().next_back()?.split_once(':')?;
+    let first = first.parse::<u32>().ok()?.checked_sub(1)?;
+    let last = last.parse::<u32>().ok()?.checked_sub(1)?;
+    (first <= last).then_some((first, last))
+}
+
+/// Read the attributes of a `<pane>` element
+///
+/// Invalid attributes are skipped, view settings never fail a cell read.
+impl<RS> Drop for XlsxCellReader<'_, RS>
+where
+    RS: Read + Seek,
+{
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            *pool = self.take_buffers();
+        }
+    }
+}
+
+/// Reads the worksheet up to `<sheetData>`: its dimensions, column and row
+/// definitions and first sheet view
+fn read_sheet_header<RS: Read + Seek>(
+    xml: &mut XlReader<'_, RS>,
+    buf: &mut Vec<u8>,
+) -> Result<(Dimensions, ColumnWidths, RowDefinitions, SheetView), XlsxError> {
+    let mut dimensions = Dimensions::default();
+    let mut column_widths = ColumnWidths::new();
+    let mut row_definitions = RowDefinitions::new();
+    let mut sh_type = None;
+    let mut view = None;
+    let mut pane = None;
+    'xml: loop {
+        buf.clear();
+        match xml.read_event_into(buf).map_err(XlsxError::from)? {
+            Event::Start(ref e) => match e.local_name().as_ref() {
+                b"dimension" => {
+                    for a in e.attributes() {
+                        if let Attribute {
+                            key: QName(b"ref"),
+                            value: rdim,
+                        } = a.map_err(XlsxError::XmlAttr)?
+                        {
+                            dimensions = get_dimension(&rdim)?;
+                            continue 'xml;
+                        }
+                    }
+                    return Err(XlsxError::UnexpectedNode("dimension"));
+                }
+                b"sheetFormatPr" => {
+                    // Parse sheet format properties - store raw values
+                    for a in e.attributes() {
+                        match a.map_err(XlsxError::XmlAttr)? {
+                            Attribute {
+                                key: QName(b"defaultColWidth"),
+                                value: v,
+                            } => {
+                                if let Ok(width_str) = xml.decoder().decode(&v) {
+                                    if let Ok(width) = width_str.parse::<f64>() {
+                                        column_widths.sheet_format.default_col_width = Some(width);
+                                        row_definitions.sheet_format.default_col_width =
+                                            Some(width);
+                                    }
+                                }
+                            }
+                            Attribute {
+                                key: QName(b"baseColWidth"),
+                                value: v,
+                            } => {
+                                if let Ok(width_str) = xml.decoder().decode(&v) {
+                                    if let Ok(width) = width_str.parse::<u8>() {
+                                        column_widths.sheet_format.base_col_width = Some(width);
+                                        row_definitions.sheet_format.base_col_width = Some(width);
+                                    }
+                                }
+                            }
+                            Attribute {
+                                key: QName(b"defaultRowHeight"),
+                                value: v,
+                            } => {
+                                if let Ok(height_str) = xml.decoder().decode(&v) {
+                                    if let Ok(height) = height_str.parse::<f64>() {
+                                        column_widths.sheet_format.default_row_height =
+                                            Some(height);
+                                        row_definitions.sheet_format.default_row_height =
+                                            Some(height);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                b"cols" => {
+                    // Parse column definitions - store raw values
+                    let mut inner_buf = Vec::with_capacity(512);
+                    loop {
+                        inner_buf.clear();
+                        match xml
+                            .read_event_into(&mut inner_buf)
+                            .map_err(XlsxError::from)?
+                        {
+                            Event::Start(ref col) | Event::Empty(ref col)
+                                if col.local_name().as_ref() == b"col" =>
+                            {
+                                let mut def = ColumnDefinition {
+                                    min: 1,
+                                    max: 1,
+                                    width: None,
+                                    style: None,
+                                    custom_width: None,
+                                    best_fit: None,
+                                    hidden: None,
+                                    outline_level: None,
+                                    collapsed: None,
+                                };
+
+                                for a in col.attributes() {
+                                    match a.map_err(XlsxError::XmlAttr)? {
+                                        Attribute {
+                                            key: QName(b"min"),
+                                            value: v,
+                                        } => {
+                                            def.min = atoi_simd::parse::<u32>(&v).unwrap_or(1);
+                                        }
+                                        Attribute {
+                                            key: QName(b"max"),
+                                            value: v,
+                                        } => {
+                                            def.max = atoi_simd::parse::<u32>(&v).unwrap_or(1);
+                                        }
+                                        Attribute {
+                                            key: QName(b"width"),
+                                            value: v,
+                                        } => {
+                                            if let Ok(width_str) = xml.decoder().decode(&v) {
+                                                if let Ok(width) = width_str.parse::<f64>() {
+                                                    def.width = Some(width);
+                                                }
+                                            }
+                                        }
+                                        Attribute {
+                                            key: QName(b"style"),
+                                            value: v,
+                                        } => {
+                                            def.style = atoi_simd::parse::<u32>(&v).ok();
+                                        }
+                                        Attribute {
+                                            key: QName(b"customWidth"),
+                                            value: v,
+                                        } => {
+                                            def.custom_width = Some(&*v == b"1" || &*v == b"true");
+                                        }
+                                        Attribute {
+                                            key: QName(b"bestFit"),
+                                            value: v,
+                                        } => {
+                                            def.best_fit = Some(&*v == b"1" || &*v == b"true");
+                                        }
+                                        Attribute {
+                                            key: QName(b"hidden"),
+                                            value: v,
+                                        } => {
+                                            def.hidden = Some(&*v == b"1" || &*v == b"true");
+                                        }
+                                        Attribute {
+                                            key: QName(b"outlineLevel"),
+                                            value: v,
+                                        } => {
+                                            def.outline_level = atoi_simd::parse::<u8>(&v).ok();
+                                        }
+                                        Attribute {
+                                            key: QName(b"collapsed"),
+                                            value: v,
+                                        } => {
+                                            def.collapsed = Some(&*v == b"1" || &*v == b"true");
+                                        }
+                                        _ => {}
+                                    }
+                                }
+
+                                // Store raw column definition without conversion
+                                column_widths.add_column_definition(def);
+                            }
+                            Event::End(ref e) if e.local_name().as_ref() == b"cols" => break,
+                            Event::Eof => return Err(XlsxError::XmlEof("cols")),
+                            _ => {}
+                        }
+                    }
+                }
+                b"sheetView" if view.is_none() => view = Some(read_sheet_view(e)),
+                b"pane" if pane.is_none() => pane = Some(read_pane(e)),
+                b"sheetData" => break,
+                typ => {
+                    if sh_type.is_none() {
+                        sh_type = Some(xml.decoder().decode(typ)?.to_string());
+                    }
+                }
+            },
+            Event::Eof => {
+                if let Some(typ) = sh_type {
+                    return Err(XlsxError::NotAWorksheet(typ));
+                } else {
+                    return Err(XlsxError::XmlEof("worksheet"));
+                }
+            }
+            _ => (),
+        }
+    }
+    let view = SheetView {
+        pane,
+        ..view.unwrap_or_default()
+    };
+    Ok((dimensions, column_widths, row_definitions, view))
+}
+
+fn read_pane(e: &BytesStart<'_>) -> SheetPane {
+    let mut pane = SheetPane::default();
+    for a in e.attributes().filter_map(Result::ok) {
+        let number = || std::str::from_utf8(&a.value).ok()?.parse().ok();
+        match a.key {
+            QName(b"xSplit") => pane.x_split = number().unwrap_or(pane.x_split),
+            QName(b"ySplit") => pane.y_split = number().unwrap_or(pane.y_split),
+            QName(b"topLeftCell") => pane.top_left_cell = get_row_column(&a.value).ok(),
+            QName(b"state") => {
+                pane.state = match &*a.value {
+                    b"frozen" => PaneState::Frozen,
+                    b"frozenSplit" => PaneState::FrozenSplit,
+                    _ => PaneState::Split,
+                }
+            }
+            _ => (),
+        }
+    }
+    pane
+}
+
+/// Read the attributes of a `<sheetView>` element
+///
+/// Invalid attributes are skipped, like those of [`read_pane`].
+fn read_sheet_view(e: &BytesStart<'_>) -> SheetView {
+    let mut view = SheetView::default();
+    for a in e.attributes().filter_map(Result::ok) {
+        match a.key {
+            QName(b"showGridLines") => {
+                view.show_grid_lines = !matches!(&*a.value, b"0" | b"false");
+            }
+            QName(b"showRowColHeaders") => {
+                view.show_row_col_headers = !matches!(&*a.value, b"0" | b"false");
+            }
+            QName(b"zoomScale") => {
+                if let Some(zoom) = std::str::from_utf8(&a.value)
+                    .ok()
+                    .and_then(|z| z.parse().ok())
+                {
+                    view.zoom_scale = zoom;
+                }
+            }
+            QName(b"view") => {
+                view.view_type = match &*a.value {
+                    b"pageLayout" => SheetViewType::PageLayout,
+                    b"pageBreakPreview" => SheetViewType::PageBreakPreview,
+                    _ => SheetViewType::Normal,
+                }
+            }
+            _ => (),
+        }
+    }
+    view
+}
+
+/// Check that a cell position is within the row and column limits
+fn check_limits(
+    (row, col): (u32, u32),
+    max_rows: Option<u32>,
+    max_cols: Option<u32>,
+) -> Result<(), XlsxError> {
+    match (max_rows, max_cols) {
+        (Some(max), _) if row >= max => Err(XlsxError::LimitExceeded {
+            limit: "max_rows",
+            max: max.into(),
+        }),
+        (_, Some(max)) if col >= max => Err(XlsxError::LimitExceeded {
+            limit: "max_cols",
+            max: max.into(),
+        }),
+        _ => Ok(()),
+    }
+}
+
 fn read_formula<RS>(xml: &mut XlReader<RS>, e: &BytesStart) -> Result<Option<String>, XlsxError>
 where
     RS: Read + Seek,
@@ -0,0 +1,58 @@
+use crate::Dimensions;
+
+/// Kind of a chart, from the first chart of its plot area
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChartType {
+    /// Bar or column chart (`barChart`, `bar3DChart`)
+    Bar,
+    /// Line chart (`lineChart`, `line3DChart`)
+    Line,
+    /// Pie chart (`pieChart`, `pie3DChart`, `ofPieChart`)
+    Pie,
+    /// Doughnut chart (`doughnutChart`)
+    Doughnut,
+    /// Area chart (`areaChart`, `area3DChart`)
+    Area,
+    /// Scatter chart (`scatterChart`)
+    Scatter,
+    /// Radar chart (`radarChart`)
+    Radar,
+    /// Bubble chart (`bubbleChart`)
+    Bubble,
+    /// Any other chart, with the name of its element, e.g. `stockChart`
+    Other(String),
+}
+
+/// A series of a chart and the cells it plots (`<c:ser>`)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SeriesRef {
+    /// Name of the series, as last calculated (`<c:tx>`)
+    pub name: Option<String>,
+    /// Sheet of the values range, `None` if the reference has no sheet name
+    pub values_sheet: Option<String>,
+    /// Values range (`<c:val>`, or `<c:yVal>` of scatter and bubble charts)
+    pub values: Option<Dimensions>,
+    /// Sheet of the categories range, `None` if the reference has no sheet
+    /// name
+    pub categories_sheet: Option<String>,
+    /// Categories range (`<c:cat>`, or `<c:xVal>` of scatter and bubble
+    /// charts)
+    pub categories: Option<Dimensions>,
+}
+
+/// A chart drawn over a worksheet (`<c:chartSpace>` in a chart part)
+///
+/// Charts are placed by the drawing part linked to the worksheet, e.g.
+/// `xl/drawings/drawing1.xml`, and defined in their own part, e.g.
+/// `xl/charts/chart1.xml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartInfo {
+    /// Path of the chart part in the archive
+    pub path: String,
+    /// Title of the chart, `None` if it has no explicit title
+    pub title: Option<String>,
+    /// Kind of the chart
+    pub chart_type: ChartType,
+    /// Series of the chart, in plot order
+    pub series: Vec<SeriesRef>,
+}
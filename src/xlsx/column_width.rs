@@ -82,6 +82,8 @@ pub struct RowDefinition {
     pub height: Option<f64>,
     /// s attribute: Style index (0-65429)
     pub style: Option<u32>,
+    /// customFormat attribute: the `style` applies to the row
+    pub custom_format: Option<bool>,
     /// customHeight attribute
     pub custom_height: Option<bool>,
     /// hidden attribute
@@ -0,0 +1,19 @@
+/// A defined name of the workbook (`<definedName>` in `workbook.xml`)
+///
+/// Besides ranges and constants, defined names register the functions of
+/// macro sheets and add-ins, which Excel lists in the function wizard under
+/// their function group.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DefinedName {
+    /// Name, e.g. `Sales` (`name`)
+    pub name: String,
+    /// Formula the name refers to, e.g. `Sheet1!$A$1:$A$10`
+    pub formula: String,
+    /// Whether the name is a function, rather than a data name (`function`)
+    ///
+    /// Names of XLL add-in functions, prefixed by `_xll.`, are functions too.
+    pub is_function: bool,
+    /// Function group (category) of the function, e.g. `14` for
+    /// "User Defined" (`functionGroupId`)
+    pub function_group_id: Option<u32>,
+}
@@ -0,0 +1,17 @@
+use crate::{CellDiff, Data};
+
+/// A difference between two workbooks, see [`super::Xlsx::diff_workbook`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkbookDiff {
+    /// Sheet only present in the first workbook
+    SheetOnlyInLeft(String),
+    /// Sheet only present in the second workbook
+    SheetOnlyInRight(String),
+    /// Cell whose value differs in a sheet present in both workbooks
+    Cell {
+        /// Name of the sheet
+        sheet: String,
+        /// Position and values of the cell
+        diff: CellDiff<Data>,
+    },
+}
@@ -0,0 +1,46 @@
+/// A picture placed over a worksheet (`<xdr:pic>` in a drawing part)
+///
+/// Pictures are anchored to the worksheet cells by the drawing part linked
+/// to the worksheet, e.g. `xl/drawings/drawing1.xml`, and their image is
+/// stored in its own part, e.g. `xl/media/image1.png`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WorksheetPicture {
+    /// How the picture is anchored to the worksheet
+    pub anchor_kind: AnchorKind,
+    /// Top left cell (row, col) the picture is anchored to, `(0, 0)` for
+    /// absolute anchors
+    pub from: (u32, u32),
+    /// Offset (row, col) of the top left corner within the `from` cell, in EMUs
+    pub from_offset: (i64, i64),
+    /// Bottom right cell (row, col) of a two cell anchor
+    pub to: Option<(u32, u32)>,
+    /// Offset (row, col) of the bottom right corner within the `to` cell, in
+    /// EMUs
+    pub to_offset: (i64, i64),
+    /// Size (width, height) of a one cell or absolute anchor, in EMUs
+    /// (`<xdr:ext cx cy>`)
+    pub extent: Option<(i64, i64)>,
+    /// Position (x, y) of the top left corner of an absolute anchor, in EMUs
+    /// (`<xdr:pos x y>`)
+    pub position: Option<(i64, i64)>,
+    /// Name of the picture, e.g. `Picture 1` (`name` of `cNvPr`)
+    pub name: String,
+    /// Alternative text describing the picture (`descr` of `cNvPr`)
+    pub alt_text: Option<String>,
+    /// Path of the image part in the archive
+    pub path: String,
+    /// Bytes of the image part, as is
+    pub data: Vec<u8>,
+}
+
+/// How a drawing object is anchored to the worksheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnchorKind {
+    /// Spans from one cell to another (`<xdr:twoCellAnchor>`)
+    #[default]
+    TwoCell,
+    /// Pinned to its top left cell, with a fixed size (`<xdr:oneCellAnchor>`)
+    OneCell,
+    /// Placed at absolute coordinates, in EMUs (`<xdr:absoluteAnchor>`)
+    Absolute,
+}
@@ -0,0 +1,328 @@
+//! Decryption of password protected workbooks (ECMA-376 agile encryption)
+//!
+//! An encrypted workbook is a CFB container holding the encryption
+//! parameters in its `EncryptionInfo` stream and the encrypted zip package in
+//! its `EncryptedPackage` stream. See [MS-OFFCRYPTO] 2.3.4.10 to 2.3.4.15.
+//!
+//! [MS-OFFCRYPTO]: https://learn.microsoft.com/en-us/openspecs/office_file_formats/ms-offcrypto
+
+use aes::cipher::{
+    block_padding::NoPadding, BlockCipher, BlockDecryptMut, BlockSizeUser, KeyInit, KeyIvInit,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use quick_xml::{
+    encoding::Decoder,
+    events::{BytesStart, Event},
+    Reader as XmlReader,
+};
+use sha2::{Digest, Sha512};
+
+use super::XlsxError;
+use crate::cfb::{Cfb, CfbError};
+
+/// Block key of the key deriving the verifier hash input
+const VERIFIER_HASH_INPUT_BLOCK_KEY: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+/// Block key of the key deriving the verifier hash value
+const VERIFIER_HASH_VALUE_BLOCK_KEY: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+/// Block key of the key deriving the secret key of the package
+const ENCRYPTED_KEY_VALUE_BLOCK_KEY: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+/// Size of the independently encrypted segments of the package
+const SEGMENT_SIZE: usize = 4096;
+/// Maximum number of hash iterations deriving the password key
+///
+/// Excel uses 100,000. Larger counts would make opening a crafted file hang.
+const MAX_SPIN_COUNT: u32 = 10_000_000;
+
+/// Encryption parameters of a `<keyData>` or password `<p:encryptedKey>`
+#[derive(Debug, Default)]
+struct KeyParams {
+    salt: Vec<u8>,
+    block_size: usize,
+    key_bits: usize,
+    hash_size: usize,
+    spin_count: u32,
+    encrypted_verifier_hash_input: Vec<u8>,
+    encrypted_verifier_hash_value: Vec<u8>,
+    encrypted_key_value: Vec<u8>,
+}
+
+/// Decrypt the zip package of a workbook encrypted with `password`
+///
+/// Workbooks which are not a CFB container are returned as is. Fails with
+/// [`XlsxError::Password`] if the password is wrong. The integrity of the
+/// package (`<dataIntegrity>`) is not checked.
+pub(crate) fn decrypt_workbook(data: Vec<u8>, password: &str) -> Result<Vec<u8>, XlsxError> {
+    let mut reader = &data[..];
+    let mut cfb = match Cfb::new(&mut reader, data.len()) {
+        Ok(cfb) => cfb,
+        Err(CfbError::Ole) => return Ok(data),
+        Err(e) => return Err(e.into()),
+    };
+    let info = cfb.get_stream("EncryptionInfo", &mut reader)?;
+    let mut package = cfb.get_stream("EncryptedPackage", &mut reader)?;
+
+    // only the agile encryption, version 4.4, is supported
+    match info.get(..4) {
+        Some([4, 0, 4, 0]) => (),
+        Some(v) => {
+            return Err(XlsxError::Unrecognized {
+                typ: "encryption version",
+                val: format!("{}.{}", v[0], v[2]),
+            })
+        }
+        None => return Err(XlsxError::Unexpected("truncated EncryptionInfo stream")),
+    }
+    let xml = info
+        .get(8..)
+        .ok_or(XlsxError::Unexpected("truncated EncryptionInfo stream"))?;
+    let (key_data, password_key) = read_encryption_info(xml)?;
+
+    // derive the secret key of the package from the password
+    let mut hash = Sha512::new()
+        .chain_update(&password_key.salt)
+        .chain_update(
+            password
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .collect::<Vec<_>>(),
+        )
+        .finalize();
+    for i in 0..password_key.spin_count {
+        hash = Sha512::new()
+            .chain_update(i.to_le_bytes())
+            .chain_update(hash)
+            .finalize();
+    }
+    let key_len = password_key.key_bits / 8;
+    let decrypt_with = |block_key: &[u8], encrypted: &[u8]| {
+        let key = resize(
+            Sha512::new().chain_update(hash).chain_update(block_key),
+            key_len,
+        );
+        let mut data = encrypted.to_vec();
+        decrypt_aes_cbc(&key, &password_key.salt, &mut data)?;
+        Ok::<_, XlsxError>(data)
+    };
+    let verifier_hash_input = decrypt_with(
+        &VERIFIER_HASH_INPUT_BLOCK_KEY,
+        &password_key.encrypted_verifier_hash_input,
+    )?;
+    let verifier_hash_value = decrypt_with(
+        &VERIFIER_HASH_VALUE_BLOCK_KEY,
+        &password_key.encrypted_verifier_hash_value,
+    )?;
+    let verifier_hash_input =
+        &verifier_hash_input[..password_key.salt.len().min(verifier_hash_input.len())];
+    let verifier_hash = Sha512::digest(verifier_hash_input);
+    if verifier_hash_value.get(..password_key.hash_size)
+        != verifier_hash.get(..password_key.hash_size)
+    {
+        return Err(XlsxError::Password);
+    }
+    let mut secret_key = decrypt_with(
+        &ENCRYPTED_KEY_VALUE_BLOCK_KEY,
+        &password_key.encrypted_key_value,
+    )?;
+    secret_key.truncate(key_len);
+
+    // the package is prefixed by its size and encrypted by segments
+    let size = match package.get(..8) {
+        Some(size) => u64::from_le_bytes(size.try_into().unwrap()) as usize,
+        None => return Err(XlsxError::Unexpected("truncated EncryptedPackage stream")),
+    };
+    package.drain(..8);
+    for (i, segment) in package.chunks_mut(SEGMENT_SIZE).enumerate() {
+        let iv = Sha512::new()
+            .chain_update(&key_data.salt)
+            .chain_update((i as u32).to_le_bytes());
+        decrypt_aes_cbc(&secret_key, &resize(iv, key_data.block_size), segment)?;
+    }
+    package.truncate(size);
+    Ok(package)
+}
+
+/// Read the parameters of the package key and of the password key
+fn read_encryption_info(xml: &[u8]) -> Result<(KeyParams, KeyParams), XlsxError> {
+    let mut reader = XmlReader::from_reader(xml);
+    let mut key_data = None;
+    let mut password_key = None;
+    let mut in_password_encryptor = false;
+    let mut buf = Vec::with_capacity(1024);
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) | Event::Empty(ref e) => match e.local_name().as_ref() {
+                b"keyData" => key_data = Some(read_key_params(e, reader.decoder())?),
+                b"keyEncryptor" => {
+                    in_password_encryptor = e
+                        .try_get_attribute("uri")?
+                        .is_some_and(|uri| uri.value.ends_with(b"keyEncryptor/password"));
+                }
+                b"encryptedKey" if in_password_encryptor => {
+                    password_key = Some(read_key_params(e, reader.decoder())?);
+                }
+                _ => (),
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    match (key_data, password_key) {
+        (Some(key_data), Some(password_key)) => Ok((key_data, password_key)),
+        _ => Err(XlsxError::Unexpected(
+            "EncryptionInfo has no password key encryptor",
+        )),
+    }
+}
+
+/// Read the parameters of a `<keyData>` or `<p:encryptedKey>` element
+fn read_key_params(e: &BytesStart, decoder: Decoder) -> Result<KeyParams, XlsxError> {
+    let mut params = KeyParams::default();
+    for a in e.attributes() {
+        let a = a.map_err(XlsxError::XmlAttr)?;
+        let value = a.decode_and_unescape_value(decoder)?;
+        let base64 = || {
+            STANDARD
+                .decode(value.as_bytes())
+                .map_err(|_| XlsxError::Unrecognized {
+                    typ: "base64 value",
+                    val: value.to_string(),
+                })
+        };
+        match a.key.as_ref() {
+            b"saltValue" => params.salt = base64()?,
+            b"blockSize" => params.block_size = value.parse()?,
+            b"keyBits" => params.key_bits = value.parse()?,
+            b"hashSize" => params.hash_size = value.parse()?,
+            b"spinCount" => params.spin_count = value.parse()?,
+            b"encryptedVerifierHashInput" => params.encrypted_verifier_hash_input = base64()?,
+            b"encryptedVerifierHashValue" => params.encrypted_verifier_hash_value = base64()?,
+            b"encryptedKeyValue" => params.encrypted_key_value = base64()?,
+            key @ (b"cipherAlgorithm" | b"cipherChaining" | b"hashAlgorithm") => {
+                let expected = match key {
+                    b"cipherAlgorithm" => "AES",
+                    b"cipherChaining" => "ChainingModeCBC",
+                    _ => "SHA512",
+                };
+                if value != expected {
+                    return Err(XlsxError::Unrecognized {
+                        typ: "encryption algorithm",
+                        val: value.to_string(),
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+    if params.spin_count > MAX_SPIN_COUNT {
+        return Err(XlsxError::Unrecognized {
+            typ: "spin count",
+            val: params.spin_count.to_string(),
+        });
+    }
+    // the verifier is a SHA-512 hash, longer hashes can't be checked
+    if params.hash_size > 64 {
+        return Err(XlsxError::Unrecognized {
+            typ: "hash size",
+            val: params.hash_size.to_string(),
+        });
+    }
+    if params.block_size == 0 || params.key_bits == 0 || params.hash_size == 0 {
+        return Err(XlsxError::Unexpected(
+            "encryption block, key and hash sizes must not be zero",
+        ));
+    }
+    Ok(params)
+}
+
+/// Finish a hash and truncate it, or pad it with `0x36`, to `len` bytes
+fn resize(hash: Sha512, len: usize) -> Vec<u8> {
+    let mut hash = hash.finalize().to_vec();
+    hash.resize(len, 0x36);
+    hash
+}
+
+/// Decrypt `data` in place with AES in CBC mode, the key size giving the
+/// AES variant
+fn decrypt_aes_cbc(key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), XlsxError> {
+    match key.len() {
+        16 => decrypt_cbc::<aes::Aes128>(key, iv, data),
+        24 => decrypt_cbc::<aes::Aes192>(key, iv, data),
+        32 => decrypt_cbc::<aes::Aes256>(key, iv, data),
+        n => Err(XlsxError::Unrecognized {
+            typ: "key size",
+            val: (n * 8).to_string(),
+        }),
+    }
+}
+
+fn decrypt_cbc<C>(key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), XlsxError>
+where
+    C: BlockCipher + BlockDecryptMut + BlockSizeUser + KeyInit,
+{
+    let iv = &iv[..C::block_size().min(iv.len())];
+    cbc::Decryptor::<C>::new_from_slices(key, iv)
+        .map_err(|_| XlsxError::Unexpected("invalid encryption key or initialization vector"))?
+        .decrypt_padded_mut::<NoPadding>(data)
+        .map_err(|_| XlsxError::Unexpected("encrypted data is not a multiple of the block size"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encryption_info(password_key: &str) -> String {
+        format!(
+            r#"<encryption xmlns="http://schemas.microsoft.com/office/2006/encryption" xmlns:p="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+                <keyData saltValue="AAAA" blockSize="16" keyBits="256" hashSize="64"/>
+                <keyEncryptors><keyEncryptor uri="http://schemas.microsoft.com/office/2006/keyEncryptor/password">
+                    <p:encryptedKey saltValue="AAAA" {password_key}/>
+                </keyEncryptor></keyEncryptors>
+            </encryption>"#
+        )
+    }
+
+    #[test]
+    fn test_read_encryption_info() {
+        let xml =
+            encryption_info(r#"spinCount="100000" blockSize="16" keyBits="256" hashSize="64""#);
+        let (key_data, password_key) = read_encryption_info(xml.as_bytes()).unwrap();
+        assert_eq!(key_data.key_bits, 256);
+        assert_eq!(password_key.spin_count, 100_000);
+    }
+
+    #[test]
+    fn test_read_encryption_info_invalid_params() {
+        let xml =
+            encryption_info(r#"spinCount="4294967295" blockSize="16" keyBits="256" hashSize="64""#);
+        assert!(matches!(
+            read_encryption_info(xml.as_bytes()),
+            Err(XlsxError::Unrecognized {
+                typ: "spin count",
+                ..
+            })
+        ));
+        let xml =
+            encryption_info(r#"spinCount="100000" blockSize="16" keyBits="256" hashSize="65""#);
+        assert!(matches!(
+            read_encryption_info(xml.as_bytes()),
+            Err(XlsxError::Unrecognized {
+                typ: "hash size",
+                ..
+            })
+        ));
+        for sizes in [
+            r#"blockSize="0" keyBits="256" hashSize="64""#,
+            r#"blockSize="16" keyBits="0" hashSize="64""#,
+            r#"blockSize="16" keyBits="256" hashSize="0""#,
+            r#"spinCount="100000""#,
+        ] {
+            let xml = encryption_info(sizes);
+            assert!(matches!(
+                read_encryption_info(xml.as_bytes()),
+                Err(XlsxError::Unexpected(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{Read, Seek};
+
+use super::{XlsxCellReader, XlsxError};
+use crate::datatype::DataRef;
+use crate::{Cell, Data};
+
+/// An event of a worksheet read by [`SheetEvents`]
+#[derive(Debug, Clone)]
+pub enum SheetEvent {
+    /// A row with at least one value starts, with its zero indexed row number
+    RowStart(u32),
+    /// A non-empty cell of the current row
+    Cell(Cell<Data>),
+    /// The current row ends, with its zero indexed row number
+    RowEnd(u32),
+    /// All the cells of the worksheet were read
+    End,
+}
+
+/// An iterator over the events of a worksheet, see [`Xlsx::worksheet_events`]
+///
+/// [`Xlsx::worksheet_events`]: super::Xlsx::worksheet_events
+pub struct SheetEvents<'a, RS>
+where
+    RS: Read + Seek,
+{
+    reader: XlsxCellReader<'a, RS>,
+    // Row of the last cell returned, until its `RowEnd` is returned
+    row: Option<u32>,
+    // Cell read ahead while the end of the previous row is returned
+    pending: Option<Cell<Data>>,
+    // All the cells were read, the reader must not be called again
+    cells_read: bool,
+    done: bool,
+}
+
+impl<'a, RS> SheetEvents<'a, RS>
+where
+    RS: Read + Seek,
+{
+    pub(crate) fn new(reader: XlsxCellReader<'a, RS>) -> Self {
+        SheetEvents {
+            reader,
+            row: None,
+            pending: None,
+            cells_read: false,
+            done: false,
+        }
+    }
+
+    /// Next non-empty cell of the worksheet
+    fn next_value(&mut self) -> Result<Option<Cell<Data>>, XlsxError> {
+        if self.cells_read {
+            return Ok(None);
+        }
+        while let Some(cell) = self.reader.next_cell()? {
+            if !matches!(cell.val, DataRef::Empty) {
+                return Ok(Some(Cell::new(cell.pos, cell.val.into())));
+            }
+        }
+        self.cells_read = true;
+        Ok(None)
+    }
+}
+
+impl<'a, RS> Iterator for SheetEvents<'a, RS>
+where
+    RS: Read + Seek,
+{
+    type Item = Result<SheetEvent, XlsxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let cell = match self.pending.take() {
+            Some(cell) => cell,
+            None => match self.next_value() {
+                Ok(Some(cell)) => cell,
+                Ok(None) => {
+                    return Some(Ok(match self.row.take() {
+                        Some(row) => SheetEvent::RowEnd(row),
+                        None => {
+                            self.done = true;
+                            SheetEvent::End
+                        }
+                    }));
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            },
+        };
+        let row = cell.pos.0;
+        match self.row {
+            Some(current) if current == row => Some(Ok(SheetEvent::Cell(cell))),
+            Some(current) => {
+                self.row = None;
+                self.pending = Some(cell);
+                Some(Ok(SheetEvent::RowEnd(current)))
+            }
+            None => {
+                self.row = Some(row);
+                self.pending = Some(cell);
+                Some(Ok(SheetEvent::RowStart(row)))
+            }
+        }
+    }
+}
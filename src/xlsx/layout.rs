@@ -0,0 +1,128 @@
+use super::{ColumnWidths, RowDefinitions};
+use crate::{Dimensions, SheetVisible};
+
+/// Layout of a worksheet, as read by [`Xlsx::worksheet_layout`](crate::Xlsx::worksheet_layout)
+#[derive(Debug, Clone, Default)]
+pub struct SheetLayout {
+    /// Column definitions (`<cols>`) and sheet format defaults
+    /// (`<sheetFormatPr>`)
+    pub column_widths: ColumnWidths,
+    /// Row definitions (`<row>` attributes)
+    pub row_definitions: RowDefinitions,
+    /// Merged cells (`<mergeCells>`)
+    pub merged_cells: Vec<Dimensions>,
+    /// Split or frozen pane of the first sheet view (`<pane>`), if any
+    pub pane: Option<SheetPane>,
+}
+
+/// Split or frozen pane of a sheet view (`<pane>`)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SheetPane {
+    /// Horizontal position of the split (`xSplit`)
+    ///
+    /// Number of columns left of the split for frozen panes, else in 1/20th
+    /// of a point.
+    pub x_split: f64,
+    /// Vertical position of the split (`ySplit`)
+    ///
+    /// Number of rows above the split for frozen panes, else in 1/20th of a
+    /// point.
+    pub y_split: f64,
+    /// Top left visible cell of the bottom right pane (`topLeftCell`)
+    pub top_left_cell: Option<(u32, u32)>,
+    /// Whether the pane is split or frozen (`state`)
+    pub state: PaneState,
+}
+
+/// State of a sheet view pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaneState {
+    /// Panes are split but not frozen (`split`)
+    #[default]
+    Split,
+    /// Panes are frozen (`frozen`)
+    Frozen,
+    /// Panes are frozen, and split when unfrozen (`frozenSplit`)
+    FrozenSplit,
+}
+
+/// Display settings of the first view of a worksheet (`<sheetView>`), as
+/// read by [`Xlsx::worksheet_view`](crate::Xlsx::worksheet_view)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetView {
+    /// Gridlines are shown (`showGridLines`)
+    pub show_grid_lines: bool,
+    /// Row and column headings are shown (`showRowColHeaders`)
+    pub show_row_col_headers: bool,
+    /// Zoom, in percent (`zoomScale`)
+    pub zoom_scale: u32,
+    /// How the worksheet is displayed (`view`)
+    pub view_type: SheetViewType,
+    /// Split or frozen pane (`<pane>`), if any
+    pub pane: Option<SheetPane>,
+}
+
+impl Default for SheetView {
+    fn default() -> Self {
+        SheetView {
+            show_grid_lines: true,
+            show_row_col_headers: true,
+            zoom_scale: 100,
+            view_type: SheetViewType::Normal,
+            pane: None,
+        }
+    }
+}
+
+/// How a worksheet is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SheetViewType {
+    /// Normal view (`normal`)
+    #[default]
+    Normal,
+    /// Page layout view (`pageLayout`)
+    PageLayout,
+    /// Page break preview (`pageBreakPreview`)
+    PageBreakPreview,
+}
+
+/// A custom view of a worksheet (`<customSheetView>`), as read by
+/// [`Xlsx::worksheet_custom_views`](crate::Xlsx::worksheet_custom_views)
+///
+/// Custom views are named in the workbook, by their GUID. A view only flags
+/// that it hides rows or columns: the hidden rows and columns themselves are
+/// not stored per view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomSheetView {
+    /// Identifier of the view, e.g. `{8F2D...}` (`guid`)
+    pub guid: String,
+    /// Name of the view (`name` of the `<customWorkbookView>` of same GUID)
+    pub name: Option<String>,
+    /// Visibility of the worksheet in the view (`state`)
+    pub state: SheetVisible,
+    /// Some rows are hidden in the view (`hiddenRows`)
+    pub hidden_rows: bool,
+    /// Some columns are hidden in the view (`hiddenColumns`)
+    pub hidden_columns: bool,
+    /// An autofilter is applied in the view (`filter`)
+    pub filter: bool,
+    /// Only unique rows are shown by an advanced filter (`filterUnique`)
+    pub filter_unique: bool,
+    /// Range of the autofilter of the view (`ref` of `<autoFilter>`)
+    pub auto_filter: Option<Dimensions>,
+}
+
+impl Default for CustomSheetView {
+    fn default() -> Self {
+        CustomSheetView {
+            guid: String::new(),
+            name: None,
+            state: SheetVisible::Visible,
+            hidden_rows: false,
+            hidden_columns: false,
+            filter: false,
+            filter_unique: false,
+            auto_filter: None,
+        }
+    }
+}
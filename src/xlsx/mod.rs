@@ -1,9 +1,25 @@
 mod cells_reader;
+mod charts;
 pub mod column_width;
+mod defined_names;
+mod diff;
+mod drawings;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod events;
+mod layout;
+mod named_styles;
+mod profile;
+mod protection;
+mod relationships;
+mod sparklines;
+mod table_styles;
+mod threaded_comments;
 
 use std::borrow::Cow;
-use std::collections::BTreeMap;
-use std::io::{BufReader, Read, Seek};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Read, Seek, Write};
+use std::ops::ControlFlow;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -20,23 +36,40 @@ use zip::read::{ZipArchive, ZipFile};
 use zip::result::ZipError;
 
 use crate::conditional_formatting::{ConditionalFormatting, DifferentialFormat};
-use crate::datatype::DataRef;
+use crate::datatype::{DataRef, DataType};
 use crate::formats::{
-    builtin_format_by_id, detect_custom_number_format_with_interner, Alignment, Border, BorderSide,
-    CellFormat, CellStyle, Color, Fill, Font, FormatStringInterner,
+    builtin_format_by_id, detect_custom_number_format_with_interner, Alignment, ApplyFlags, Border,
+    BorderSide, CellFormat, CellStyle, Color, Fill, Font, FormatStringInterner,
+    BUILTIN_INDEXED_COLORS,
 };
+use crate::number_format::{builtin_format_code, format_value};
 use crate::theme::Theme;
 use crate::vba::VbaProject;
 use crate::{
-    Cell, CellErrorType, Data, DataWithFormatting, Dimensions, HeaderRow, Metadata, Range, Reader,
-    ReaderRef, Sheet, SheetType, SheetVisible, Table,
+    Cell, CellErrorType, CellType, CsvOptions, Data, DataWithFormatting, Dimensions, HeaderRow,
+    Metadata, Range, Reader, ReaderRef, Sheet, SheetType, SheetVisible, Table,
 };
-pub use cells_reader::XlsxCellReader;
+pub use cells_reader::{CellResultType, ErrorPolicy, ReaderBuffers, XlsxCellReader};
+pub use charts::{ChartInfo, ChartType, SeriesRef};
 pub use column_width::{
     ColumnDefinition, ColumnWidths, RowDefinition, RowDefinitions, SheetFormatProperties,
 };
-
-pub(crate) type XlReader<'a, RS> = XmlReader<BufReader<ZipFile<'a, RS>>>;
+pub use defined_names::DefinedName;
+pub use diff::WorkbookDiff;
+pub use drawings::{AnchorKind, WorksheetPicture};
+#[cfg(feature = "encryption")]
+pub(crate) use encryption::decrypt_workbook;
+pub use events::{SheetEvent, SheetEvents};
+pub use layout::{CustomSheetView, PaneState, SheetLayout, SheetPane, SheetView, SheetViewType};
+pub use named_styles::NamedStyle;
+pub use profile::ColumnProfile;
+pub use protection::{ProtectedRange, WorkbookProtection};
+pub use relationships::Relationship;
+pub use sparklines::{Sparkline, SparklineGroup, SparklineType};
+pub use table_styles::{TableStyle, TableStyleElement, TableStyleInfo};
+pub use threaded_comments::ThreadedComment;
+
+pub(crate) type XlReader<'a, RS> = XmlReader<PartReader<LimitedReader<ZipFile<'a, RS>>>>;
 
 /// Maximum number of rows allowed in an xlsx file
 pub const MAX_ROWS: u32 = 1_048_576;
@@ -53,6 +86,8 @@ pub enum XlsxError {
     Zip(zip::result::ZipError),
     /// Vba error
     Vba(crate::vba::VbaError),
+    /// Cfb error
+    Cfb(crate::cfb::CfbError),
     /// Xml error
     Xml(quick_xml::Error),
     /// Xml attribute error
@@ -106,12 +141,18 @@ pub enum XlsxError {
     Encoding(quick_xml::encoding::EncodingError),
     /// XML attribute error
     XmlAttribute(quick_xml::events::attributes::AttrError),
+    /// A limit set on the reader was exceeded
+    LimitExceeded {
+        /// Limit name: `max_rows`, `max_cols` or `max_decompressed_bytes`
+        limit: &'static str,
+        /// Limit value
+        max: u64,
+    },
 }
 
-from_err!(std::io::Error, XlsxError, Io);
 from_err!(zip::result::ZipError, XlsxError, Zip);
 from_err!(crate::vba::VbaError, XlsxError, Vba);
-from_err!(quick_xml::Error, XlsxError, Xml);
+from_err!(crate::cfb::CfbError, XlsxError, Cfb);
 from_err!(std::string::ParseError, XlsxError, Parse);
 from_err!(std::num::ParseFloatError, XlsxError, ParseFloat);
 from_err!(std::num::ParseIntError, XlsxError, ParseInt);
@@ -122,6 +163,34 @@ from_err!(
     XmlAttribute
 );
 
+impl From<std::io::Error> for XlsxError {
+    fn from(e: std::io::Error) -> XlsxError {
+        // a `LimitedReader` failure of a part read as bytes, e.g. an image
+        if let Some(LimitExceeded(max)) = e.get_ref().and_then(|e| e.downcast_ref()) {
+            return XlsxError::LimitExceeded {
+                limit: "max_decompressed_bytes",
+                max: *max,
+            };
+        }
+        XlsxError::Io(e)
+    }
+}
+
+impl From<quick_xml::Error> for XlsxError {
+    fn from(e: quick_xml::Error) -> XlsxError {
+        // a `LimitedReader` failure surfaces as an I/O error of the xml reader
+        if let quick_xml::Error::Io(ref io) = e {
+            if let Some(LimitExceeded(max)) = io.get_ref().and_then(|e| e.downcast_ref()) {
+                return XlsxError::LimitExceeded {
+                    limit: "max_decompressed_bytes",
+                    max: *max,
+                };
+            }
+        }
+        XlsxError::Xml(e)
+    }
+}
+
 impl std::fmt::Display for XlsxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -130,6 +199,7 @@ impl std::fmt::Display for XlsxError {
             XlsxError::Xml(e) => write!(f, "Xml error: {e}"),
             XlsxError::XmlAttr(e) => write!(f, "Xml attribute error: {e}"),
             XlsxError::Vba(e) => write!(f, "Vba error: {e}"),
+            XlsxError::Cfb(e) => write!(f, "Cfb error: {e}"),
             XlsxError::Parse(e) => write!(f, "Parse string error: {e}"),
             XlsxError::ParseInt(e) => write!(f, "Parse integer error: {e}"),
             XlsxError::ParseFloat(e) => write!(f, "Parse float error: {e}"),
@@ -164,6 +234,9 @@ impl std::fmt::Display for XlsxError {
             XlsxError::NotAWorksheet(typ) => write!(f, "Expecting a worksheet, got {typ}"),
             XlsxError::Encoding(e) => write!(f, "XML encoding error: {e}"),
             XlsxError::XmlAttribute(e) => write!(f, "XML attribute error: {e}"),
+            XlsxError::LimitExceeded { limit, max } => {
+                write!(f, "Limit exceeded: {limit} is {max}")
+            }
         }
     }
 }
@@ -175,6 +248,7 @@ impl std::error::Error for XlsxError {
             XlsxError::Zip(e) => Some(e),
             XlsxError::Xml(e) => Some(e),
             XlsxError::Vba(e) => Some(e),
+            XlsxError::Cfb(e) => Some(e),
             XlsxError::Parse(e) => Some(e),
             XlsxError::ParseInt(e) => Some(e),
             XlsxError::ParseFloat(e) => Some(e),
@@ -196,12 +270,29 @@ impl FromStr for CellErrorType {
             "#NUM!" => Ok(CellErrorType::Num),
             "#REF!" => Ok(CellErrorType::Ref),
             "#VALUE!" => Ok(CellErrorType::Value),
+            "#SPILL!" => Ok(CellErrorType::Spill),
+            "#CALC!" => Ok(CellErrorType::Calc),
+            // Numeric error codes, as in BIFF, written by some producers
+            "0" => Ok(CellErrorType::Null),
+            "7" => Ok(CellErrorType::Div0),
+            "15" => Ok(CellErrorType::Value),
+            "23" => Ok(CellErrorType::Ref),
+            "29" => Ok(CellErrorType::Name),
+            "36" => Ok(CellErrorType::Num),
+            "42" => Ok(CellErrorType::NA),
+            "43" => Ok(CellErrorType::GettingData),
             _ => Err(XlsxError::CellError(s.into())),
         }
     }
 }
 
-type Tables = Option<Vec<(String, String, Vec<String>, Dimensions)>>;
+type CellImages = Vec<((u32, u32), Vec<u8>)>;
+type RichValues = HashMap<(u32, u32), HashMap<String, Data>>;
+type ValueMetadataCells = Vec<((u32, u32), usize)>;
+/// Fields of a rich value: key name, key type (`t`) and raw value
+type RichValue = Vec<(String, String, String)>;
+/// Non-empty cells of a worksheet: position, value and displayed text
+type DisplayedCells = Vec<((u32, u32), Data, String)>;
 
 /// A struct representing xml zipped excel file
 /// Xlsx, Xlsm, Xlam
@@ -211,16 +302,34 @@ pub struct Xlsx<RS> {
     strings: Vec<String>,
     /// Sheets paths
     sheets: Vec<(String, String)>,
-    /// Tables: Name, Sheet, Columns, Data dimensions
-    tables: Tables,
+    /// Tables, once loaded
+    tables: Option<Vec<TableMetadata>>,
     /// Cell formats (backward compatible)
     formats: Vec<CellFormat>,
     /// Cell formats (comprehensive formatting information)
     styles: Vec<CellStyle>,
+    /// Attributes of each cell format besides its style, by index as in `styles`
+    cell_xfs: Vec<CellXf>,
+    /// Named cell styles (`<cellStyles>`)
+    named_styles: Vec<NamedStyle>,
+    /// Custom table styles (`<tableStyles>`)
+    table_styles: Vec<TableStyle>,
+    /// Default table style (`defaultTableStyle` of `<tableStyles>`)
+    default_table_style: Option<String>,
+    /// Default pivot table style (`defaultPivotStyle` of `<tableStyles>`)
+    default_pivot_style: Option<String>,
+    /// Default cell font (first font of the `<fonts>` table)
+    default_font: Font,
     /// Format string interner for reuse across sheets
     format_interner: FormatStringInterner,
     /// 1904 datetime system
     is_1904: bool,
+    /// Workbook protection (`<workbookProtection>`)
+    workbook_protection: Option<WorkbookProtection>,
+    /// Names of the custom views, by GUID (`<customWorkbookView>`)
+    custom_view_names: HashMap<String, String>,
+    /// Defined names with their attributes (`<definedName>`)
+    defined_names: Vec<DefinedName>,
     /// Metadata
     metadata: Metadata,
     /// Pictures
@@ -236,18 +345,58 @@ pub struct Xlsx<RS> {
     conditional_formats: BTreeMap<String, Vec<ConditionalFormatting>>,
     /// Theme information
     theme: Option<Theme>,
+    /// Custom indexed color palette (`<indexedColors>`), empty if not redefined
+    indexed_colors: Vec<Color>,
+    /// Most recently used colors (`<mruColors>`)
+    mru_colors: Vec<Color>,
+    /// Scratch buffers reused across worksheet reads
+    buffers: ReaderBuffers,
+}
+
+/// Attributes of a cell format (`<xf>` of `<cellXfs>`) not held by its
+/// [`CellStyle`]
+#[derive(Debug, Clone)]
+struct CellXf {
+    /// Number format code, builtin formats included
+    number_format_code: Option<Arc<str>>,
+    /// Named style format (`xfId`)
+    xf_id: u32,
+    /// Parts applied by the format (`apply*` attributes)
+    apply: ApplyFlags,
 }
 
-/// Xlsx reader options
-#[derive(Debug, Default)]
+/// Xlsx reader options, see [`Xlsx::new_with_options`]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-struct XlsxOptions {
+pub struct XlsxOptions {
+    /// Row to use as header
     pub header_row: HeaderRow,
+    /// Maximum number of rows of the worksheets, see [`Xlsx::with_max_rows`]
+    pub max_rows: Option<u32>,
+    /// Maximum number of columns of the worksheets, see [`Xlsx::with_max_cols`]
+    pub max_cols: Option<u32>,
+    /// Maximum decompressed size, in bytes, of each part of the workbook
+    ///
+    /// The limit is checked against the size declared in the archive and
+    /// enforced while the part is decompressed, so that a decompression bomb
+    /// fails with [`XlsxError::LimitExceeded`] instead of exhausting memory.
+    /// It applies to every part, including the shared strings, styles and
+    /// workbook parts read when opening the file, images and the VBA project.
+    pub max_decompressed_bytes: Option<u64>,
+    /// How error cells are read, see [`Xlsx::with_errors_as`]
+    pub error_policy: ErrorPolicy,
+    /// Read text formatted cells as strings, see
+    /// [`Xlsx::with_text_format_as_string`]
+    pub text_format_as_string: bool,
 }
 
 impl<RS: Read + Seek> Xlsx<RS> {
     fn read_shared_strings(&mut self) -> Result<(), XlsxError> {
-        let mut xml = match xml_reader(&mut self.zip, "xl/sharedStrings.xml") {
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            "xl/sharedStrings.xml",
+            self.options.max_decompressed_bytes,
+        ) {
             None => return Ok(()),
             Some(x) => x?,
         };
@@ -270,7 +419,11 @@ impl<RS: Read + Seek> Xlsx<RS> {
     }
 
     fn read_styles(&mut self) -> Result<(), XlsxError> {
-        let mut xml = match xml_reader(&mut self.zip, "xl/styles.xml") {
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            "xl/styles.xml",
+            self.options.max_decompressed_bytes,
+        ) {
             None => return Ok(()),
             Some(x) => x?,
         };
@@ -380,6 +533,9 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         match xml.read_event_into(&mut inner_buf) {
                             Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"xf" => {
                                 let mut cell_formatting = CellStyle::default();
+                                let mut format_code = builtin_format_code(0).map(Arc::from);
+                                let mut xf_id = 0;
+                                let mut apply = ApplyFlags::default();
 
                                 // Parse attributes to get references to fonts, fills, borders, number formats
                                 for attr in e.attributes() {
@@ -390,6 +546,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                         } => {
                                             let num_fmt_id =
                                                 atoi_simd::parse::<u32>(&v).unwrap_or(0);
+                                            cell_formatting.number_format_id = Some(num_fmt_id);
                                             if let Some(fmt) = number_formats.get(&num_fmt_id) {
                                                 let (detected_format, format_string) =
                                                     detect_custom_number_format_with_interner(
@@ -398,12 +555,15 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                                     );
                                                 cell_formatting.number_format = detected_format;
                                                 cell_formatting.format_string = format_string;
+                                                format_code = Some(Arc::from(fmt.as_str()));
                                             } else {
                                                 cell_formatting.number_format =
                                                     builtin_format_by_id(
                                                         &num_fmt_id.to_string().into_bytes(),
                                                     );
                                                 cell_formatting.format_string = None;
+                                                format_code =
+                                                    builtin_format_code(num_fmt_id).map(Arc::from);
                                             }
                                         }
                                         Attribute {
@@ -431,7 +591,22 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                             cell_formatting.border =
                                                 borders.get(border_id).cloned();
                                         }
-                                        _ => (),
+                                        Attribute {
+                                            key: QName(b"xfId"),
+                                            value: v,
+                                        } => xf_id = atoi_simd::parse::<u32>(&v).unwrap_or(0),
+                                        Attribute { key, value: v } => {
+                                            let flag = Some(matches!(&*v, b"1" | b"true"));
+                                            match key.as_ref() {
+                                                b"applyNumberFormat" => apply.number_format = flag,
+                                                b"applyFont" => apply.font = flag,
+                                                b"applyFill" => apply.fill = flag,
+                                                b"applyBorder" => apply.border = flag,
+                                                b"applyAlignment" => apply.alignment = flag,
+                                                b"applyProtection" => apply.protection = flag,
+                                                _ => (),
+                                            }
+                                        }
                                     }
                                 }
 
@@ -443,6 +618,11 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                 // For backward compatibility, also push to the old formats field
                                 self.formats.push(cell_formatting.number_format.clone());
                                 self.styles.push(cell_formatting);
+                                self.cell_xfs.push(CellXf {
+                                    number_format_code: format_code,
+                                    xf_id,
+                                    apply,
+                                });
                             }
                             Ok(Event::End(ref e)) if e.local_name().as_ref() == b"cellXfs" => break,
                             Ok(Event::Eof) => return Err(XlsxError::XmlEof("cellXfs")),
@@ -451,6 +631,113 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         }
                     }
                 }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"cellStyles" => {
+                    // Parse named styles
+                    loop {
+                        inner_buf.clear();
+                        match xml.read_event_into(&mut inner_buf) {
+                            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"cellStyle" => {
+                                let mut style = NamedStyle::default();
+                                for a in e.attributes() {
+                                    let a = a.map_err(XlsxError::XmlAttr)?;
+                                    match a.key {
+                                        QName(b"name") => {
+                                            style.name = a
+                                                .decode_and_unescape_value(xml.decoder())?
+                                                .into_owned()
+                                        }
+                                        QName(b"xfId") => {
+                                            style.xf_id =
+                                                atoi_simd::parse::<u32>(&a.value).unwrap_or(0)
+                                        }
+                                        QName(b"builtinId") => {
+                                            style.builtin_id =
+                                                atoi_simd::parse::<u32>(&a.value).ok()
+                                        }
+                                        _ => (),
+                                    }
+                                }
+                                self.named_styles.push(style);
+                            }
+                            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"cellStyles" => {
+                                break
+                            }
+                            Ok(Event::Eof) => return Err(XlsxError::XmlEof("cellStyles")),
+                            Err(e) => return Err(XlsxError::Xml(e)),
+                            _ => (),
+                        }
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"tableStyles" => {
+                    (self.default_table_style, self.default_pivot_style) =
+                        Self::parse_default_table_styles(&xml, e)?;
+                    // Parse custom table styles
+                    loop {
+                        inner_buf.clear();
+                        match xml.read_event_into(&mut inner_buf) {
+                            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"tableStyle" => {
+                                let mut style = TableStyle {
+                                    pivot: true,
+                                    table: true,
+                                    ..Default::default()
+                                };
+                                for a in e.attributes() {
+                                    let a = a.map_err(XlsxError::XmlAttr)?;
+                                    match a.key {
+                                        QName(b"name") => {
+                                            style.name = a
+                                                .decode_and_unescape_value(xml.decoder())?
+                                                .into_owned()
+                                        }
+                                        QName(b"pivot") => {
+                                            style.pivot = !matches!(&*a.value, b"0" | b"false")
+                                        }
+                                        QName(b"table") => {
+                                            style.table = !matches!(&*a.value, b"0" | b"false")
+                                        }
+                                        _ => (),
+                                    }
+                                }
+                                self.table_styles.push(style);
+                            }
+                            Ok(Event::Start(ref e))
+                                if e.local_name().as_ref() == b"tableStyleElement" =>
+                            {
+                                let mut element = TableStyleElement {
+                                    size: 1,
+                                    ..Default::default()
+                                };
+                                for a in e.attributes() {
+                                    let a = a.map_err(XlsxError::XmlAttr)?;
+                                    match a.key {
+                                        QName(b"type") => {
+                                            element.element_type = a
+                                                .decode_and_unescape_value(xml.decoder())?
+                                                .into_owned()
+                                        }
+                                        QName(b"dxfId") => {
+                                            element.dxf_id = atoi_simd::parse::<u32>(&a.value).ok()
+                                        }
+                                        QName(b"size") => {
+                                            element.size =
+                                                atoi_simd::parse::<u32>(&a.value).unwrap_or(1)
+                                        }
+                                        _ => (),
+                                    }
+                                }
+                                if let Some(style) = self.table_styles.last_mut() {
+                                    style.elements.push(element);
+                                }
+                            }
+                            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"tableStyles" => {
+                                break
+                            }
+                            Ok(Event::Eof) => return Err(XlsxError::XmlEof("tableStyles")),
+                            Err(e) => return Err(XlsxError::Xml(e)),
+                            _ => (),
+                        }
+                    }
+                }
                 Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"dxfs" => {
                     // Parse differential formats
                     loop {
@@ -472,6 +759,40 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         }
                     }
                 }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"colors" => {
+                    // Parse the custom indexed palette and the most recently used colors
+                    let mut in_mru_colors = false;
+                    loop {
+                        inner_buf.clear();
+                        match xml.read_event_into(&mut inner_buf) {
+                            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"mruColors" => {
+                                in_mru_colors = true;
+                            }
+                            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"mruColors" => {
+                                in_mru_colors = false;
+                            }
+                            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"rgbColor" => {
+                                // keep the palette positions aligned even for unreadable entries
+                                let color = Self::parse_color_from_attributes(e.attributes())?
+                                    .unwrap_or(Color::Auto);
+                                self.indexed_colors.push(color);
+                            }
+                            Ok(Event::Start(ref e))
+                                if in_mru_colors && e.local_name().as_ref() == b"color" =>
+                            {
+                                if let Some(color) =
+                                    Self::parse_color_from_attributes(e.attributes())?
+                                {
+                                    self.mru_colors.push(color);
+                                }
+                            }
+                            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"colors" => break,
+                            Ok(Event::Eof) => return Err(XlsxError::XmlEof("colors")),
+                            Err(e) => return Err(XlsxError::Xml(e)),
+                            _ => (),
+                        }
+                    }
+                }
                 Ok(Event::End(ref e)) if e.local_name().as_ref() == b"styleSheet" => break,
                 Ok(Event::Eof) => return Err(XlsxError::XmlEof("styleSheet")),
                 Err(e) => return Err(XlsxError::Xml(e)),
@@ -479,11 +800,19 @@ impl<RS: Read + Seek> Xlsx<RS> {
             }
         }
 
+        if let Some(font) = fonts.first() {
+            self.default_font = Font::clone(font);
+        }
+
         Ok(())
     }
 
     fn read_theme(&mut self) -> Result<(), XlsxError> {
-        let mut xml = match xml_reader(&mut self.zip, "xl/theme/theme1.xml") {
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            "xl/theme/theme1.xml",
+            self.options.max_decompressed_bytes,
+        ) {
             None => return Ok(()), // No theme file is OK, we'll use default
             Some(x) => x?,
         };
@@ -884,6 +1213,28 @@ impl<RS: Read + Seek> Xlsx<RS> {
         Ok(Some(BorderSide { style, color }))
     }
 
+    /// Parse the default table and pivot table styles of `<tableStyles>`
+    fn parse_default_table_styles(
+        xml: &XlReader<'_, RS>,
+        element: &BytesStart<'_>,
+    ) -> Result<(Option<String>, Option<String>), XlsxError> {
+        let mut table_style = None;
+        let mut pivot_style = None;
+        for a in element.attributes() {
+            let a = a.map_err(XlsxError::XmlAttr)?;
+            match a.key {
+                QName(b"defaultTableStyle") => {
+                    table_style = Some(a.decode_and_unescape_value(xml.decoder())?.into_owned())
+                }
+                QName(b"defaultPivotStyle") => {
+                    pivot_style = Some(a.decode_and_unescape_value(xml.decoder())?.into_owned())
+                }
+                _ => (),
+            }
+        }
+        Ok((table_style, pivot_style))
+    }
+
     /// Parse alignment information from cellXfs
     fn parse_alignment_from_xf(
         xml: &mut XlReader<'_, RS>,
@@ -1096,7 +1447,11 @@ impl<RS: Read + Seek> Xlsx<RS> {
         // Check if we've already loaded this sheet's conditional formatting
         if !self.conditional_formats.contains_key(name) {
             // Load the conditional formatting
-            let formats = Self::parse_worksheet_conditional_formatting(&sheet_path, &mut self.zip)?;
+            let formats = Self::parse_worksheet_conditional_formatting(
+                &sheet_path,
+                &mut self.zip,
+                self.options.max_decompressed_bytes,
+            )?;
             self.conditional_formats.insert(name.to_string(), formats);
         }
 
@@ -1107,30 +1462,56 @@ impl<RS: Read + Seek> Xlsx<RS> {
             .unwrap_or(&[]))
     }
 
-    /// Get differential formats
+    /// Get the differential formats (`<dxfs>`) of the workbook
+    ///
+    /// A conditional formatting rule `dxf_id` is an index into these formats,
+    /// which hold the font, fill and border overrides applied to the cells
+    /// matching the rule.
     pub fn dxf_formats(&self) -> &[DifferentialFormat] {
         &self.dxf_formats
     }
 
+    /// Get the default cell font of the workbook
+    ///
+    /// This is the first font of the `<fonts>` table, used by the `Normal`
+    /// style. It is empty if the workbook has no styles.
+    pub fn default_font(&self) -> &Font {
+        &self.default_font
+    }
+
     /// Parse conditional formatting from a worksheet
     fn parse_worksheet_conditional_formatting(
         sheet_path: &str,
         zip: &mut ZipArchive<RS>,
+        max_bytes: Option<u64>,
     ) -> Result<Vec<ConditionalFormatting>, XlsxError> {
-        use crate::conditional_formatting::ConditionalFormatting;
+        use crate::conditional_formatting::{
+            ConditionalFormatType, ConditionalFormatting, DataBar,
+        };
 
-        let mut xml = match xml_reader(zip, sheet_path) {
+        let mut xml = match xml_reader(zip, sheet_path, max_bytes) {
             None => return Ok(Vec::new()),
             Some(x) => x?,
         };
 
         let mut conditional_formats = Vec::new();
         let mut buf = Vec::with_capacity(1024);
+        // Extension ids of the rules, by format and rule index
+        let mut rule_ext_ids = Vec::new();
+        // Data bars of the 2010+ extensions (`<x14:conditionalFormatting>`)
+        let mut ext_data_bars = HashMap::new();
+        let mut in_ext_lst = false;
 
         // Skip to conditionalFormatting elements
         loop {
             buf.clear();
             match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"extLst" => {
+                    in_ext_lst = true;
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"extLst" => {
+                    in_ext_lst = false;
+                }
                 Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"conditionalFormatting" => {
                     let mut ranges = Vec::new();
                     let mut pivot = false;
@@ -1143,10 +1524,12 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                 value: v,
                             } => {
                                 let sqref = xml.decoder().decode(&v)?;
-                                // Split by space and parse each range
-                                for range_str in sqref.split_whitespace() {
-                                    if let Ok(dims) = get_dimension(range_str.as_bytes()) {
-                                        ranges.push(dims);
+                                for area in sqref.split_whitespace() {
+                                    match parse_sqref_area(area) {
+                                        Ok(dims) => ranges.push(dims),
+                                        Err(e) => {
+                                            warn!("skipping conditional format range '{area}': {e}")
+                                        }
                                     }
                                 }
                             }
@@ -1169,8 +1552,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         match xml.read_event_into(&mut inner_buf) {
                             Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"cfRule" => {
                                 let mut rule_buf = Vec::new();
-                                let rule = Self::parse_cf_rule(&mut xml, e, &mut rule_buf, pivot)?;
-                                rules.push(rule);
+                                rules.push(Self::parse_cf_rule(&mut xml, e, &mut rule_buf, pivot)?);
                             }
                             Ok(Event::End(ref e))
                                 if e.local_name().as_ref() == b"conditionalFormatting" =>
@@ -1185,10 +1567,23 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         }
                     }
 
-                    if !rules.is_empty() && !ranges.is_empty() {
+                    if in_ext_lst {
+                        for (rule, id) in rules {
+                            if let (ConditionalFormatType::DataBar(data_bar), Some(id)) =
+                                (rule.rule_type, id)
+                            {
+                                ext_data_bars.insert(id, data_bar);
+                            }
+                        }
+                    } else if !rules.is_empty() && !ranges.is_empty() {
+                        for (index, (_, id)) in rules.iter().enumerate() {
+                            if let Some(id) = id {
+                                rule_ext_ids.push((conditional_formats.len(), index, id.clone()));
+                            }
+                        }
                         conditional_formats.push(ConditionalFormatting {
                             ranges,
-                            rules,
+                            rules: rules.into_iter().map(|(rule, _)| rule).collect(),
                             scope: None,
                             table: None,
                         });
@@ -1201,16 +1596,45 @@ impl<RS: Read + Seek> Xlsx<RS> {
             }
         }
 
+        // Complete the data bars with the settings of their extension, keeping
+        // the thresholds, color and showValue of the rule
+        for (format, index, id) in rule_ext_ids {
+            let Some(ext) = ext_data_bars.remove(&id) else {
+                continue;
+            };
+            if let ConditionalFormatType::DataBar(ref mut data_bar) =
+                conditional_formats[format].rules[index].rule_type
+            {
+                *data_bar = DataBar {
+                    min_cfvo: data_bar.min_cfvo.clone(),
+                    max_cfvo: data_bar.max_cfvo.clone(),
+                    color: data_bar.color.clone(),
+                    show_value: data_bar.show_value,
+                    ..ext
+                };
+            }
+        }
+
         Ok(conditional_formats)
     }
 
     /// Parse a single cfRule element
+    ///
+    /// Also returns the id linking the rule to its 2010+ extension: the `id`
+    /// attribute of an `<x14:cfRule>`, or the `<x14:id>` of the extension
+    /// list of a `<cfRule>`.
     fn parse_cf_rule(
         xml: &mut XlReader<'_, RS>,
         rule_start: &BytesStart<'_>,
         buf: &mut Vec<u8>,
         pivot: bool,
-    ) -> Result<crate::conditional_formatting::ConditionalFormatRule, XlsxError> {
+    ) -> Result<
+        (
+            crate::conditional_formatting::ConditionalFormatRule,
+            Option<String>,
+        ),
+        XlsxError,
+    > {
         use crate::conditional_formatting::{
             CfvoType, ColorScale, ComparisonOperator, ConditionalFormatRule, ConditionalFormatType,
             ConditionalFormatValue, DataBar, IconSet, IconSetType, TimePeriod,
@@ -1230,6 +1654,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
         let mut above_average = true;
         let mut equal_average = false;
         let mut std_dev = None;
+        let mut ext_id = None;
 
         // Parse attributes
         for attr in rule_start.attributes() {
@@ -1293,6 +1718,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                             max_length: 90,
                             direction: None,
                             bar_only: false,
+                            border: false,
                             border_color: None,
                             negative_border_color: None,
                             gradient: true,
@@ -1304,12 +1730,12 @@ impl<RS: Read + Seek> Xlsx<RS> {
                             colors: Vec::new(),
                         }),
                         "iconSet" => ConditionalFormatType::IconSet(IconSet {
-                            icon_set: IconSetType::Arrows3,
+                            icon_set: IconSetType::TrafficLights3,
                             cfvos: Vec::new(),
                             show_value: true,
                             reverse: false,
                             custom_icons: Vec::new(),
-                            percent: false,
+                            percent: true,
                         }),
                         _ => ConditionalFormatType::Expression,
                     };
@@ -1330,6 +1756,12 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         priority = p;
                     }
                 }
+                Attribute {
+                    key: QName(b"id"),
+                    value: v,
+                } => {
+                    ext_id = Some(xml.decoder().decode(&v)?.into_owned());
+                }
                 Attribute {
                     key: QName(b"stopIfTrue"),
                     value: v,
@@ -1490,7 +1922,8 @@ impl<RS: Read + Seek> Xlsx<RS> {
                     }
                     b"dataBar" => {
                         if let ConditionalFormatType::DataBar(ref mut data_bar) = rule_type {
-                            Self::parse_data_bar(xml, buf, data_bar)?;
+                            let mut bar_buf = Vec::new();
+                            Self::parse_data_bar(xml, e, &mut bar_buf, data_bar)?;
                         }
                     }
                     b"colorScale" => {
@@ -1500,14 +1933,26 @@ impl<RS: Read + Seek> Xlsx<RS> {
                     }
                     b"iconSet" => {
                         if let ConditionalFormatType::IconSet(ref mut icon_set) = rule_type {
-                            Self::parse_icon_set(xml, buf, icon_set)?;
+                            let mut icon_buf = Vec::new();
+                            Self::parse_icon_set(xml, e, &mut icon_buf, icon_set)?;
                         }
                     }
-                    b"extLst" => {
-                        // Skip extensions for now
-                        let mut temp_buf = Vec::new();
-                        xml.read_to_end_into(e.name(), &mut temp_buf)?;
+                    b"id" => {
+                        // `<x14:id>` of the extension list
+                        let mut id = String::new();
+                        let mut id_buf = Vec::new();
+                        loop {
+                            id_buf.clear();
+                            match xml.read_event_into(&mut id_buf)? {
+                                Event::Text(t) => id.push_str(&t.unescape()?),
+                                Event::End(ref end) if end.local_name().as_ref() == b"id" => break,
+                                Event::Eof => return Err(XlsxError::XmlEof("id")),
+                                _ => (),
+                            }
+                        }
+                        ext_id = Some(id);
                     }
+                    b"extLst" | b"ext" => (),
                     _ => {
                         let mut temp_buf = Vec::new();
                         xml.read_to_end_into(e.name(), &mut temp_buf)?;
@@ -1520,7 +1965,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
             }
         }
 
-        Ok(ConditionalFormatRule {
+        let rule = ConditionalFormatRule {
             rule_type,
             priority,
             stop_if_true,
@@ -1535,53 +1980,63 @@ impl<RS: Read + Seek> Xlsx<RS> {
             above_average: if above_average { Some(true) } else { None },
             equal_average: if equal_average { Some(true) } else { None },
             std_dev: std_dev.map(|d| d as i32),
-        })
+        };
+        Ok((rule, ext_id))
     }
 
     /// Parse data bar element
+    ///
+    /// This is either a `<dataBar>` of a rule or the `<x14:dataBar>` of its
+    /// 2010+ extension, which holds the border, axis and negative bar settings.
     fn parse_data_bar(
         xml: &mut XlReader<'_, RS>,
+        data_bar_start: &BytesStart<'_>,
         buf: &mut Vec<u8>,
         data_bar: &mut crate::conditional_formatting::DataBar,
     ) -> Result<(), XlsxError> {
         use crate::conditional_formatting::{AxisPosition, BarDirection};
 
+        for attr in data_bar_start.attributes() {
+            let attr = attr.map_err(XlsxError::XmlAttr)?;
+            let v = &*attr.value;
+            match attr.key.local_name().as_ref() {
+                b"showValue" => data_bar.show_value = v != b"0" && v != b"false",
+                b"minLength" => {
+                    if let Ok(len) = atoi_simd::parse::<u32>(v) {
+                        data_bar.min_length = len;
+                    }
+                }
+                b"maxLength" => {
+                    if let Ok(len) = atoi_simd::parse::<u32>(v) {
+                        data_bar.max_length = len;
+                    }
+                }
+                b"gradient" => data_bar.gradient = v != b"0" && v != b"false",
+                b"border" => data_bar.border = v == b"1" || v == b"true",
+                b"direction" => {
+                    data_bar.direction = match v {
+                        b"leftToRight" => Some(BarDirection::LeftToRight),
+                        b"rightToLeft" => Some(BarDirection::RightToLeft),
+                        _ => None,
+                    };
+                }
+                b"axisPosition" => {
+                    data_bar.axis_position = Some(match v {
+                        b"middle" | b"midpoint" => AxisPosition::Midpoint,
+                        b"none" => AxisPosition::None,
+                        _ => AxisPosition::Automatic,
+                    });
+                }
+                _ => (),
+            }
+        }
+
         let mut cfvo_count = 0;
 
         loop {
             buf.clear();
             match xml.read_event_into(buf) {
                 Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
-                    b"dataBar" => {
-                        // Parse dataBar attributes
-                        for attr in e.attributes() {
-                            match attr.map_err(XlsxError::XmlAttr)? {
-                                Attribute {
-                                    key: QName(b"showValue"),
-                                    value: v,
-                                } => {
-                                    data_bar.show_value = &*v != b"0" && &*v != b"false";
-                                }
-                                Attribute {
-                                    key: QName(b"minLength"),
-                                    value: v,
-                                } => {
-                                    if let Ok(len) = atoi_simd::parse::<u32>(&v) {
-                                        data_bar.min_length = len;
-                                    }
-                                }
-                                Attribute {
-                                    key: QName(b"maxLength"),
-                                    value: v,
-                                } => {
-                                    if let Ok(len) = atoi_simd::parse::<u32>(&v) {
-                                        data_bar.max_length = len;
-                                    }
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
                     b"cfvo" => {
                         let cfvo = Self::parse_cfvo(e.attributes(), xml)?;
                         if cfvo_count == 0 {
@@ -1591,7 +2046,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         }
                         cfvo_count += 1;
                     }
-                    b"color" => {
+                    b"color" | b"fillColor" => {
                         if let Some(color) = Self::parse_color_from_attributes(e.attributes())? {
                             data_bar.color = color;
                         }
@@ -1621,46 +2076,6 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         xml.read_to_end_into(e.name(), &mut temp_buf)?;
                     }
                 },
-                Ok(Event::Empty(ref e)) => match e.local_name().as_ref() {
-                    b"dataBar" => {
-                        // Handle self-closing dataBar tag with attributes
-                        for attr in e.attributes() {
-                            match attr.map_err(XlsxError::XmlAttr)? {
-                                Attribute {
-                                    key: QName(b"direction"),
-                                    value: v,
-                                } => {
-                                    let dir_str = xml.decoder().decode(&v)?;
-                                    data_bar.direction = Some(match dir_str.as_ref() {
-                                        "leftToRight" => BarDirection::LeftToRight,
-                                        "rightToLeft" => BarDirection::RightToLeft,
-                                        _ => BarDirection::LeftToRight,
-                                    });
-                                }
-                                Attribute {
-                                    key: QName(b"gradient"),
-                                    value: v,
-                                } => {
-                                    data_bar.gradient = &*v != b"0" && &*v != b"false";
-                                }
-                                Attribute {
-                                    key: QName(b"axisPosition"),
-                                    value: v,
-                                } => {
-                                    let pos_str = xml.decoder().decode(&v)?;
-                                    data_bar.axis_position = Some(match pos_str.as_ref() {
-                                        "automatic" => AxisPosition::Automatic,
-                                        "midpoint" => AxisPosition::Midpoint,
-                                        "none" => AxisPosition::None,
-                                        _ => AxisPosition::Automatic,
-                                    });
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    _ => (),
-                },
                 Ok(Event::End(ref e)) if e.local_name().as_ref() == b"dataBar" => break,
                 Ok(Event::Eof) => return Err(XlsxError::XmlEof("dataBar")),
                 Err(e) => return Err(XlsxError::Xml(e)),
@@ -1708,71 +2123,59 @@ impl<RS: Read + Seek> Xlsx<RS> {
     /// Parse icon set element
     fn parse_icon_set(
         xml: &mut XlReader<'_, RS>,
+        icon_set_start: &BytesStart<'_>,
         buf: &mut Vec<u8>,
         icon_set: &mut crate::conditional_formatting::IconSet,
     ) -> Result<(), XlsxError> {
         use crate::conditional_formatting::IconSetType;
 
+        for attr in icon_set_start.attributes() {
+            let attr = attr.map_err(XlsxError::XmlAttr)?;
+            let v = &*attr.value;
+            match attr.key.local_name().as_ref() {
+                b"iconSet" => {
+                    icon_set.icon_set = match xml.decoder().decode(v)?.as_ref() {
+                        "3Arrows" => IconSetType::Arrows3,
+                        "3ArrowsGray" => IconSetType::Arrows3Gray,
+                        "4Arrows" => IconSetType::Arrows4,
+                        "4ArrowsGray" => IconSetType::Arrows4Gray,
+                        "5Arrows" => IconSetType::Arrows5,
+                        "5ArrowsGray" => IconSetType::Arrows5Gray,
+                        "3Flags" => IconSetType::Flags3,
+                        "3TrafficLights1" => IconSetType::TrafficLights3,
+                        "3TrafficLights2" => IconSetType::TrafficLights3Rimmed,
+                        "4TrafficLights" => IconSetType::TrafficLights4,
+                        "3Signs" => IconSetType::Signs3,
+                        "3Symbols" => IconSetType::Symbols3,
+                        "3Symbols2" => IconSetType::Symbols3Uncircled,
+                        "4Rating" => IconSetType::Rating4,
+                        "5Rating" => IconSetType::Rating5,
+                        "5Quarters" => IconSetType::Quarters5,
+                        "3Stars" => IconSetType::Stars3,
+                        "3Triangles" => IconSetType::Triangles3,
+                        "5Boxes" => IconSetType::Boxes5,
+                        "4RedToBlack" => IconSetType::RedToBlack4,
+                        "4RatingBars" => IconSetType::RatingBars4,
+                        "5RatingBars" => IconSetType::RatingBars5,
+                        "3ColoredArrows" => IconSetType::ColoredArrows3,
+                        "4ColoredArrows" => IconSetType::ColoredArrows4,
+                        "5ColoredArrows" => IconSetType::ColoredArrows5,
+                        "3WhiteArrows" => IconSetType::WhiteArrows3,
+                        "4WhiteArrows" => IconSetType::WhiteArrows4,
+                        "5WhiteArrows" => IconSetType::WhiteArrows5,
+                        _ => IconSetType::Arrows3,
+                    };
+                }
+                b"showValue" => icon_set.show_value = v != b"0" && v != b"false",
+                b"reverse" => icon_set.reverse = v == b"1" || v == b"true",
+                b"percent" => icon_set.percent = v != b"0" && v != b"false",
+                _ => (),
+            }
+        }
+
         loop {
             buf.clear();
             match xml.read_event_into(buf) {
-                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"iconSet" => {
-                    // Parse attributes
-                    for attr in e.attributes() {
-                        match attr.map_err(XlsxError::XmlAttr)? {
-                            Attribute {
-                                key: QName(b"iconSet"),
-                                value: v,
-                            } => {
-                                let icon_str = xml.decoder().decode(&v)?;
-                                icon_set.icon_set = match icon_str.as_ref() {
-                                    "3Arrows" => IconSetType::Arrows3,
-                                    "3ArrowsGray" => IconSetType::Arrows3Gray,
-                                    "4Arrows" => IconSetType::Arrows4,
-                                    "4ArrowsGray" => IconSetType::Arrows4Gray,
-                                    "5Arrows" => IconSetType::Arrows5,
-                                    "5ArrowsGray" => IconSetType::Arrows5Gray,
-                                    "3Flags" => IconSetType::Flags3,
-                                    "3TrafficLights1" => IconSetType::TrafficLights3,
-                                    "3TrafficLights2" => IconSetType::TrafficLights3Rimmed,
-                                    "4TrafficLights" => IconSetType::TrafficLights4,
-                                    "3Signs" => IconSetType::Signs3,
-                                    "3Symbols" => IconSetType::Symbols3,
-                                    "3Symbols2" => IconSetType::Symbols3Uncircled,
-                                    "4Rating" => IconSetType::Rating4,
-                                    "5Rating" => IconSetType::Rating5,
-                                    "5Quarters" => IconSetType::Quarters5,
-                                    "3Stars" => IconSetType::Stars3,
-                                    "3Triangles" => IconSetType::Triangles3,
-                                    "5Boxes" => IconSetType::Boxes5,
-                                    "4RedToBlack" => IconSetType::RedToBlack4,
-                                    "4RatingBars" => IconSetType::RatingBars4,
-                                    "5RatingBars" => IconSetType::RatingBars5,
-                                    "3ColoredArrows" => IconSetType::ColoredArrows3,
-                                    "4ColoredArrows" => IconSetType::ColoredArrows4,
-                                    "5ColoredArrows" => IconSetType::ColoredArrows5,
-                                    "3WhiteArrows" => IconSetType::WhiteArrows3,
-                                    "4WhiteArrows" => IconSetType::WhiteArrows4,
-                                    "5WhiteArrows" => IconSetType::WhiteArrows5,
-                                    _ => IconSetType::Arrows3,
-                                };
-                            }
-                            Attribute {
-                                key: QName(b"showValue"),
-                                value: v,
-                            } => {
-                                icon_set.show_value = &*v != b"0" && &*v != b"false";
-                            }
-                            Attribute {
-                                key: QName(b"reverse"),
-                                value: v,
-                            } => {
-                                icon_set.reverse = &*v == b"1" || &*v == b"true";
-                            }
-                            _ => (),
-                        }
-                    }
-                }
                 Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
                     b"cfvo" => {
                         let cfvo = Self::parse_cfvo(e.attributes(), xml)?;
@@ -2171,11 +2574,14 @@ impl<RS: Read + Seek> Xlsx<RS> {
         &mut self,
         relationships: &BTreeMap<Vec<u8>, String>,
     ) -> Result<(), XlsxError> {
-        let mut xml = match xml_reader(&mut self.zip, "xl/workbook.xml") {
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            "xl/workbook.xml",
+            self.options.max_decompressed_bytes,
+        ) {
             None => return Ok(()),
             Some(x) => x?,
         };
-        let mut defined_names = Vec::new();
         let mut buf = Vec::with_capacity(1024);
         let mut val_buf = Vec::with_capacity(1024);
         loop {
@@ -2211,14 +2617,11 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                     }
                                 }
                             }
-                            Attribute {
-                                key: QName(b"r:id"),
-                                value: v,
-                            }
-                            | Attribute {
-                                key: QName(b"relationships:id"),
-                                value: v,
-                            } => {
+                            // usually `r:id`, but the prefix of the relationships
+                            // namespace is up to the writer
+                            Attribute { key, value: v }
+                                if key.prefix().is_some() && key.local_name().as_ref() == b"id" =>
+                            {
                                 let r = &relationships
                                     .get(&*v)
                                     .ok_or(XlsxError::RelationshipNotFound)?[..];
@@ -2253,7 +2656,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                     });
                     self.sheets.push((name, path));
                 }
-                Ok(Event::Start(ref e)) if e.name().as_ref() == b"workbookPr" => {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"workbookPr" => {
                     self.is_1904 = match e.try_get_attribute("date1904")? {
                         Some(c) => ["1", "true"].contains(
                             &c.decode_and_unescape_value(xml.decoder())
@@ -2263,25 +2666,89 @@ impl<RS: Read + Seek> Xlsx<RS> {
                         None => false,
                     };
                 }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"customWorkbookView" => {
+                    let (mut guid, mut view_name) = (None, None);
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        match a.key.local_name().as_ref() {
+                            b"guid" => guid = Some(a.decode_and_unescape_value(xml.decoder())?),
+                            b"name" => {
+                                view_name = Some(a.decode_and_unescape_value(xml.decoder())?)
+                            }
+                            _ => (),
+                        }
+                    }
+                    if let (Some(guid), Some(view_name)) = (guid, view_name) {
+                        self.custom_view_names
+                            .insert(guid.into_owned(), view_name.into_owned());
+                    }
+                }
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"workbookProtection" => {
+                    let mut protection = WorkbookProtection::default();
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let value = a.decode_and_unescape_value(xml.decoder())?;
+                        match a.key.local_name().as_ref() {
+                            b"lockStructure" => {
+                                protection.lock_structure = ["1", "true"].contains(&&*value);
+                            }
+                            b"lockWindows" => {
+                                protection.lock_windows = ["1", "true"].contains(&&*value);
+                            }
+                            b"lockRevision" => {
+                                protection.lock_revision = ["1", "true"].contains(&&*value);
+                            }
+                            b"workbookPassword" => {
+                                protection.password_hash = Some(value.into_owned());
+                            }
+                            b"workbookAlgorithmName" => {
+                                protection.algorithm_name = Some(value.into_owned());
+                            }
+                            b"workbookHashValue" => {
+                                protection.hash_value = Some(value.into_owned());
+                            }
+                            b"workbookSaltValue" => {
+                                protection.salt_value = Some(value.into_owned());
+                            }
+                            b"workbookSpinCount" => protection.spin_count = value.parse().ok(),
+                            _ => (),
+                        }
+                    }
+                    self.workbook_protection = Some(protection);
+                }
                 Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"definedName" => {
-                    if let Some(a) = e
-                        .attributes()
-                        .filter_map(std::result::Result::ok)
-                        .find(|a| a.key == QName(b"name"))
-                    {
-                        let name = a.decode_and_unescape_value(xml.decoder())?.to_string();
-                        val_buf.clear();
-                        let mut value = String::new();
-                        loop {
-                            match xml.read_event_into(&mut val_buf)? {
-                                Event::Text(t) => value.push_str(&t.unescape()?),
-                                Event::End(end) if end.name() == e.name() => break,
-                                Event::Eof => return Err(XlsxError::XmlEof("workbook")),
-                                _ => (),
+                    let mut defined_name = DefinedName::default();
+                    for a in e.attributes().filter_map(std::result::Result::ok) {
+                        match a.key.as_ref() {
+                            b"name" => {
+                                defined_name.name =
+                                    a.decode_and_unescape_value(xml.decoder())?.into_owned();
+                            }
+                            b"function" => {
+                                defined_name.is_function = matches!(&*a.value, b"1" | b"true");
+                            }
+                            b"functionGroupId" => {
+                                defined_name.function_group_id = std::str::from_utf8(&a.value)
+                                    .ok()
+                                    .and_then(|v| v.parse().ok());
                             }
+                            _ => (),
+                        }
+                    }
+                    if defined_name.name.is_empty() {
+                        continue;
+                    }
+                    defined_name.is_function |= defined_name.name.starts_with("_xll.");
+                    val_buf.clear();
+                    loop {
+                        match xml.read_event_into(&mut val_buf)? {
+                            Event::Text(t) => defined_name.formula.push_str(&t.unescape()?),
+                            Event::End(end) if end.name() == e.name() => break,
+                            Event::Eof => return Err(XlsxError::XmlEof("workbook")),
+                            _ => (),
                         }
-                        defined_names.push((name, value));
                     }
+                    self.defined_names.push(defined_name);
                 }
                 Ok(Event::End(ref e)) if e.local_name().as_ref() == b"workbook" => break,
                 Ok(Event::Eof) => return Err(XlsxError::XmlEof("workbook")),
@@ -2289,49 +2756,22 @@ impl<RS: Read + Seek> Xlsx<RS> {
                 _ => (),
             }
         }
-        self.metadata.names = defined_names;
+        self.metadata.names = self
+            .defined_names
+            .iter()
+            .map(|name| (name.name.clone(), name.formula.clone()))
+            .collect();
         Ok(())
     }
 
     fn read_relationships(&mut self) -> Result<BTreeMap<Vec<u8>, String>, XlsxError> {
-        let mut xml = match xml_reader(&mut self.zip, "xl/_rels/workbook.xml.rels") {
-            None => {
-                return Err(XlsxError::FileNotFound(
-                    "xl/_rels/workbook.xml.rels".to_string(),
-                ));
-            }
-            Some(x) => x?,
-        };
-        let mut relationships = BTreeMap::new();
-        let mut buf = Vec::with_capacity(64);
-        loop {
-            buf.clear();
-            match xml.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"Relationship" => {
-                    let mut id = Vec::new();
-                    let mut target = String::new();
-                    for a in e.attributes() {
-                        match a.map_err(XlsxError::XmlAttr)? {
-                            Attribute {
-                                key: QName(b"Id"),
-                                value: v,
-                            } => id.extend_from_slice(&v),
-                            Attribute {
-                                key: QName(b"Target"),
-                                value: v,
-                            } => target = xml.decoder().decode(&v)?.into_owned(),
-                            _ => (),
-                        }
-                    }
-                    relationships.insert(id, target);
-                }
-                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"Relationships" => break,
-                Ok(Event::Eof) => return Err(XlsxError::XmlEof("Relationships")),
-                Err(e) => return Err(XlsxError::Xml(e)),
-                _ => (),
-            }
-        }
-        Ok(relationships)
+        let relationships = self
+            .read_part_relationships("xl/workbook.xml")?
+            .ok_or_else(|| XlsxError::FileNotFound("xl/_rels/workbook.xml.rels".to_string()))?;
+        Ok(relationships
+            .into_iter()
+            .map(|(id, relationship)| (id.into_bytes(), relationship.target))
+            .collect())
     }
 
     // sheets must be added before this is called!!
@@ -2346,7 +2786,11 @@ impl<RS: Read + Seek> Xlsx<RS> {
             let mut buf = Vec::with_capacity(64);
             // we need another mutable borrow of self.zip later so we enclose this borrow within braces
             {
-                let mut xml = match xml_reader(&mut self.zip, &rel_path) {
+                let mut xml = match xml_reader(
+                    &mut self.zip,
+                    &rel_path,
+                    self.options.max_decompressed_bytes,
+                ) {
                     None => continue,
                     Some(x) => x?,
                 };
@@ -2370,7 +2814,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                     Attribute {
                                         key: QName(b"Type"),
                                         value: v,
-                                    } => table_type = *v == b"http://schemas.openxmlformats.org/officeDocument/2006/relationships/table"[..],
+                                    } => table_type = is_relationship_type(&v, "table"),
                                     _ => (),
                                 }
                             }
@@ -2398,12 +2842,17 @@ impl<RS: Read + Seek> Xlsx<RS> {
                 }
             }
             for table_file in table_locations {
-                let mut xml = match xml_reader(&mut self.zip, &table_file) {
+                let mut xml = match xml_reader(
+                    &mut self.zip,
+                    &table_file,
+                    self.options.max_decompressed_bytes,
+                ) {
                     None => continue,
                     Some(x) => x?,
                 };
                 let mut column_names = Vec::new();
                 let mut table_meta = InnerTableMetadata::new();
+                let mut style = None;
                 loop {
                     buf.clear();
                     match xml.read_event_into(&mut buf) {
@@ -2457,6 +2906,27 @@ impl<RS: Read + Seek> Xlsx<RS> {
                                 }
                             }
                         }
+                        Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"tableStyleInfo" => {
+                            let mut info = TableStyleInfo::default();
+                            for a in e.attributes() {
+                                let a = a.map_err(XlsxError::XmlAttr)?;
+                                let flag = matches!(&*a.value, b"1" | b"true");
+                                match a.key {
+                                    QName(b"name") => {
+                                        info.name = Some(
+                                            a.decode_and_unescape_value(xml.decoder())?
+                                                .into_owned(),
+                                        )
+                                    }
+                                    QName(b"showFirstColumn") => info.show_first_column = flag,
+                                    QName(b"showLastColumn") => info.show_last_column = flag,
+                                    QName(b"showRowStripes") => info.show_row_stripes = flag,
+                                    QName(b"showColumnStripes") => info.show_column_stripes = flag,
+                                    _ => (),
+                                }
+                            }
+                            style = Some(info);
+                        }
                         Ok(Event::End(ref e)) if e.local_name().as_ref() == b"table" => break,
                         Ok(Event::Eof) => return Err(XlsxError::XmlEof("Table")),
                         Err(e) => return Err(XlsxError::Xml(e)),
@@ -2473,12 +2943,13 @@ impl<RS: Read + Seek> Xlsx<RS> {
                 if table_meta.insert_row {
                     dims.end.0 -= 1;
                 }
-                new_tables.push((
-                    table_meta.display_name,
-                    sheet_name.clone(),
-                    column_names,
-                    dims,
-                ));
+                new_tables.push(TableMetadata {
+                    name: table_meta.display_name,
+                    sheet_name: sheet_name.clone(),
+                    columns: column_names,
+                    dimensions: dims,
+                    style,
+                });
             }
         }
         self.tables = Some(new_tables);
@@ -2490,7 +2961,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
     fn read_pictures(&mut self) -> Result<(), XlsxError> {
         let mut pics = Vec::new();
         for i in 0..self.zip.len() {
-            let mut zfile = self.zip.by_index(i)?;
+            let zfile = self.zip.by_index(i)?;
             let zname = zfile.name();
             if zname.starts_with("xl/media") {
                 if let Some(ext) = zname.split('.').next_back() {
@@ -2501,8 +2972,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
                     .contains(&ext)
                     {
                         let ext = ext.to_string();
-                        let mut buf: Vec<u8> = Vec::new();
-                        zfile.read_to_end(&mut buf)?;
+                        let buf = read_part(zfile, self.options.max_decompressed_bytes)?;
                         pics.push((ext, buf));
                     }
                 }
@@ -2520,7 +2990,11 @@ impl<RS: Read + Seek> Xlsx<RS> {
         for (sheet_name, sheet_path) in &self.sheets {
             // we need another mutable borrow of self.zip later so we enclose this borrow within braces
             {
-                let mut xml = match xml_reader(&mut self.zip, sheet_path) {
+                let mut xml = match xml_reader(
+                    &mut self.zip,
+                    sheet_path,
+                    self.options.max_decompressed_bytes,
+                ) {
                     None => continue,
                     Some(x) => x?,
                 };
@@ -2551,28 +3025,13 @@ impl<RS: Read + Seek> Xlsx<RS> {
 
     #[inline]
     fn get_table_meta(&self, table_name: &str) -> Result<TableMetadata, XlsxError> {
-        let match_table_meta = self
-            .tables
+        self.tables
             .as_ref()
             .expect("Tables must be loaded before they are referenced")
             .iter()
-            .find(|(table, ..)| table == table_name)
-            .ok_or_else(|| XlsxError::TableNotFound(table_name.into()))?;
-
-        let name = match_table_meta.0.to_owned();
-        let sheet_name = match_table_meta.1.clone();
-        let columns = match_table_meta.2.clone();
-        let dimensions = Dimensions {
-            start: match_table_meta.3.start,
-            end: match_table_meta.3.end,
-        };
-
-        Ok(TableMetadata {
-            name,
-            sheet_name,
-            columns,
-            dimensions,
-        })
+            .find(|table| table.name == table_name)
+            .cloned()
+            .ok_or_else(|| XlsxError::TableNotFound(table_name.into()))
     }
 
     /// Get comprehensive formatting information for a cell by its style index
@@ -2591,6 +3050,302 @@ impl<RS: Read + Seek> Xlsx<RS> {
         &self.format_interner
     }
 
+    /// Get the indexed color palette of the workbook
+    ///
+    /// Returns the custom palette defined by `<indexedColors>` in `styles.xml`
+    /// if the workbook redefines it, otherwise Excel's default palette.
+    pub fn indexed_palette(&self) -> &[Color] {
+        if self.indexed_colors.is_empty() {
+            &BUILTIN_INDEXED_COLORS
+        } else {
+            &self.indexed_colors
+        }
+    }
+
+    /// Resolve a [`Color::Indexed`] index to a color
+    ///
+    /// The workbook's custom palette takes precedence over the default one.
+    /// Returns `None` if the index is not part of the palette, e.g. for the
+    /// system foreground (64) and background (65) colors.
+    pub fn indexed_color(&self, index: u32) -> Option<&Color> {
+        self.indexed_palette().get(index as usize)
+    }
+
+    /// Get the most recently used colors (`<mruColors>`) of the workbook
+    pub fn mru_colors(&self) -> &[Color] {
+        &self.mru_colors
+    }
+
+    /// Get the named cell styles of the workbook (`<cellStyles>`)
+    pub fn named_styles(&self) -> &[NamedStyle] {
+        &self.named_styles
+    }
+
+    /// Get the custom table styles of the workbook (`<tableStyles>`)
+    ///
+    /// Builtin styles, e.g. "TableStyleMedium2", are not listed.
+    pub fn table_styles(&self) -> &[TableStyle] {
+        &self.table_styles
+    }
+
+    /// Get the name of the style applied by default to new tables
+    /// (`defaultTableStyle` of `<tableStyles>`)
+    pub fn default_table_style(&self) -> Option<&str> {
+        self.default_table_style.as_deref()
+    }
+
+    /// Get the name of the style applied by default to new pivot tables
+    /// (`defaultPivotStyle` of `<tableStyles>`)
+    pub fn default_pivot_style(&self) -> Option<&str> {
+        self.default_pivot_style.as_deref()
+    }
+
+    /// Get the defined names of the workbook with their attributes
+    ///
+    /// Unlike [`Reader::defined_names`], which only gives the name and the
+    /// formula, this tells the functions registered by macro sheets and
+    /// add-ins apart from the data names.
+    pub fn defined_names_with_attributes(&self) -> &[DefinedName] {
+        &self.defined_names
+    }
+
+    /// Whether the workbook uses the 1904 date system (`date1904`)
+    pub fn is_1904(&self) -> bool {
+        self.is_1904
+    }
+
+    /// Get the workbook protection settings
+    ///
+    /// Returns `None` if the workbook has no `<workbookProtection>` element.
+    pub fn workbook_protection(&self) -> Option<WorkbookProtection> {
+        self.workbook_protection.clone()
+    }
+
+    /// Get the relationships of a part of the workbook, by relationship id
+    ///
+    /// `part_path` is the path of the part in the archive, e.g.
+    /// `xl/workbook.xml` or `xl/worksheets/sheet1.xml`, whose relationships
+    /// are read from its `.rels` part. Returns an empty map if the part has
+    /// no relationships.
+    pub fn relationships(
+        &mut self,
+        part_path: &str,
+    ) -> Result<HashMap<String, Relationship>, XlsxError> {
+        let relationships = self.read_part_relationships(part_path)?;
+        Ok(relationships.into_iter().flatten().collect())
+    }
+
+    /// Read the relationships of a part, in document order
+    ///
+    /// Returns `None` if the part has no `.rels` part.
+    fn read_part_relationships(
+        &mut self,
+        part_path: &str,
+    ) -> Result<Option<Vec<(String, Relationship)>>, XlsxError> {
+        let (base_folder, file_name) = part_path.rsplit_once('/').unwrap_or(("", part_path));
+        let rel_path = if base_folder.is_empty() {
+            format!("_rels/{file_name}.rels")
+        } else {
+            format!("{base_folder}/_rels/{file_name}.rels")
+        };
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            &rel_path,
+            self.options.max_decompressed_bytes,
+        ) {
+            None => return Ok(None),
+            Some(x) => x?,
+        };
+
+        let mut relationships = Vec::new();
+        let mut buf = Vec::with_capacity(64);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"Relationship" => {
+                    let mut id = String::new();
+                    let mut relationship = Relationship::default();
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let value = a.decode_and_unescape_value(xml.decoder())?.into_owned();
+                        match a.key {
+                            QName(b"Id") => id = value,
+                            QName(b"Type") => relationship.rel_type = value,
+                            QName(b"Target") => relationship.target = value,
+                            QName(b"TargetMode") => relationship.target_mode = Some(value),
+                            _ => (),
+                        }
+                    }
+                    if !relationship.is_external() && !relationship.target.is_empty() {
+                        relationship.target =
+                            resolve_relationship_target(base_folder, &relationship.target);
+                    }
+                    relationships.push((id, relationship));
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(Some(relationships))
+    }
+
+    /// Get the paths of the parts related to a part with a relationship
+    /// `Type` ending with `/{typ}`
+    fn relationship_targets(
+        &mut self,
+        part_path: &str,
+        typ: &str,
+    ) -> Result<Vec<String>, XlsxError> {
+        let relationships = self.read_part_relationships(part_path)?;
+        Ok(relationships
+            .into_iter()
+            .flatten()
+            .filter(|(_, rel)| {
+                !rel.is_external()
+                    && !rel.target.is_empty()
+                    && rel
+                        .rel_type
+                        .strip_suffix(typ)
+                        .is_some_and(|t| t.ends_with('/'))
+            })
+            .map(|(_, rel)| rel.target)
+            .collect())
+    }
+
+    /// Get the paths of the parts related to a part, by relationship id
+    fn part_relationships(
+        &mut self,
+        part_path: &str,
+    ) -> Result<BTreeMap<Vec<u8>, String>, XlsxError> {
+        let relationships = self.read_part_relationships(part_path)?;
+        Ok(relationships
+            .into_iter()
+            .flatten()
+            .filter(|(_, rel)| !rel.target.is_empty())
+            .map(|(id, rel)| (id.into_bytes(), rel.target))
+            .collect())
+    }
+
+    /// Get the custom document properties (`docProps/custom.xml`)
+    ///
+    /// Each property is returned with its typed value: strings as
+    /// [`Data::String`], integers as [`Data::Int`], reals and currencies as
+    /// [`Data::Float`], booleans as [`Data::Bool`] and dates as
+    /// [`Data::DateTimeIso`]. Values of other types are kept as strings.
+    /// Returns an empty vector if the workbook has no custom properties.
+    pub fn custom_properties(&mut self) -> Result<Vec<(String, Data)>, XlsxError> {
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            "docProps/custom.xml",
+            self.options.max_decompressed_bytes,
+        ) {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
+        };
+
+        let mut properties = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        let mut val_buf = Vec::new();
+        let mut name = None;
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"property" => {
+                    name = match get_attribute(e.attributes(), QName(b"name"))? {
+                        Some(n) => Some(xml.decoder().decode(n)?.into_owned()),
+                        None => None,
+                    };
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"property" => name = None,
+                Ok(Event::Start(ref e)) if name.is_some() => {
+                    let mut text = String::new();
+                    loop {
+                        val_buf.clear();
+                        match xml.read_event_into(&mut val_buf)? {
+                            Event::Text(t) => text.push_str(&t.unescape()?),
+                            Event::End(end) if end.name() == e.name() => break,
+                            Event::Eof => return Err(XlsxError::XmlEof("property")),
+                            _ => (),
+                        }
+                    }
+                    let value = match e.local_name().as_ref() {
+                        b"i1" | b"i2" | b"i4" | b"i8" | b"int" | b"ui1" | b"ui2" | b"ui4"
+                        | b"ui8" | b"uint" => text.trim().parse().map(Data::Int).ok(),
+                        b"r4" | b"r8" | b"decimal" | b"cy" => {
+                            text.trim().parse().map(Data::Float).ok()
+                        }
+                        b"bool" => Some(Data::Bool(matches!(text.trim(), "true" | "1"))),
+                        b"date" | b"filetime" => Some(Data::DateTimeIso(text.trim().to_string())),
+                        _ => None,
+                    };
+                    let name = name.take().unwrap_or_default();
+                    properties.push((name, value.unwrap_or(Data::String(text))));
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(properties)
+    }
+
+    /// Get the shared strings table (`xl/sharedStrings.xml`)
+    ///
+    /// Cells of type `s` hold an index into this table. Rich text strings are
+    /// flattened to their plain text, without the phonetic runs, as they are
+    /// when reading cells.
+    pub fn shared_strings(&self) -> &[String] {
+        &self.strings
+    }
+
+    /// Get the print areas of all the sheets, by sheet name
+    ///
+    /// Print areas are the `_xlnm.Print_Area` defined names. A print area
+    /// made of several ranges gives several rectangles. Sheets without a
+    /// print area are not in the map, whole columns and rows span all the
+    /// rows or columns of the sheet, and invalid references are skipped.
+    pub fn all_print_areas(&self) -> HashMap<String, Vec<Dimensions>> {
+        let mut print_areas: HashMap<String, Vec<Dimensions>> = HashMap::new();
+        for (name, formula) in &self.metadata.names {
+            if name != "_xlnm.Print_Area" {
+                continue;
+            }
+            for reference in split_reference_union(formula) {
+                let Some((sheet, range)) = reference.rsplit_once('!') else {
+                    continue;
+                };
+                match parse_sqref(range) {
+                    Ok(dimensions) => print_areas
+                        .entry(unquote_sheet_name(sheet))
+                        .or_default()
+                        .extend(dimensions),
+                    Err(e) => warn!("skipping print area '{reference}': {e}"),
+                }
+            }
+        }
+        print_areas
+    }
+
+    /// Get the value of a defined name holding a constant
+    ///
+    /// Defined names can hold a literal instead of a reference, e.g. `42` or
+    /// `"Q1"`, to be used as workbook constants. Numbers are read as
+    /// [`Data::Float`], strings as [`Data::String`], `TRUE` and `FALSE` as
+    /// [`Data::Bool`] and errors, e.g. `#N/A`, as [`Data::Error`].
+    ///
+    /// Names are matched ignoring ASCII case, as in Excel. Returns `None` if
+    /// there is no such name, or if it holds a reference or any other
+    /// formula.
+    pub fn defined_name_value(&self, name: &str) -> Option<Data> {
+        let (_, formula) = self
+            .metadata
+            .names
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))?;
+        parse_literal(formula)
+    }
+
     /// Load the merged regions
     pub fn load_merged_regions(&mut self) -> Result<(), XlsxError> {
         if self.merged_regions.is_none() {
@@ -2631,7 +3386,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
             .as_ref()
             .expect("Tables must be loaded before they are referenced")
             .iter()
-            .map(|(name, ..)| name)
+            .map(|table| &table.name)
             .collect()
     }
 
@@ -2641,8 +3396,8 @@ impl<RS: Read + Seek> Xlsx<RS> {
             .as_ref()
             .expect("Tables must be loaded before they are referenced")
             .iter()
-            .filter(|(_, sheet, ..)| sheet == sheet_name)
-            .map(|(name, ..)| name)
+            .filter(|table| table.sheet_name == sheet_name)
+            .map(|table| &table.name)
             .collect()
     }
 
@@ -2657,6 +3412,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
             sheet_name,
             columns,
             dimensions,
+            style,
         } = self.get_table_meta(table_name)?;
         let Dimensions { start, end } = dimensions;
         let range = self.worksheet_range(&sheet_name)?;
@@ -2667,6 +3423,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
             sheet_name,
             columns,
             data: tbl_rng,
+            style,
         })
     }
 
@@ -2677,6 +3434,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
             sheet_name,
             columns,
             dimensions,
+            style,
         } = self.get_table_meta(table_name)?;
         let Dimensions { start, end } = dimensions;
         let range = self.worksheet_range_ref(&sheet_name)?;
@@ -2687,6 +3445,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
             sheet_name,
             columns,
             data: tbl_rng,
+            style,
         })
     }
 
@@ -2696,7 +3455,7 @@ impl<RS: Read + Seek> Xlsx<RS> {
         name: &str,
     ) -> Option<Result<Vec<Dimensions>, XlsxError>> {
         let (_, path) = self.sheets.iter().find(|(n, _)| n == name)?;
-        let xml = xml_reader(&mut self.zip, path);
+        let xml = xml_reader(&mut self.zip, path, self.options.max_decompressed_bytes);
 
         xml.map(|xml| {
             let mut xml = xml?;
@@ -2739,87 +3498,1754 @@ impl<RS: Read + Seek> Xlsx<RS> {
         self.worksheet_merge_cells(&name)
     }
 
-    /// Get a cell reader for the worksheet (with comprehensive formatting)
-    pub fn worksheet_cells_reader_ext(
-        &mut self,
-        name: &str,
-    ) -> Result<XlsxCellReader<'_, RS>, XlsxError> {
-        let xml = xml_reader(&mut self.zip, &format!("xl/worksheets/{}.xml", name))
-            .ok_or_else(|| XlsxError::FileNotFound(format!("xl/worksheets/{}.xml", name)))??;
-        let is_1904 = self.is_1904;
-        let strings = &self.strings;
-        let formats = &self.styles;
-        XlsxCellReader::new(xml, strings, formats, is_1904)
+    /// Get the worksheet at `index`, in the order of [`Reader::sheet_names`]
+    ///
+    /// Unlike [`Reader::worksheet_range_at`], the sheet is not looked up by
+    /// name, so the right part is read even when several sheets share the
+    /// same name. The [`HeaderRow`] option is honored. Only the cell values
+    /// are read, without their formatting, and sheets which are not
+    /// worksheets, e.g. chartsheets, give an empty range.
+    pub fn worksheet_range_at_index(&mut self, index: usize) -> Result<Range<Data>, XlsxError> {
+        if index >= self.sheets.len() {
+            return Err(XlsxError::WorksheetNotFound(format!("#{index}")));
+        }
+        let header_row = self.options.header_row;
+        let mut cell_reader = match self.cells_reader_at(index) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(typ)) => {
+                warn!("'{typ}' not a worksheet");
+                return Ok(Range::default());
+            }
+            Err(e) => return Err(e),
+        };
+        let mut cells = Vec::new();
+        while let Some(cell) = cell_reader.next_cell()? {
+            if !matches!(cell.val, DataRef::Empty) && header_row.includes(cell.pos.0) {
+                cells.push(Cell::new(cell.pos, Data::from(cell.val)));
+            }
+        }
+        self.buffers = cell_reader.into_buffers();
+
+        Ok(range_with_header_row(header_row, cells))
     }
 
-    /// Get column widths for a worksheet
-    pub fn worksheet_column_widths(&mut self, name: &str) -> Result<ColumnWidths, XlsxError> {
-        let cell_reader = self.worksheet_cells_reader(name)?;
-        Ok(cell_reader.column_widths().clone())
+    /// Compare the cell values of every worksheet with another workbook
+    ///
+    /// Worksheets are matched by name and compared with [`Range::diff`].
+    /// Differences are reported in the sheet order of this workbook, then
+    /// the worksheets only present in `other`. Formatting is not compared,
+    /// and sheets which are not worksheets, e.g. chartsheets, are compared as
+    /// empty worksheets.
+    pub fn diff_workbook<RS2: Read + Seek>(
+        &mut self,
+        other: &mut Xlsx<RS2>,
+    ) -> Result<Vec<WorkbookDiff>, XlsxError> {
+        let mut diffs = Vec::new();
+        for index in 0..self.sheets.len() {
+            let name = self.sheets[index].0.clone();
+            let Some(other_index) = other.sheets.iter().position(|(n, _)| *n == name) else {
+                diffs.push(WorkbookDiff::SheetOnlyInLeft(name));
+                continue;
+            };
+            let left = self.worksheet_range_at_index(index)?;
+            let right = other.worksheet_range_at_index(other_index)?;
+            diffs.extend(
+                left.diff(&right)
+                    .into_iter()
+                    .map(|diff| WorkbookDiff::Cell {
+                        sheet: name.clone(),
+                        diff,
+                    }),
+            );
+        }
+        for (name, _) in &other.sheets {
+            if !self.sheets.iter().any(|(n, _)| n == name) {
+                diffs.push(WorkbookDiff::SheetOnlyInRight(name.clone()));
+            }
+        }
+        Ok(diffs)
     }
 
-    /// Get row definitions for a worksheet
-    pub fn worksheet_row_definitions(&mut self, name: &str) -> Result<RowDefinitions, XlsxError> {
+    /// Get only some columns of a worksheet
+    ///
+    /// The worksheet is streamed and only the cells of the absolute columns
+    /// `cols` are kept. Column `i` of the returned range holds column
+    /// `cols[i]` of the worksheet, so the range is `cols.len()` columns wide
+    /// and starts at column 0, while rows keep their worksheet positions.
+    pub fn worksheet_columns(
+        &mut self,
+        name: &str,
+        cols: &[u32],
+    ) -> Result<Range<Data>, XlsxError> {
         let mut cell_reader = self.worksheet_cells_reader(name)?;
-        // TODO - cleanup
-        while let Some((_cell, _)) = cell_reader.next_cell_with_formatting()? {
-            continue;
+        let mut cells = Vec::new();
+        while let Some(cell) = cell_reader.next_cell()? {
+            if matches!(cell.val, DataRef::Empty) || !cols.contains(&cell.pos.1) {
+                continue;
+            }
+            let value = Data::from(cell.val);
+            for (i, _) in cols.iter().enumerate().filter(|(_, &c)| c == cell.pos.1) {
+                cells.push(Cell::new((cell.pos.0, i as u32), value.clone()));
+            }
         }
-        Ok(cell_reader.row_definitions().clone())
+        self.buffers = cell_reader.into_buffers();
+
+        let (Some(first), Some(last)) = (cells.first(), cells.last()) else {
+            return Ok(Range::empty());
+        };
+        let mut range = Range::new((first.pos.0, 0), (last.pos.0, cols.len() as u32 - 1));
+        for cell in cells {
+            range.set_value(cell.pos, cell.val);
+        }
+        Ok(range)
     }
-}
 
-struct TableMetadata {
-    name: String,
-    sheet_name: String,
-    columns: Vec<String>,
-    dimensions: Dimensions,
-}
+    /// Convert a worksheet to CSV, one row at a time
+    ///
+    /// Cells are streamed from the worksheet and each row is written as soon
+    /// as it is complete, so that at most one row is held in memory, whatever
+    /// the size of the worksheet. The output matches [`Range::write_csv`] on
+    /// the worksheet range: a first pass over the cells gets the area of the
+    /// non-empty cells, so that every row has the same number of fields.
+    pub fn worksheet_to_csv<W: Write>(
+        &mut self,
+        sheet: &str,
+        mut w: W,
+        opts: CsvOptions,
+    ) -> Result<(), XlsxError> {
+        let header_row = match self.options.header_row {
+            HeaderRow::FirstNonEmptyRow => None,
+            HeaderRow::Row(row) => Some(row),
+        };
+        let mut cell_reader = self.worksheet_cells_reader(sheet)?;
+        let mut used = None;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if !matches!(cell.val, DataRef::Empty) && header_row.map_or(true, |r| cell.pos.0 >= r) {
+                extend_dimensions(&mut used, cell.pos);
+            }
+        }
+        self.buffers = cell_reader.into_buffers();
+        let Some(mut used) = used else {
+            return Ok(());
+        };
+        // like the worksheet range, the header row is kept even if empty
+        if let Some(row) = header_row {
+            used.start.0 = row;
+        }
 
-struct InnerTableMetadata {
-    display_name: String,
-    ref_cells: String,
-    header_row_count: u32,
-    insert_row: bool,
-    totals_row_count: u32,
-}
+        let mut cell_reader = self.worksheet_cells_reader(sheet)?;
+        let (first_col, last_col) = (used.start.1, used.end.1);
+        let write_row = |w: &mut W, cells: &[(u32, Data)]| -> Result<(), XlsxError> {
+            let mut cells = cells.iter().peekable();
+            for col in first_col..=last_col {
+                if col > first_col {
+                    write!(w, "{}", opts.delimiter)?;
+                }
+                if let Some((_, value)) = cells.next_if(|c| c.0 == col) {
+                    opts.write_field(w, &value.to_string())?;
+                }
+            }
+            opts.write_line_end(w)?;
+            Ok(())
+        };
 
-impl InnerTableMetadata {
-    fn new() -> Self {
-        Self {
-            display_name: String::new(),
-            ref_cells: String::new(),
-            header_row_count: 1,
-            insert_row: false,
-            totals_row_count: 0,
+        // the row being read, whose cells are buffered, and the next row to write
+        let mut row = None;
+        let mut cells = Vec::new();
+        let mut next_row = used.start.0;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if matches!(cell.val, DataRef::Empty) || cell.pos.0 < used.start.0 {
+                continue;
+            }
+            let (r, col) = cell.pos;
+            if row != Some(r) {
+                if let Some(row) = row {
+                    write_row(&mut w, &cells)?;
+                    cells.clear();
+                    next_row = row + 1;
+                }
+                while next_row < r {
+                    write_row(&mut w, &[])?;
+                    next_row += 1;
+                }
+                row = Some(r);
+            }
+            cells.push((col, Data::from(cell.val)));
+        }
+        self.buffers = cell_reader.into_buffers();
+
+        if row.is_some() {
+            write_row(&mut w, &cells)?;
         }
+        Ok(())
     }
-}
 
-impl<RS: Read + Seek> Xlsx<RS> {
-    /// Get a reader over all used cells in the given worksheet cell reader
-    pub fn worksheet_cells_reader<'a>(
-        &'a mut self,
+    /// Get at most `max_rows` rows of a worksheet
+    ///
+    /// Rows are counted by position from the first row with a non-empty
+    /// cell, empty rows included, and every column is kept. Reading stops as
+    /// soon as a cell beyond the last row is met, so the rest of the
+    /// worksheet is not parsed.
+    pub fn worksheet_range_limited(
+        &mut self,
         name: &str,
-    ) -> Result<XlsxCellReader<'a, RS>, XlsxError> {
-        let (_, path) = self
+        max_rows: u32,
+    ) -> Result<Range<Data>, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        let mut cells = Vec::new();
+        let mut end_row = None;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if matches!(cell.val, DataRef::Empty) {
+                continue;
+            }
+            let end = *end_row.get_or_insert(cell.pos.0 as u64 + max_rows as u64);
+            if cell.pos.0 as u64 >= end {
+                break;
+            }
+            cells.push(Cell::new(cell.pos, Data::from(cell.val)));
+        }
+        self.buffers = cell_reader.into_buffers();
+        Ok(Range::from_sparse(cells))
+    }
+
+    /// Get a worksheet along with the index of its likely header row
+    ///
+    /// The header row is the last frozen row if the worksheet has frozen
+    /// rows at the top, else the first row of the range if all its non-empty
+    /// cells are strings, else `None`. The index is the absolute row index,
+    /// zero indexed from row `1`, as in [`Range::start`].
+    pub fn worksheet_range_with_header_hint(
+        &mut self,
+        name: &str,
+    ) -> Result<(Option<u32>, Range<Data>), XlsxError> {
+        let frozen_rows = self.worksheet_frozen_rows(name)?;
+        let index = self
             .sheets
             .iter()
-            .find(|&(n, _)| n == name)
+            .position(|(n, _)| n == name)
             .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
-        let xml = xml_reader(&mut self.zip, path)
-            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
+        let range = self.worksheet_range_at_index(index)?;
+        let header = match (frozen_rows, range.start()) {
+            (Some(rows), _) => Some(rows - 1),
+            (None, Some((row, _))) => {
+                let first_row = range.rows().next().into_iter().flatten();
+                let mut values = first_row.filter(|v| !v.is_empty()).peekable();
+                (values.peek().is_some() && values.all(|v| v.is_string())).then_some(row)
+            }
+            (None, None) => None,
+        };
+        Ok((header, range))
+    }
+
+    /// Get the number of rows frozen at the top of a worksheet, if any
+    fn worksheet_frozen_rows(&mut self, name: &str) -> Result<Option<u32>, XlsxError> {
+        let cell_reader = match self.worksheet_cells_reader(name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let rows = cell_reader
+            .pane()
+            .filter(|pane| matches!(pane.state, PaneState::Frozen | PaneState::FrozenSplit))
+            .map(|pane| pane.y_split)
+            .filter(|&rows| rows >= 1.);
+        self.buffers = cell_reader.into_buffers();
+        Ok(rows.map(|rows| rows as u32))
+    }
+
+    /// Get the threaded comments of a worksheet, in document order
+    ///
+    /// Replies come after the comment they answer and carry its id as
+    /// `parent_id`. The author `personId` is resolved to its display name
+    /// using the workbook person list.
+    pub fn worksheet_threaded_comments(
+        &mut self,
+        name: &str,
+    ) -> Result<Vec<ThreadedComment>, XlsxError> {
+        let sheet_path = match self.sheets.iter().find(|(n, _)| n == name) {
+            Some((_, path)) => path.clone(),
+            None => return Err(XlsxError::WorksheetNotFound(name.to_string())),
+        };
+        let paths = self.relationship_targets(&sheet_path, "threadedComment")?;
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        let persons = self.read_persons()?;
+
+        let mut comments = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        for path in paths {
+            let mut xml =
+                match xml_reader(&mut self.zip, &path, self.options.max_decompressed_bytes) {
+                    None => continue,
+                    Some(x) => x?,
+                };
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"threadedComment" => {
+                        let mut comment = ThreadedComment {
+                            cell: (0, 0),
+                            id: String::new(),
+                            parent_id: None,
+                            person_id: String::new(),
+                            author_display_name: String::new(),
+                            text: String::new(),
+                            timestamp: None,
+                            done: false,
+                        };
+                        for a in e.attributes() {
+                            let a = a.map_err(XlsxError::XmlAttr)?;
+                            match a.key {
+                                QName(b"ref") => comment.cell = get_row_column(&a.value)?,
+                                QName(b"id") => {
+                                    comment.id = a.decode_and_unescape_value(xml.decoder())?.into()
+                                }
+                                QName(b"parentId") => {
+                                    comment.parent_id =
+                                        Some(a.decode_and_unescape_value(xml.decoder())?.into())
+                                }
+                                QName(b"personId") => {
+                                    comment.person_id =
+                                        a.decode_and_unescape_value(xml.decoder())?.into()
+                                }
+                                QName(b"dT") => {
+                                    comment.timestamp =
+                                        Some(a.decode_and_unescape_value(xml.decoder())?.into())
+                                }
+                                QName(b"done") => {
+                                    comment.done = matches!(&*a.value, b"1" | b"true")
+                                }
+                                _ => (),
+                            }
+                        }
+                        if let Some(display_name) = persons.get(&comment.person_id) {
+                            comment.author_display_name = display_name.clone();
+                        }
+                        comments.push(comment);
+                    }
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"text" => {
+                        let mut text = String::new();
+                        let mut val_buf = Vec::new();
+                        loop {
+                            val_buf.clear();
+                            match xml.read_event_into(&mut val_buf)? {
+                                Event::Text(t) => text.push_str(&t.unescape()?),
+                                Event::End(end) if end.name() == e.name() => break,
+                                Event::Eof => return Err(XlsxError::XmlEof("text")),
+                                _ => (),
+                            }
+                        }
+                        if let Some(comment) = comments.last_mut() {
+                            comment.text = text;
+                        }
+                    }
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+        Ok(comments)
+    }
+
+    /// Get the sparkline groups of a worksheet
+    ///
+    /// Sparklines are read from the `x14:sparklineGroups` worksheet extension.
+    /// Each sparkline gives its data range and the cell it is drawn in.
+    /// Sparklines whose data is not a plain range, such as a defined name, are
+    /// skipped.
+    pub fn worksheet_sparklines(&mut self, name: &str) -> Result<Vec<SparklineGroup>, XlsxError> {
+        let (_, path) = self
+            .sheets
+            .iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        let mut xml = xml_reader(&mut self.zip, path, self.options.max_decompressed_bytes)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
+
+        let mut groups = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        let mut val_buf = Vec::new();
+        let mut formula = None;
+        let mut in_groups = false;
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"sparklineGroups" => {
+                    in_groups = true;
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sparklineGroups" => {
+                    in_groups = false;
+                }
+                Ok(Event::Start(ref e))
+                    if in_groups && e.local_name().as_ref() == b"sparklineGroup" =>
+                {
+                    let mut group = SparklineGroup::default();
+                    if let Some(t) = get_attribute(e.attributes(), QName(b"type"))? {
+                        group.sparkline_type = match t {
+                            b"column" => SparklineType::Column,
+                            b"stacked" => SparklineType::WinLoss,
+                            _ => SparklineType::Line,
+                        };
+                    }
+                    groups.push(group);
+                }
+                Ok(Event::Start(ref e))
+                    if in_groups && matches!(e.local_name().as_ref(), b"f" | b"sqref") =>
+                {
+                    let mut text = String::new();
+                    loop {
+                        val_buf.clear();
+                        match xml.read_event_into(&mut val_buf)? {
+                            Event::Text(t) => text.push_str(&t.unescape()?),
+                            Event::End(end) if end.name() == e.name() => break,
+                            Event::Eof => return Err(XlsxError::XmlEof("sparkline")),
+                            _ => (),
+                        }
+                    }
+                    if e.local_name().as_ref() == b"f" {
+                        formula = Some(text);
+                    } else if let Some(formula) = formula.take() {
+                        let (data_sheet, data) = match formula.rsplit_once('!') {
+                            Some((sheet, range)) => (Some(unquote_sheet_name(sheet)), range),
+                            None => (None, formula.as_str()),
+                        };
+                        let sparkline =
+                            get_dimension(data.replace('$', "").as_bytes()).and_then(|data| {
+                                Ok(Sparkline {
+                                    data_sheet,
+                                    data,
+                                    location: get_row_column(text.trim().as_bytes())?,
+                                })
+                            });
+                        match (sparkline, groups.last_mut()) {
+                            (Ok(sparkline), Some(group)) => group.sparklines.push(sparkline),
+                            // e.g. a defined name, which is not resolved
+                            (Err(e), _) => warn!("skipping sparkline '{formula}': {e}"),
+                            (Ok(_), None) => (),
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Get the ranges users can edit when a worksheet is protected
+    ///
+    /// These are the "Allow Edit Ranges" (`<protectedRanges>`) of the
+    /// worksheet, with the password hash of each range if it has one.
+    pub fn worksheet_protected_ranges(
+        &mut self,
+        name: &str,
+    ) -> Result<Vec<ProtectedRange>, XlsxError> {
+        let (_, path) = self
+            .sheets
+            .iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        let mut xml = xml_reader(&mut self.zip, path, self.options.max_decompressed_bytes)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
+
+        let mut ranges = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"protectedRange" => {
+                    let mut range = ProtectedRange::default();
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let value = a.decode_and_unescape_value(xml.decoder())?;
+                        match a.key.local_name().as_ref() {
+                            b"name" => range.name = value.into_owned(),
+                            b"sqref" => {
+                                for area in value.split_whitespace() {
+                                    match parse_sqref_area(area) {
+                                        Ok(dims) => range.sqref.push(dims),
+                                        Err(e) => {
+                                            warn!("skipping protected range area '{area}': {e}")
+                                        }
+                                    }
+                                }
+                            }
+                            b"password" => range.password_hash = Some(value.into_owned()),
+                            b"algorithmName" => range.algorithm_name = Some(value.into_owned()),
+                            b"hashValue" => range.hash_value = Some(value.into_owned()),
+                            b"saltValue" => range.salt_value = Some(value.into_owned()),
+                            b"spinCount" => range.spin_count = value.parse().ok(),
+                            b"securityDescriptor" => {
+                                range.security_descriptor = Some(value.into_owned());
+                            }
+                            _ => (),
+                        }
+                    }
+                    ranges.push(range);
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(ranges)
+    }
+
+    /// Get the custom views of a worksheet (`<customSheetViews>`)
+    ///
+    /// Views are returned in the worksheet order, named after the workbook
+    /// custom view of the same GUID.
+    pub fn worksheet_custom_views(
+        &mut self,
+        name: &str,
+    ) -> Result<Vec<CustomSheetView>, XlsxError> {
+        let (_, path) = self
+            .sheets
+            .iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        let mut xml = xml_reader(&mut self.zip, path, self.options.max_decompressed_bytes)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
+
+        let mut views: Vec<CustomSheetView> = Vec::new();
+        let mut in_view = false;
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"customSheetView" => {
+                    let mut view = CustomSheetView::default();
+                    for a in e.attributes() {
+                        let a = a.map_err(XlsxError::XmlAttr)?;
+                        let value = a.decode_and_unescape_value(xml.decoder())?;
+                        let flag = ["1", "true"].contains(&&*value);
+                        match a.key.local_name().as_ref() {
+                            b"guid" => view.guid = value.into_owned(),
+                            b"state" => {
+                                view.state = match &*value {
+                                    "hidden" => SheetVisible::Hidden,
+                                    "veryHidden" => SheetVisible::VeryHidden,
+                                    _ => SheetVisible::Visible,
+                                }
+                            }
+                            b"hiddenRows" => view.hidden_rows = flag,
+                            b"hiddenColumns" => view.hidden_columns = flag,
+                            b"filter" => view.filter = flag,
+                            b"filterUnique" => view.filter_unique = flag,
+                            _ => (),
+                        }
+                    }
+                    view.name = self.custom_view_names.get(&view.guid).cloned();
+                    in_view = true;
+                    views.push(view);
+                }
+                Ok(Event::Start(ref e)) if in_view && e.local_name().as_ref() == b"autoFilter" => {
+                    if let (Some(view), Some(r)) = (
+                        views.last_mut(),
+                        get_attribute(e.attributes(), QName(b"ref"))?,
+                    ) {
+                        view.auto_filter = Some(get_dimension(r)?);
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"customSheetView" => {
+                    in_view = false;
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(views)
+    }
+
+    /// Get the images placed in the cells of a worksheet, with their position
+    ///
+    /// In-cell images (inserted with "Place in Cell" or returned by the
+    /// `IMAGE` function) are rich values: the cell `vm` attribute points to
+    /// `xl/metadata.xml`, which leads to a rich value in `xl/richData/` holding
+    /// the relationship of the image. The bytes of the image part are
+    /// returned as is.
+    pub fn worksheet_cell_images(&mut self, name: &str) -> Result<CellImages, XlsxError> {
+        let cells = self.worksheet_value_metadata_cells(name)?;
+        if cells.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let image_paths = self.read_rich_value_image_paths()?;
+        let mut images = Vec::new();
+        for (pos, vm) in cells {
+            let Some(Some(path)) = vm.checked_sub(1).and_then(|vm| image_paths.get(vm)) else {
+                continue;
+            };
+            let data = match self.zip.by_name(path) {
+                Ok(file) => read_part(file, self.options.max_decompressed_bytes)?,
+                Err(ZipError::FileNotFound) => continue,
+                Err(e) => return Err(XlsxError::Zip(e)),
+            };
+            images.push((pos, data));
+        }
+        Ok(images)
+    }
+
+    /// Get the pictures placed over a worksheet, in drawing order
+    ///
+    /// Pictures are read from the drawing parts of the worksheet, along with
+    /// their anchor cell, name and alternative text. Shapes, charts and
+    /// pictures linked to an external file are skipped. See
+    /// [`Xlsx::worksheet_cell_images`] for the images placed in cells and
+    /// [`Xlsx::worksheet_charts`] for the charts.
+    pub fn worksheet_pictures(&mut self, name: &str) -> Result<Vec<WorksheetPicture>, XlsxError> {
+        let sheet_path = match self.sheets.iter().find(|(n, _)| n == name) {
+            Some((_, path)) => path.clone(),
+            None => return Err(XlsxError::WorksheetNotFound(name.to_string())),
+        };
+        let drawing_paths = self.relationship_targets(&sheet_path, "drawing")?;
+
+        let mut pictures = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        let mut text_buf = Vec::with_capacity(64);
+        for drawing_path in drawing_paths {
+            let targets = self.part_relationships(&drawing_path)?;
+            let mut xml = match xml_reader(
+                &mut self.zip,
+                &drawing_path,
+                self.options.max_decompressed_bytes,
+            ) {
+                None => continue,
+                Some(x) => x?,
+            };
+            // picture of the current anchor, with the relationship id of its image
+            let mut picture: Option<(WorksheetPicture, Vec<u8>)> = None;
+            let mut anchor_kind = AnchorKind::TwoCell;
+            let (mut from, mut from_offset) = ((0, 0), (0, 0));
+            let (mut to, mut to_offset) = ((0, 0), (0, 0));
+            let (mut extent, mut position) = (None, None);
+            let mut in_from = false;
+            let mut in_to = false;
+            // depth of the current element, and of the current anchor, to
+            // tell the `ext` of the anchor from the ones of the picture
+            let mut depth = 0;
+            let mut anchor_depth = 0;
+            loop {
+                buf.clear();
+                let event = xml.read_event_into(&mut buf);
+                match &event {
+                    Ok(Event::Start(_)) => depth += 1,
+                    Ok(Event::End(_)) => depth -= 1,
+                    _ => (),
+                }
+                match event {
+                    Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                        kind @ (b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor") => {
+                            anchor_kind = match kind {
+                                b"oneCellAnchor" => AnchorKind::OneCell,
+                                b"absoluteAnchor" => AnchorKind::Absolute,
+                                _ => AnchorKind::TwoCell,
+                            };
+                            (from, from_offset) = ((0, 0), (0, 0));
+                            (to, to_offset) = ((0, 0), (0, 0));
+                            (extent, position) = (None, None);
+                            anchor_depth = depth;
+                        }
+                        name @ (b"ext" | b"pos") if depth == anchor_depth + 1 => {
+                            let (x_key, y_key): (&[u8], &[u8]) = match name {
+                                b"ext" => (b"cx", b"cy"),
+                                _ => (b"x", b"y"),
+                            };
+                            let (mut x, mut y) = (0, 0);
+                            for a in e.attributes() {
+                                let a = a.map_err(XlsxError::XmlAttr)?;
+                                let value = || atoi_simd::parse::<i64>(&a.value).unwrap_or(0);
+                                match a.key.as_ref() {
+                                    key if key == x_key => x = value(),
+                                    key if key == y_key => y = value(),
+                                    _ => (),
+                                }
+                            }
+                            if name == b"ext" {
+                                extent = Some((x, y));
+                            } else {
+                                position = Some((x, y));
+                            }
+                        }
+                        b"from" => in_from = true,
+                        b"to" => in_to = true,
+                        b"row" | b"col" | b"rowOff" | b"colOff" if in_from || in_to => {
+                            let mut text = String::new();
+                            loop {
+                                text_buf.clear();
+                                match xml.read_event_into(&mut text_buf)? {
+                                    Event::Text(t) => text.push_str(&t.unescape()?),
+                                    Event::End(end) if end.name() == e.name() => break,
+                                    Event::Eof => return Err(XlsxError::XmlEof("anchor")),
+                                    _ => (),
+                                }
+                            }
+                            // the end of the element was read above
+                            depth -= 1;
+                            let text = text.trim();
+                            let (cell, offset) = if in_from {
+                                (&mut from, &mut from_offset)
+                            } else {
+                                (&mut to, &mut to_offset)
+                            };
+                            match e.local_name().as_ref() {
+                                b"row" => cell.0 = text.parse().unwrap_or(0),
+                                b"col" => cell.1 = text.parse().unwrap_or(0),
+                                b"rowOff" => offset.0 = text.parse().unwrap_or(0),
+                                _ => offset.1 = text.parse().unwrap_or(0),
+                            }
+                        }
+                        b"pic" => picture = Some((WorksheetPicture::default(), Vec::new())),
+                        b"cNvPr" => {
+                            if let Some((picture, _)) = picture.as_mut() {
+                                for a in e.attributes() {
+                                    let a = a.map_err(XlsxError::XmlAttr)?;
+                                    match a.key.as_ref() {
+                                        b"name" => {
+                                            picture.name = a
+                                                .decode_and_unescape_value(xml.decoder())?
+                                                .into_owned()
+                                        }
+                                        b"descr" => {
+                                            picture.alt_text = Some(
+                                                a.decode_and_unescape_value(xml.decoder())?
+                                                    .into_owned(),
+                                            )
+                                        }
+                                        _ => (),
+                                    }
+                                }
+                            }
+                        }
+                        b"blip" => {
+                            if let Some((_, embed)) = picture.as_mut() {
+                                for a in e.attributes() {
+                                    let a = a.map_err(XlsxError::XmlAttr)?;
+                                    if a.key.local_name().as_ref() == b"embed" {
+                                        *embed = a.value.to_vec();
+                                    }
+                                }
+                            }
+                        }
+                        _ => (),
+                    },
+                    Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                        b"from" => in_from = false,
+                        b"to" => in_to = false,
+                        b"pic" => {
+                            let Some((mut picture, embed)) = picture.take() else {
+                                continue;
+                            };
+                            let Some(path) = targets.get(&embed) else {
+                                continue;
+                            };
+                            picture.anchor_kind = anchor_kind;
+                            picture.from = from;
+                            picture.from_offset = from_offset;
+                            if anchor_kind == AnchorKind::TwoCell {
+                                picture.to = Some(to);
+                                picture.to_offset = to_offset;
+                            }
+                            picture.extent = extent;
+                            picture.position = position;
+                            picture.path = path.clone();
+                            pictures.push(picture);
+                        }
+                        _ => (),
+                    },
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+
+        let mut found = Vec::with_capacity(pictures.len());
+        for mut picture in pictures {
+            match self.zip.by_name(&picture.path) {
+                Ok(file) => {
+                    picture.data = read_part(file, self.options.max_decompressed_bytes)?;
+                }
+                Err(ZipError::FileNotFound) => continue,
+                Err(e) => return Err(XlsxError::Zip(e)),
+            }
+            found.push(picture);
+        }
+        Ok(found)
+    }
+
+    /// Get the charts drawn over a worksheet, in drawing order
+    ///
+    /// Charts are read from the chart parts referenced by the drawing parts
+    /// of the worksheet. Only their metadata is read: the title, the kind of
+    /// chart and the ranges plotted by each series. References to defined
+    /// names or to other workbooks are not resolved and are skipped.
+    pub fn worksheet_charts(&mut self, name: &str) -> Result<Vec<ChartInfo>, XlsxError> {
+        let sheet_path = match self.sheets.iter().find(|(n, _)| n == name) {
+            Some((_, path)) => path.clone(),
+            None => return Err(XlsxError::WorksheetNotFound(name.to_string())),
+        };
+        let drawing_paths = self.relationship_targets(&sheet_path, "drawing")?;
+
+        let mut chart_paths = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        for drawing_path in drawing_paths {
+            let targets = self.part_relationships(&drawing_path)?;
+            let mut xml = match xml_reader(
+                &mut self.zip,
+                &drawing_path,
+                self.options.max_decompressed_bytes,
+            ) {
+                None => continue,
+                Some(x) => x?,
+            };
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"chart" => {
+                        for a in e.attributes() {
+                            let a = a.map_err(XlsxError::XmlAttr)?;
+                            if a.key.local_name().as_ref() == b"id" {
+                                if let Some(path) = targets.get(a.value.as_ref()) {
+                                    chart_paths.push(path.clone());
+                                }
+                            }
+                        }
+                    }
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+
+        let mut charts = Vec::with_capacity(chart_paths.len());
+        for path in chart_paths {
+            let mut xml =
+                match xml_reader(&mut self.zip, &path, self.options.max_decompressed_bytes) {
+                    None => continue,
+                    Some(x) => x?,
+                };
+            let (title, chart_type, series) = read_chart(&mut xml)?;
+            charts.push(ChartInfo {
+                path,
+                title,
+                chart_type,
+                series,
+            });
+        }
+        Ok(charts)
+    }
+
+    /// Get the fields of the rich values held by the cells of a worksheet
+    ///
+    /// Cells linked to a data type, such as stocks or geography, hold a rich
+    /// value: the cell `vm` attribute points to `xl/metadata.xml`, which
+    /// leads to a rich value in `xl/richData/rdrichvalue.xml` whose fields
+    /// are named by its structure, e.g. `Price` or `Ticker symbol`.
+    ///
+    /// Fields are typed by their key type: numbers as [`Data::Float`],
+    /// integers as [`Data::Int`], booleans as [`Data::Bool`], errors as
+    /// [`Data::Error`] and strings as [`Data::String`]. References to
+    /// relationships, property bags and arrays are kept as their
+    /// [`Data::Int`] index. Cells without a rich value are not returned.
+    pub fn worksheet_rich_values(&mut self, name: &str) -> Result<RichValues, XlsxError> {
+        let cells = self.worksheet_value_metadata_cells(name)?;
+        if cells.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let metadata = self.read_value_metadata_rich_values()?;
+        let rich_values = self.read_rich_values()?;
+        let mut values = HashMap::new();
+        for (pos, vm) in cells {
+            let Some(rv) = vm
+                .checked_sub(1)
+                .and_then(|vm| metadata.get(vm).copied().flatten())
+                .and_then(|rv| rich_values.get(rv))
+            else {
+                continue;
+            };
+            let fields = rv
+                .iter()
+                .map(|(key, typ, value)| (key.clone(), rich_value_field(typ, value)))
+                .collect();
+            values.insert(pos, fields);
+        }
+        Ok(values)
+    }
+
+    /// Get the cells of a worksheet with value metadata, with their 1-based
+    /// `vm` index into the value metadata blocks of `xl/metadata.xml`
+    fn worksheet_value_metadata_cells(
+        &mut self,
+        name: &str,
+    ) -> Result<ValueMetadataCells, XlsxError> {
+        let (_, path) = self
+            .sheets
+            .iter()
+            .find(|&(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        let mut xml = xml_reader(&mut self.zip, path, self.options.max_decompressed_bytes)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))??;
+
+        let mut cells = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"c" => {
+                    let vm = get_attribute(e.attributes(), QName(b"vm"))?
+                        .and_then(|vm| atoi_simd::parse::<usize>(vm).ok());
+                    let pos = get_attribute(e.attributes(), QName(b"r"))?;
+                    if let (Some(vm), Some(pos)) = (vm, pos) {
+                        cells.push((get_row_column(pos)?, vm));
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"sheetData" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Read the person list used by threaded comments: id to display name
+    fn read_persons(&mut self) -> Result<BTreeMap<String, String>, XlsxError> {
+        let paths = self
+            .zip
+            .file_names()
+            .filter(|n| n.starts_with("xl/persons/") && n.ends_with(".xml"))
+            .map(|n| n.to_owned())
+            .collect::<Vec<_>>();
+
+        let mut persons = BTreeMap::new();
+        let mut buf = Vec::with_capacity(64);
+        for path in paths {
+            let mut xml =
+                match xml_reader(&mut self.zip, &path, self.options.max_decompressed_bytes) {
+                    None => continue,
+                    Some(x) => x?,
+                };
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"person" => {
+                        let mut id = String::new();
+                        let mut display_name = String::new();
+                        for a in e.attributes() {
+                            let a = a.map_err(XlsxError::XmlAttr)?;
+                            match a.key {
+                                QName(b"id") => {
+                                    id = a.decode_and_unescape_value(xml.decoder())?.into()
+                                }
+                                QName(b"displayName") => {
+                                    display_name =
+                                        a.decode_and_unescape_value(xml.decoder())?.into()
+                                }
+                                _ => (),
+                            }
+                        }
+                        persons.insert(id, display_name);
+                    }
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+        Ok(persons)
+    }
+
+    /// Resolve the image part of each value metadata block (by `vm` - 1)
+    ///
+    /// Blocks that are not rich values, or rich values that are not local
+    /// images, are `None`.
+    fn read_rich_value_image_paths(&mut self) -> Result<Vec<Option<String>>, XlsxError> {
+        // value metadata -> rich value index
+        let rich_values = self.read_value_metadata_rich_values()?;
+        if rich_values.iter().all(Option::is_none) {
+            return Ok(Vec::new());
+        }
+
+        // rich value -> index of the image relationship
+        let relationship_indexes = self
+            .read_rich_values()?
+            .iter()
+            .map(|rv| {
+                rv.iter()
+                    .find(|(key, _, _)| key == "_rvRel:LocalImageIdentifier")
+                    .and_then(|(_, _, value)| value.trim().parse::<usize>().ok())
+            })
+            .collect::<Vec<_>>();
+
+        // image relationship index -> part path
+        let mut buf = Vec::with_capacity(1024);
+        let rel_path = "xl/richData/richValueRel.xml";
+        let targets = self.part_relationships(rel_path)?;
+        let mut paths = Vec::new();
+        if let Some(xml) = xml_reader(&mut self.zip, rel_path, self.options.max_decompressed_bytes)
+        {
+            let mut xml = xml?;
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"rel" => {
+                        let mut path = None;
+                        for a in e.attributes() {
+                            let a = a.map_err(XlsxError::XmlAttr)?;
+                            if a.key.local_name().as_ref() == b"id" {
+                                path = targets.get(&*a.value).cloned();
+                            }
+                        }
+                        paths.push(path);
+                    }
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(rich_values
+            .into_iter()
+            .map(|rv| {
+                let index = relationship_indexes.get(rv?).copied().flatten()?;
+                paths.get(index).cloned().flatten()
+            })
+            .collect())
+    }
+
+    /// Read the rich values of `xl/richData/rdrichvalue.xml`, along with the
+    /// key names and types of their structure
+    fn read_rich_values(&mut self) -> Result<Vec<RichValue>, XlsxError> {
+        // rich value structure -> keys (name, type)
+        let mut structures: Vec<Vec<(String, String)>> = Vec::new();
+        let mut buf = Vec::with_capacity(1024);
+        if let Some(xml) = xml_reader(
+            &mut self.zip,
+            "xl/richData/rdrichvaluestructure.xml",
+            self.options.max_decompressed_bytes,
+        ) {
+            let mut xml = xml?;
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"s" => {
+                        structures.push(Vec::new());
+                    }
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"k" => {
+                        let mut key = (String::new(), String::new());
+                        for a in e.attributes() {
+                            let a = a.map_err(XlsxError::XmlAttr)?;
+                            match a.key.local_name().as_ref() {
+                                b"n" => key.0 = a.decode_and_unescape_value(xml.decoder())?.into(),
+                                b"t" => key.1 = a.decode_and_unescape_value(xml.decoder())?.into(),
+                                _ => (),
+                            }
+                        }
+                        if let Some(s) = structures.last_mut() {
+                            s.push(key);
+                        }
+                    }
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+
+        let mut rich_values = Vec::new();
+        if let Some(xml) = xml_reader(
+            &mut self.zip,
+            "xl/richData/rdrichvalue.xml",
+            self.options.max_decompressed_bytes,
+        ) {
+            let mut xml = xml?;
+            let mut keys: &[(String, String)] = &[];
+            let mut val_buf = Vec::new();
+            loop {
+                buf.clear();
+                match xml.read_event_into(&mut buf) {
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"rv" => {
+                        keys = get_attribute(e.attributes(), QName(b"s"))?
+                            .and_then(|s| atoi_simd::parse::<usize>(s).ok())
+                            .and_then(|s| structures.get(s))
+                            .map_or(&[], Vec::as_slice);
+                        rich_values.push(Vec::new());
+                    }
+                    Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"v" => {
+                        let mut value = String::new();
+                        loop {
+                            val_buf.clear();
+                            match xml.read_event_into(&mut val_buf)? {
+                                Event::Text(t) => value.push_str(&t.unescape()?),
+                                Event::End(end) if end.name() == e.name() => break,
+                                Event::Eof => return Err(XlsxError::XmlEof("v")),
+                                _ => (),
+                            }
+                        }
+                        if let Some(rv) = rich_values.last_mut() {
+                            // values are in the order of the keys of the structure
+                            if let Some((name, typ)) = keys.get(rv.len()) {
+                                rv.push((name.clone(), typ.clone(), value));
+                            }
+                        }
+                    }
+                    Ok(Event::Eof) => break,
+                    Err(e) => return Err(XlsxError::Xml(e)),
+                    _ => (),
+                }
+            }
+        }
+        Ok(rich_values)
+    }
+
+    /// Read the rich value index of each value metadata block of
+    /// `xl/metadata.xml`, `None` for blocks that are not rich values
+    fn read_value_metadata_rich_values(&mut self) -> Result<Vec<Option<usize>>, XlsxError> {
+        let mut xml = match xml_reader(
+            &mut self.zip,
+            "xl/metadata.xml",
+            self.options.max_decompressed_bytes,
+        ) {
+            None => return Ok(Vec::new()),
+            Some(x) => x?,
+        };
+        let mut types = Vec::new();
+        let mut future_rich_values = Vec::new();
+        let mut value_metadata = Vec::new();
+        let mut in_rich_values = false;
+        let mut in_value_metadata = false;
+        let mut buf = Vec::with_capacity(1024);
+        loop {
+            buf.clear();
+            match xml.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                    b"metadataType" => {
+                        let name = get_attribute(e.attributes(), QName(b"name"))?;
+                        types.push(name == Some(b"XLRICHVALUE"));
+                    }
+                    b"futureMetadata" => {
+                        let name = get_attribute(e.attributes(), QName(b"name"))?;
+                        in_rich_values = name == Some(b"XLRICHVALUE");
+                    }
+                    b"valueMetadata" => in_value_metadata = true,
+                    b"bk" if in_rich_values => future_rich_values.push(None),
+                    b"bk" if in_value_metadata => value_metadata.push(None),
+                    b"rvb" if in_rich_values => {
+                        let i = get_attribute(e.attributes(), QName(b"i"))?
+                            .and_then(|i| atoi_simd::parse::<usize>(i).ok());
+                        if let Some(rv) = future_rich_values.last_mut() {
+                            *rv = i;
+                        }
+                    }
+                    b"rc" if in_value_metadata => {
+                        let t = get_attribute(e.attributes(), QName(b"t"))?
+                            .and_then(|t| atoi_simd::parse::<usize>(t).ok());
+                        let v = get_attribute(e.attributes(), QName(b"v"))?
+                            .and_then(|v| atoi_simd::parse::<usize>(v).ok());
+                        // `t` is a 1-based index into the metadata types
+                        let is_rich_value = t
+                            .and_then(|t| t.checked_sub(1))
+                            .and_then(|t| types.get(t).copied())
+                            .unwrap_or(false);
+                        if let (true, Some(v), Some(bk)) =
+                            (is_rich_value, v, value_metadata.last_mut())
+                        {
+                            *bk = Some(v);
+                        }
+                    }
+                    _ => (),
+                },
+                Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                    b"futureMetadata" => in_rich_values = false,
+                    b"valueMetadata" => in_value_metadata = false,
+                    _ => (),
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(XlsxError::Xml(e)),
+                _ => (),
+            }
+        }
+        Ok(value_metadata
+            .into_iter()
+            .map(|v| future_rich_values.get(v?).copied().flatten())
+            .collect())
+    }
+
+    /// Get a cell reader for the worksheet (with comprehensive formatting)
+    pub fn worksheet_cells_reader_ext(
+        &mut self,
+        name: &str,
+    ) -> Result<XlsxCellReader<'_, RS>, XlsxError> {
+        let xml = xml_reader(
+            &mut self.zip,
+            &format!("xl/worksheets/{}.xml", name),
+            self.options.max_decompressed_bytes,
+        )
+        .ok_or_else(|| XlsxError::FileNotFound(format!("xl/worksheets/{}.xml", name)))??;
+        let is_1904 = self.is_1904;
+        let strings = &self.strings;
+        let formats = &self.styles;
+        XlsxCellReader::new(xml, strings, formats, is_1904)
+    }
+
+    /// Get the dimensions of a worksheet without reading its cells
+    ///
+    /// The dimensions are read from the worksheet `<dimension>` element. If it
+    /// is missing (or only covers `A1`) the cells are scanned to find the
+    /// used area instead.
+    pub fn worksheet_dimension(&mut self, name: &str) -> Result<Dimensions, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        let mut dimensions = cell_reader.dimensions();
+        if dimensions == Dimensions::default() {
+            while cell_reader.next_cell()?.is_some() {}
+            dimensions = cell_reader.computed_dimensions().unwrap_or_default();
+        }
+        self.buffers = cell_reader.into_buffers();
+        Ok(dimensions)
+    }
+
+    /// Get the share of non-empty cells in the area of a worksheet
+    ///
+    /// The density is the number of non-empty cells divided by the number of
+    /// cells of the worksheet area, as given by [`Xlsx::worksheet_dimension`],
+    /// computed in a single pass over the cells. A low density suggests
+    /// storing the cells in a sparse structure rather than in a [`Range`]. An
+    /// empty worksheet has a density of `0.0`.
+    pub fn worksheet_density(&mut self, name: &str) -> Result<f64, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        let mut non_empty = 0u64;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if !matches!(cell.val, DataRef::Empty) {
+                non_empty += 1;
+            }
+        }
+        let mut dimensions = cell_reader.dimensions();
+        if dimensions == Dimensions::default() {
+            dimensions = cell_reader.computed_dimensions().unwrap_or_default();
+        }
+        self.buffers = cell_reader.into_buffers();
+        if non_empty == 0 {
+            return Ok(0.);
+        }
+        Ok(non_empty as f64 / dimensions.len() as f64)
+    }
+
+    /// Call `f` for every non-empty cell of a worksheet, in document order
+    ///
+    /// Unlike [`Reader::worksheet_range`] no [`Range`] is allocated. Reading
+    /// stops as soon as `f` returns [`ControlFlow::Break`].
+    pub fn for_each_cell<F>(&mut self, name: &str, mut f: F) -> Result<(), XlsxError>
+    where
+        F: FnMut(Cell<Data>) -> ControlFlow<()>,
+    {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if matches!(cell.val, DataRef::Empty) {
+                continue;
+            }
+            if f(Cell::new(cell.pos, cell.val.into())).is_break() {
+                break;
+            }
+        }
+        self.buffers = cell_reader.into_buffers();
+        Ok(())
+    }
+
+    /// Get a worksheet as text, the way its cells are displayed
+    ///
+    /// Numbers and dates are rendered with their number format code, errors
+    /// as their Excel representation (e.g. `#DIV/0!`) and booleans as `TRUE`
+    /// or `FALSE`. Empty cells are `""`. Month and day names are in English
+    /// and fraction formats fall back to `General`.
+    pub fn worksheet_text(&mut self, name: &str) -> Result<Range<String>, XlsxError> {
+        let cells = self
+            .worksheet_displayed_cells(name)?
+            .into_iter()
+            .map(|(pos, _, text)| Cell::new(pos, text))
+            .collect();
+        Ok(Range::from_sparse(cells))
+    }
+
+    /// Get a worksheet with both the value of each cell and its displayed text
+    ///
+    /// The worksheet is read once: values are as in
+    /// [`Xlsx::worksheet_range_at_index`] and texts as in
+    /// [`Xlsx::worksheet_text`], e.g. a date cell gives its
+    /// [`Data::DateTime`] along with `"2024-01-31"`. Empty cells are
+    /// `(Data::Empty, "")`.
+    pub fn worksheet_range_raw_and_formatted(
+        &mut self,
+        name: &str,
+    ) -> Result<Range<(Data, String)>, XlsxError> {
+        let cells = self
+            .worksheet_displayed_cells(name)?
+            .into_iter()
+            .map(|(pos, value, text)| Cell::new(pos, (value, text)))
+            .collect();
+        Ok(Range::from_sparse(cells))
+    }
+
+    /// Read the non-empty cells of a worksheet with their displayed text
+    fn worksheet_displayed_cells(&mut self, name: &str) -> Result<DisplayedCells, XlsxError> {
+        let mut cells = Vec::new();
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        while let Some((cell, _)) = cell_reader.next_cell_with_formatting()? {
+            if !matches!(cell.val, DataRef::Empty) {
+                let style = cell_reader.last_cell_style_index();
+                cells.push((cell.pos, Data::from(cell.val), style));
+            }
+        }
+        self.buffers = cell_reader.into_buffers();
+
+        let is_1904 = self.is_1904;
+        Ok(cells
+            .into_iter()
+            .map(|(pos, value, style)| {
+                let code = style
+                    .and_then(|s| self.cell_xfs.get(s))
+                    .and_then(|xf| xf.number_format_code.as_deref())
+                    .unwrap_or("General");
+                let text = format_value(&value, code, is_1904);
+                (pos, value, text)
+            })
+            .collect())
+    }
+
+    /// Find the cells containing some text, in all the worksheets
+    ///
+    /// Worksheets are streamed in order without building any [`Range`].
+    /// String cells are matched on their value and the other cells on their
+    /// displayed text, as in [`Xlsx::worksheet_text`]. Returns the
+    /// `(sheet, row, col)` of each hit, stopping after `limit` hits if any.
+    pub fn find_text(
+        &mut self,
+        needle: &str,
+        case_sensitive: bool,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, u32, u32)>, XlsxError> {
+        let needle = if case_sensitive {
+            needle.to_string()
+        } else {
+            needle.to_lowercase()
+        };
+        let names = self
+            .sheets
+            .iter()
+            .map(|(n, _)| n.clone())
+            .collect::<Vec<_>>();
+        let cell_xfs = self.cell_xfs.clone();
+        let is_1904 = self.is_1904;
+        let mut hits = Vec::new();
+        for name in names {
+            if limit.is_some_and(|limit| hits.len() >= limit) {
+                break;
+            }
+            let mut cell_reader = match self.worksheet_cells_reader(&name) {
+                Ok(reader) => reader,
+                Err(XlsxError::NotAWorksheet(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            while let Some(cell) = cell_reader.next_cell()? {
+                let text = match cell.val {
+                    DataRef::Empty => continue,
+                    DataRef::String(s) => s,
+                    DataRef::SharedString(s) => s.to_string(),
+                    value => {
+                        let code = cell_reader
+                            .last_cell_style_index()
+                            .and_then(|s| cell_xfs.get(s))
+                            .and_then(|xf| xf.number_format_code.as_deref())
+                            .unwrap_or("General");
+                        format_value(&Data::from(value), code, is_1904)
+                    }
+                };
+                let found = if case_sensitive {
+                    text.contains(&needle)
+                } else {
+                    text.to_lowercase().contains(&needle)
+                };
+                if found {
+                    hits.push((name.clone(), cell.pos.0, cell.pos.1));
+                    if limit.is_some_and(|limit| hits.len() >= limit) {
+                        break;
+                    }
+                }
+            }
+            self.buffers = cell_reader.into_buffers();
+        }
+        Ok(hits)
+    }
+
+    /// Count the types of the cell values of each column of a worksheet
+    ///
+    /// The worksheet is read in a single streaming pass, without building a
+    /// [`Range`]. Cells with a date or time number format count as dates.
+    /// Returns one [`ColumnProfile`] per column, from the first to the last
+    /// non-empty column, or an empty vector for an empty worksheet.
+    pub fn worksheet_profile(&mut self, name: &str) -> Result<Vec<ColumnProfile>, XlsxError> {
+        let mut columns: BTreeMap<u32, ColumnProfile> = BTreeMap::new();
+        let mut rows: Option<(u32, u32)> = None;
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        while let Some(cell) = cell_reader.next_cell()? {
+            if matches!(cell.val, DataRef::Empty) {
+                continue;
+            }
+            let (row, col) = cell.pos;
+            rows = Some(rows.map_or((row, row), |(first, last)| (first.min(row), last.max(row))));
+            columns.entry(col).or_default().add(&cell.val);
+        }
+        self.buffers = cell_reader.into_buffers();
+
+        let (Some((first_row, last_row)), Some(first_col), Some(last_col)) = (
+            rows,
+            columns.keys().next().copied(),
+            columns.keys().next_back().copied(),
+        ) else {
+            return Ok(Vec::new());
+        };
+        let height = (last_row - first_row + 1) as usize;
+        Ok((first_col..=last_col)
+            .map(|column| {
+                let mut profile = columns.remove(&column).unwrap_or_default();
+                profile.column = column;
+                // a malformed sheet may repeat a cell, counted twice
+                profile.empty = height.saturating_sub(profile.total());
+                profile
+            })
+            .collect())
+    }
+
+    /// Get the formulas of a worksheet along with the type of their cached result
+    ///
+    /// The result type comes from the `t` attribute of each formula cell,
+    /// which tells whether the formula yields a number, text, a boolean or an
+    /// error without evaluating it.
+    pub fn worksheet_formula_typed(
+        &mut self,
+        name: &str,
+    ) -> Result<Range<(String, CellResultType)>, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        let mut cells = Vec::new();
+        while let Some(cell) = cell_reader.next_formula()? {
+            if !cell.val.is_empty() {
+                let result_type = cell_reader.last_formula_result_type();
+                cells.push(Cell::new(cell.pos, (cell.val, result_type)));
+            }
+        }
+        self.buffers = cell_reader.into_buffers();
+        Ok(Range::from_sparse(cells))
+    }
+
+    /// Get the formulas of a worksheet in R1C1 notation
+    ///
+    /// Relative references are written as offsets from the formula cell
+    /// (`A1` in `C3` becomes `R[-2]C[-2]`) and absolute ones as 1-based
+    /// indexes (`$B$2` becomes `R2C2`). Since shared formulas are all the
+    /// same in this notation, it makes them easy to compare.
+    pub fn worksheet_formula_r1c1(&mut self, name: &str) -> Result<Range<String>, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        let mut cells = Vec::new();
+        while let Some(cell) = cell_reader.next_formula()? {
+            if !cell.val.is_empty() {
+                let pos = cell.pos;
+                let formula = map_cell_names(&cell.val, |name| r1c1_cell_name(name, pos))?;
+                cells.push(Cell::new(pos, formula));
+            }
+        }
+        self.buffers = cell_reader.into_buffers();
+        Ok(Range::from_sparse(cells))
+    }
+
+    /// Get column widths for a worksheet
+    pub fn worksheet_column_widths(&mut self, name: &str) -> Result<ColumnWidths, XlsxError> {
+        let cell_reader = self.worksheet_cells_reader(name)?;
+        Ok(cell_reader.column_widths().clone())
+    }
+
+    /// Get the layout of a worksheet: column and row definitions, sheet
+    /// format defaults, merged cells and pane
+    ///
+    /// The worksheet is read once for all of them, without keeping its cells.
+    pub fn worksheet_layout(&mut self, name: &str) -> Result<SheetLayout, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        while cell_reader.next_cell()?.is_some() {}
+        let merged_cells = cell_reader.merge_cells()?;
+        let layout = SheetLayout {
+            column_widths: cell_reader.column_widths().clone(),
+            row_definitions: cell_reader.row_definitions().clone(),
+            merged_cells,
+            pane: cell_reader.pane().cloned(),
+        };
+        self.buffers = cell_reader.into_buffers();
+        Ok(layout)
+    }
+
+    /// Get the display settings of a worksheet: gridlines, headings, zoom,
+    /// view type and pane
+    ///
+    /// Settings are read from the first `<sheetView>` of the worksheet, the
+    /// cells are not read.
+    pub fn worksheet_view(&mut self, name: &str) -> Result<SheetView, XlsxError> {
+        let cell_reader = self.worksheet_cells_reader(name)?;
+        let view = cell_reader.view().clone();
+        self.buffers = cell_reader.into_buffers();
+        Ok(view)
+    }
+
+    /// Get row definitions for a worksheet
+    pub fn worksheet_row_definitions(&mut self, name: &str) -> Result<RowDefinitions, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        // TODO - cleanup
+        while let Some((_cell, _)) = cell_reader.next_cell_with_formatting()? {
+            continue;
+        }
+        Ok(cell_reader.row_definitions().clone())
+    }
+
+    /// Get the effective style of a worksheet cell
+    ///
+    /// A cell without a style of its own, or without any `<c>` element,
+    /// inherits the style of its row (`<row s="..">`), else the style of its
+    /// column (`<col style="..">`), else the default cell style.
+    pub fn effective_cell_style(
+        &mut self,
+        name: &str,
+        pos: (u32, u32),
+    ) -> Result<CellStyle, XlsxError> {
+        let index = self.effective_style_index(name, pos)?;
+        Ok(self.styles.get(index).cloned().unwrap_or_default())
+    }
+
+    /// Get the named style applied to a worksheet cell, e.g. "Heading 1"
+    ///
+    /// The cell format is resolved as in [`Xlsx::effective_cell_style`].
+    /// Returns `None` if the format does not point to a named style.
+    pub fn cell_named_style(
+        &mut self,
+        name: &str,
+        pos: (u32, u32),
+    ) -> Result<Option<&NamedStyle>, XlsxError> {
+        let index = self.effective_style_index(name, pos)?;
+        let Some(xf) = self.cell_xfs.get(index) else {
+            return Ok(None);
+        };
+        Ok(self.named_styles.iter().find(|s| s.xf_id == xf.xf_id))
+    }
+
+    /// Get which parts of the format of a worksheet cell are applied
+    ///
+    /// The cell format is resolved as in [`Xlsx::effective_cell_style`]. A
+    /// font, fill, border or number format referenced by the format but not
+    /// applied is inherited from its named style (see
+    /// [`Xlsx::cell_named_style`]).
+    pub fn cell_apply_flags(
+        &mut self,
+        name: &str,
+        pos: (u32, u32),
+    ) -> Result<ApplyFlags, XlsxError> {
+        let index = self.effective_style_index(name, pos)?;
+        Ok(self
+            .cell_xfs
+            .get(index)
+            .map(|xf| xf.apply)
+            .unwrap_or_default())
+    }
+
+    /// Index of the cell format applied to a worksheet cell, falling back to
+    /// the row and column formats
+    fn effective_style_index(&mut self, name: &str, pos: (u32, u32)) -> Result<usize, XlsxError> {
+        let mut cell_reader = self.worksheet_cells_reader(name)?;
+        let mut cell_style = None;
+        while let Some((cell, _)) = cell_reader.next_cell_with_formatting()? {
+            if cell.pos == pos {
+                cell_style = cell_reader.last_cell_style_index();
+            }
+            if cell.pos >= pos {
+                break;
+            }
+        }
+        // the style of a row only applies with `customFormat="1"`
+        let row_style = || {
+            cell_reader
+                .row_definitions()
+                .find_definition_for_row(pos.0)
+                .filter(|def| def.custom_format == Some(true))
+                .and_then(|def| def.style)
+        };
+        let column_style = || {
+            cell_reader
+                .column_widths()
+                .find_definitions_for_column(pos.1 + 1)
+                .last()
+                .and_then(|def| def.style)
+        };
+        let index = cell_style
+            .or_else(|| row_style().or_else(column_style).map(|s| s as usize))
+            .unwrap_or(0);
+        self.buffers = cell_reader.into_buffers();
+        Ok(index)
+    }
+}
+
+/// Name, sheet, columns, data dimensions and style of a table
+#[derive(Clone)]
+struct TableMetadata {
+    name: String,
+    sheet_name: String,
+    columns: Vec<String>,
+    dimensions: Dimensions,
+    style: Option<TableStyleInfo>,
+}
+
+struct InnerTableMetadata {
+    display_name: String,
+    ref_cells: String,
+    header_row_count: u32,
+    insert_row: bool,
+    totals_row_count: u32,
+}
+
+impl InnerTableMetadata {
+    fn new() -> Self {
+        Self {
+            display_name: String::new(),
+            ref_cells: String::new(),
+            header_row_count: 1,
+            insert_row: false,
+            totals_row_count: 0,
+        }
+    }
+}
+
+impl<RS: Read + Seek> Xlsx<RS> {
+    /// Get a reader over all used cells in the given worksheet cell reader
+    pub fn worksheet_cells_reader<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Result<XlsxCellReader<'a, RS>, XlsxError> {
+        let index = self
+            .sheets
+            .iter()
+            .position(|(n, _)| n == name)
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.into()))?;
+        self.cells_reader_at(index)
+    }
+
+    /// Get an iterator over the rows and cells of a worksheet as events
+    ///
+    /// Each row with values yields [`SheetEvent::RowStart`], its non-empty
+    /// cells as [`SheetEvent::Cell`], then [`SheetEvent::RowEnd`], so records
+    /// can be emitted as soon as a row is complete. The last event is
+    /// [`SheetEvent::End`]. Rows without any value yield no events.
+    pub fn worksheet_events<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Result<SheetEvents<'a, RS>, XlsxError> {
+        self.worksheet_cells_reader(name).map(SheetEvents::new)
+    }
+
+    /// Get a cell reader for the sheet at `index` in workbook order
+    fn cells_reader_at(&mut self, index: usize) -> Result<XlsxCellReader<'_, RS>, XlsxError> {
+        let (name, path) = &self.sheets[index];
+        let max_bytes = self.options.max_decompressed_bytes;
+        let xml = limited_xml_reader(&mut self.zip, path, max_bytes, self.buffers.read_buf_mut())
+            .ok_or_else(|| XlsxError::WorksheetNotFound(name.clone()))??;
         let is_1904 = self.is_1904;
         let strings = &self.strings;
         let formats = &self.styles;
-        XlsxCellReader::new(xml, strings, formats, is_1904)
+        let buffers = &mut self.buffers;
+        let mut reader = XlsxCellReader::with_buffers(xml, strings, formats, is_1904, buffers)?;
+        reader.set_limits(self.options.max_rows, self.options.max_cols);
+        reader.set_error_policy(self.options.error_policy);
+        if self.options.text_format_as_string {
+            let text_styles = self
+                .cell_xfs
+                .iter()
+                .map(|xf| xf.number_format_code.as_deref() == Some("@"))
+                .collect();
+            reader.set_text_styles(text_styles);
+        }
+        Ok(reader)
     }
-}
 
-impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
-    type Error = XlsxError;
+    /// Sets how error cells, such as `#N/A` or `#DIV/0!`, are read
+    ///
+    /// By default errors are kept as [`Data::Error`]. They can instead be read
+    /// as empty cells, to treat them as missing values, or as their Excel text.
+    pub fn with_errors_as(&mut self, error_policy: ErrorPolicy) -> &mut Self {
+        self.options.error_policy = error_policy;
+        self
+    }
+
+    /// Sets whether cells formatted as text (`@`) are always read as strings
+    ///
+    /// Numbers typed in a text formatted column, e.g. ZIP codes, are stored
+    /// as numbers. When enabled they are read as [`Data::String`] of their
+    /// displayed value instead, like the strings of the same column.
+    pub fn with_text_format_as_string(&mut self, enabled: bool) -> &mut Self {
+        self.options.text_format_as_string = enabled;
+        self
+    }
+
+    /// Sets the maximum number of rows of the worksheets to read
+    ///
+    /// Reading a cell beyond this number of rows fails with
+    /// [`XlsxError::LimitExceeded`], protecting against sheets declaring
+    /// huge ranges when reading untrusted files.
+    pub fn with_max_rows(&mut self, max_rows: u32) -> &mut Self {
+        self.options.max_rows = Some(max_rows);
+        self
+    }
 
-    fn new(mut reader: RS) -> Result<Self, XlsxError> {
+    /// Sets the maximum number of columns of the worksheets to read
+    ///
+    /// Reading a cell beyond this number of columns fails with
+    /// [`XlsxError::LimitExceeded`].
+    pub fn with_max_cols(&mut self, max_cols: u32) -> &mut Self {
+        self.options.max_cols = Some(max_cols);
+        self
+    }
+
+    /// Replaces the scratch buffers used when reading worksheets
+    ///
+    /// Buffers taken from a previous workbook with [`Xlsx::into_buffers`] keep
+    /// their allocated capacity, avoiding reallocations when reading many
+    /// files in a row.
+    pub fn with_buffers(&mut self, buffers: ReaderBuffers) -> &mut Self {
+        self.buffers = buffers;
+        self
+    }
+
+    /// Consumes the reader and returns its scratch buffers for reuse
+    pub fn into_buffers(self) -> ReaderBuffers {
+        self.buffers
+    }
+
+    /// Creates a new instance using `Options` to inform parsing.
+    ///
+    /// The limits of `options` apply from the start, to the parts read when
+    /// opening the file as well as to the worksheets.
+    ///
+    /// ```
+    /// use calamine::{Xlsx, XlsxOptions};
+    /// # use std::io::Cursor;
+    /// # fn run() -> Result<Xlsx<Cursor<Vec<u8>>>, calamine::XlsxError> {
+    /// # let reader = Cursor::new(Vec::new());
+    /// let mut options = XlsxOptions::default();
+    /// options.max_decompressed_bytes = Some(100 << 20);
+    /// let workbook = Xlsx::new_with_options(reader, options)?;
+    /// # Ok(workbook) }
+    /// # fn main() { assert!(run().is_err()); }
+    /// ```
+    pub fn new_with_options(mut reader: RS, options: XlsxOptions) -> Result<Self, XlsxError> {
         check_for_password_protected(&mut reader)?;
 
         let mut xlsx = Xlsx {
@@ -2827,18 +5253,30 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
             strings: Vec::new(),
             formats: Vec::new(),
             styles: Vec::new(),
+            cell_xfs: Vec::new(),
+            named_styles: Vec::new(),
+            table_styles: Vec::new(),
+            default_table_style: None,
+            default_pivot_style: None,
+            default_font: Font::default(),
             format_interner: FormatStringInterner::new(),
             is_1904: false,
+            workbook_protection: None,
+            custom_view_names: HashMap::new(),
+            defined_names: Vec::new(),
             sheets: Vec::new(),
             tables: None,
             metadata: Metadata::default(),
             #[cfg(feature = "picture")]
             pictures: None,
             merged_regions: None,
-            options: XlsxOptions::default(),
+            options,
             dxf_formats: Vec::new(),
             conditional_formats: BTreeMap::new(),
             theme: None,
+            indexed_colors: Vec::new(),
+            mru_colors: Vec::new(),
+            buffers: ReaderBuffers::default(),
         };
         xlsx.read_shared_strings()?;
         xlsx.read_styles()?;
@@ -2850,6 +5288,14 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
 
         Ok(xlsx)
     }
+}
+
+impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
+    type Error = XlsxError;
+
+    fn new(reader: RS) -> Result<Self, XlsxError> {
+        Self::new_with_options(reader, XlsxOptions::default())
+    }
 
     fn with_header_row(&mut self, header_row: HeaderRow) -> &mut Self {
         self.options.header_row = header_row;
@@ -2857,8 +5303,12 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
     }
 
     fn vba_project(&mut self) -> Option<Result<Cow<'_, VbaProject>, XlsxError>> {
-        let mut f = self.zip.by_name("xl/vbaProject.bin").ok()?;
+        let f = self.zip.by_name("xl/vbaProject.bin").ok()?;
         let len = f.size() as usize;
+        let mut f = match limited_part(f, self.options.max_decompressed_bytes) {
+            Ok(f) => f,
+            Err(e) => return Some(Err(e)),
+        };
         Some(
             VbaProject::new(&mut f, len)
                 .map(Cow::Owned)
@@ -2886,58 +5336,21 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
             cells.reserve(len as usize);
         }
 
-        match header_row {
-            HeaderRow::FirstNonEmptyRow => {
-                // the header row is the row of the first non-empty cell
-                while let Some((cell, formatting)) = cell_reader.next_cell_with_formatting()? {
-                    if matches!(cell.val, DataRef::Empty) {
-                        continue;
-                    }
-                    let data_with_formatting =
-                        DataWithFormatting::new(cell.val.into(), formatting.cloned());
-                    let mut data_with_formatting = data_with_formatting;
-                    if !cell_reader.last_cell_had_formula() && cell_reader.is_in_spill(cell.pos) {
-                        data_with_formatting.is_spilled = true;
-                    }
-                    cells.push(Cell::new(cell.pos, data_with_formatting));
-                }
+        // If `header_row` is a row index, we only add non-empty cells after this index.
+        while let Some((cell, formatting)) = cell_reader.next_cell_with_formatting()? {
+            if matches!(cell.val, DataRef::Empty) || !header_row.includes(cell.pos.0) {
+                continue;
             }
-            HeaderRow::Row(header_row_idx) => {
-                // If `header_row` is a row index, we only add non-empty cells after this index.
-                while let Some((cell, formatting)) = cell_reader.next_cell_with_formatting()? {
-                    if matches!(cell.val, DataRef::Empty) {
-                        continue;
-                    }
-                    if cell.pos.0 >= header_row_idx {
-                        let data_with_formatting =
-                            DataWithFormatting::new(cell.val.into(), formatting.cloned());
-                        let mut data_with_formatting = data_with_formatting;
-                        if !cell_reader.last_cell_had_formula() && cell_reader.is_in_spill(cell.pos)
-                        {
-                            data_with_formatting.is_spilled = true;
-                        }
-                        cells.push(Cell::new(cell.pos, data_with_formatting));
-                    }
-                }
-
-                // If `header_row` is set and the first non-empty cell is not at the `header_row`, we add
-                // an empty cell at the beginning with row `header_row` and same column as the first non-empty cell.
-                if cells.first().is_some_and(|c| c.pos.0 != header_row_idx) {
-                    cells.insert(
-                        0,
-                        Cell {
-                            pos: (
-                                header_row_idx,
-                                cells.first().expect("cells should not be empty").pos.1,
-                            ),
-                            val: DataWithFormatting::default(),
-                        },
-                    );
-                }
+            let mut data_with_formatting =
+                DataWithFormatting::new(cell.val.into(), formatting.cloned());
+            if !cell_reader.last_cell_had_formula() && cell_reader.is_in_spill(cell.pos) {
+                data_with_formatting.is_spilled = true;
             }
+            cells.push(Cell::new(cell.pos, data_with_formatting));
         }
+        self.buffers = cell_reader.into_buffers();
 
-        Ok(Range::from_sparse(cells))
+        Ok(range_with_header_row(header_row, cells))
     }
 
     fn worksheet_formula(&mut self, name: &str) -> Result<Range<DataWithFormatting>, XlsxError> {
@@ -2961,6 +5374,7 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
                 cells.push(Cell::new(cell.pos, data_with_formatting));
             }
         }
+        self.buffers = cell_reader.into_buffers();
         Ok(Range::from_sparse(cells))
     }
 
@@ -3017,12 +5431,7 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
             Err(e) => return Err(e),
         };
 
-        let dimensions = cell_reader.dimensions();
-        if dimensions.start == (0, 0) && dimensions.end == (0, 0) {
-            return Ok(Range::empty());
-        }
-
-        let len = dimensions.len();
+        let len = cell_reader.dimensions().len();
         let mut cells = Vec::new();
         if len < 100_000 {
             cells.reserve(len as usize);
@@ -3032,6 +5441,7 @@ impl<RS: Read + Seek> Reader<RS> for Xlsx<RS> {
             let style = formatting.cloned().unwrap_or_default();
             cells.push(Cell::new(cell.pos, style));
         }
+        self.buffers = cell_reader.into_buffers();
 
         Ok(Range::from_sparse(cells))
     }
@@ -3111,6 +5521,113 @@ impl<RS: Read + Seek> ReaderRef<RS> for Xlsx<RS> {
 fn xml_reader<'a, RS: Read + Seek>(
     zip: &'a mut ZipArchive<RS>,
     path: &str,
+    max_bytes: Option<u64>,
+) -> Option<Result<XlReader<'a, RS>, XlsxError>> {
+    limited_xml_reader(zip, path, max_bytes, &mut Vec::new())
+}
+
+/// Error of a [`LimitedReader`] reading more than its limit
+#[derive(Debug)]
+struct LimitExceeded(u64);
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "more than {} decompressed bytes", self.0)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// A reader failing once more than `max` bytes have been read, if any
+pub(crate) struct LimitedReader<R> {
+    inner: R,
+    max: Option<u64>,
+    read: u64,
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n as u64;
+        match self.max {
+            Some(max) if self.read > max => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                LimitExceeded(max),
+            )),
+            _ => Ok(n),
+        }
+    }
+}
+
+/// Size of the buffer decompressed parts are read through
+const PART_BUFFER_SIZE: usize = 8 * 1024;
+
+/// A buffered reader of a decompressed part, whose buffer can be reused
+///
+/// Unlike [`std::io::BufReader`], the buffer is given by the caller and can be
+/// taken back with [`PartReader::into_buf`] to read the next part, sparing an
+/// allocation per part.
+pub(crate) struct PartReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+    filled: usize,
+}
+
+impl<R> PartReader<R> {
+    fn with_buf(inner: R, mut buf: Vec<u8>) -> Self {
+        if buf.len() < PART_BUFFER_SIZE {
+            buf.resize(PART_BUFFER_SIZE, 0);
+        }
+        PartReader {
+            inner,
+            buf,
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Takes the buffer of the reader, which must not be read afterwards
+    pub(crate) fn take_buf(&mut self) -> Vec<u8> {
+        self.pos = 0;
+        self.filled = 0;
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl<R: Read> Read for PartReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: Read> BufRead for PartReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.pos >= self.filled {
+            self.filled = self.inner.read(&mut self.buf)?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+    }
+}
+
+/// Gets an xml reader of a part, limiting its decompressed size to `max_bytes`
+///
+/// The part is read through the buffer taken from `read_buf`, which is
+/// grown if needed.
+fn limited_xml_reader<'a, RS: Read + Seek>(
+    zip: &'a mut ZipArchive<RS>,
+    path: &str,
+    max_bytes: Option<u64>,
+    read_buf: &mut Vec<u8>,
 ) -> Option<Result<XlReader<'a, RS>, XlsxError>> {
     let actual_path = zip
         .file_names()
@@ -3118,7 +5635,14 @@ fn xml_reader<'a, RS: Read + Seek>(
         .to_owned();
     match zip.by_name(&actual_path) {
         Ok(f) => {
-            let mut r = XmlReader::from_reader(BufReader::new(f));
+            let f = match limited_part(f, max_bytes) {
+                Ok(f) => f,
+                Err(e) => return Some(Err(e)),
+            };
+            // With the `encoding` feature, quick_xml skips a leading BOM and
+            // decodes text with the encoding given by the BOM or by the
+            // `<?xml encoding="..."?>` declaration
+            let mut r = XmlReader::from_reader(PartReader::with_buf(f, std::mem::take(read_buf)));
             let config = r.config_mut();
             config.check_end_names = false;
             config.trim_text(false);
@@ -3131,6 +5655,149 @@ fn xml_reader<'a, RS: Read + Seek>(
     }
 }
 
+/// Wrap a part of the archive in a [`LimitedReader`], failing early if its
+/// declared size is already over `max_bytes`
+fn limited_part<R: Read>(
+    f: ZipFile<'_, R>,
+    max_bytes: Option<u64>,
+) -> Result<LimitedReader<ZipFile<'_, R>>, XlsxError> {
+    if let Some(max) = max_bytes.filter(|&max| f.size() > max) {
+        return Err(XlsxError::LimitExceeded {
+            limit: "max_decompressed_bytes",
+            max,
+        });
+    }
+    Ok(LimitedReader {
+        inner: f,
+        max: max_bytes,
+        read: 0,
+    })
+}
+
+/// Read a whole part of the archive, e.g. an image, failing with
+/// [`XlsxError::LimitExceeded`] if it is larger than `max_bytes`
+fn read_part<R: Read>(f: ZipFile<'_, R>, max_bytes: Option<u64>) -> Result<Vec<u8>, XlsxError> {
+    let mut data = Vec::new();
+    limited_part(f, max_bytes)?.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Check the `Type` of a relationship, in either the transitional or the
+/// strict (ISO/IEC 29500) OOXML namespace
+fn is_relationship_type(value: &[u8], typ: &str) -> bool {
+    const TRANSITIONAL: &[u8] =
+        b"http://schemas.openxmlformats.org/officeDocument/2006/relationships/";
+    const STRICT: &[u8] = b"http://purl.oclc.org/ooxml/officeDocument/relationships/";
+    [TRANSITIONAL, STRICT].iter().any(|ns| {
+        value.len() == ns.len() + typ.len()
+            && value.starts_with(ns)
+            && value.ends_with(typ.as_bytes())
+    })
+}
+
+/// Split a union of references (`Sheet1!A1:B2,Sheet1!D4`) on the commas
+/// that are not inside a quoted sheet name
+fn split_reference_union(formula: &str) -> Vec<&str> {
+    let mut references = Vec::new();
+    let mut start = 0;
+    let mut in_quote = false;
+    for (i, c) in formula.char_indices() {
+        match c {
+            '\'' => in_quote = !in_quote,
+            ',' if !in_quote => {
+                references.push(formula[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    references.push(formula[start..].trim());
+    references
+}
+
+/// Parse a formula made of a single literal: a number, a string, a boolean
+/// or an error
+fn parse_literal(formula: &str) -> Option<Data> {
+    let formula = formula.trim();
+    let formula = formula.strip_prefix('=').unwrap_or(formula);
+    if let Some(text) = formula.strip_prefix('"').and_then(|f| f.strip_suffix('"')) {
+        // quotes within the string are doubled
+        return (!text.replace("\"\"", "").contains('"'))
+            .then(|| Data::String(text.replace("\"\"", "\"")));
+    }
+    if formula.eq_ignore_ascii_case("TRUE") {
+        return Some(Data::Bool(true));
+    }
+    if formula.eq_ignore_ascii_case("FALSE") {
+        return Some(Data::Bool(false));
+    }
+    if formula.starts_with('#') {
+        return CellErrorType::from_str(formula).ok().map(Data::Error);
+    }
+    if !formula
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'+' | b'-' | b'e' | b'E'))
+    {
+        return None;
+    }
+    formula
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .map(Data::Float)
+}
+
+/// Remove the quotes around a sheet name in a reference (`'My Sheet'`)
+fn unquote_sheet_name(name: &str) -> String {
+    match name.strip_prefix('\'').and_then(|n| n.strip_suffix('\'')) {
+        Some(quoted) => quoted.replace("''", "'"),
+        None => name.to_string(),
+    }
+}
+
+/// Get the path of a relationship target, relative to the folder of the
+/// source part
+///
+/// Targets starting with `/` are relative to the root of the archive. `.`
+/// segments are dropped and each `..` segment goes up one folder.
+fn resolve_relationship_target(base_folder: &str, target: &str) -> String {
+    let (mut segments, target) = match target.strip_prefix('/') {
+        Some(target) => (Vec::new(), target),
+        None => (
+            base_folder.split('/').filter(|s| !s.is_empty()).collect(),
+            target,
+        ),
+    };
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Convert a rich value field to `Data` according to its key type `t`
+fn rich_value_field(typ: &str, value: &str) -> Data {
+    match typ {
+        // numbers are the default type
+        "" | "d" => value
+            .parse()
+            .map_or_else(|_| Data::String(value.to_string()), Data::Float),
+        "i" | "r" | "spb" | "a" => value
+            .parse()
+            .map_or_else(|_| Data::String(value.to_string()), Data::Int),
+        "b" => Data::Bool(matches!(value, "1" | "true")),
+        "e" => value
+            .parse()
+            .map_or_else(|_| Data::String(value.to_string()), Data::Error),
+        _ => Data::String(value.to_string()),
+    }
+}
+
 /// search through an Element's attributes for the named one
 pub(crate) fn get_attribute<'a>(
     atts: Attributes<'a>,
@@ -3149,6 +5816,34 @@ pub(crate) fn get_attribute<'a>(
     Ok(None)
 }
 
+/// Build a range from the non-empty cells of a worksheet read with the
+/// `header_row` option
+///
+/// If `header_row` is set and the first non-empty cell is not at the
+/// `header_row`, we add an empty cell at the beginning with row `header_row`
+/// and same column as the first non-empty cell.
+fn range_with_header_row<T: CellType>(header_row: HeaderRow, mut cells: Vec<Cell<T>>) -> Range<T> {
+    if let HeaderRow::Row(header_row_idx) = header_row {
+        if let Some(col) = cells.first().map(|c| c.pos.1) {
+            if cells[0].pos.0 != header_row_idx {
+                cells.insert(0, Cell::new((header_row_idx, col), T::default()));
+            }
+        }
+    }
+    Range::from_sparse(cells)
+}
+
+/// Extend the dimensions to include a cell position
+pub(crate) fn extend_dimensions(dimensions: &mut Option<Dimensions>, (row, col): (u32, u32)) {
+    *dimensions = Some(match *dimensions {
+        None => Dimensions::new((row, col), (row, col)),
+        Some(d) => Dimensions::new(
+            (d.start.0.min(row), d.start.1.min(col)),
+            (d.end.0.max(row), d.end.1.max(col)),
+        ),
+    });
+}
+
 /// converts a text representation (e.g. "A6:G67") of a dimension into integers
 /// - top left (row, column),
 /// - bottom right (row, column)
@@ -3165,23 +5860,92 @@ pub(crate) fn get_dimension(dimension: &[u8]) -> Result<Dimensions, XlsxError> {
             end: parts[0],
         }),
         2 => {
-            let rows = parts[1].0 - parts[0].0;
-            let columns = parts[1].1 - parts[0].1;
+            // Corners may be given in any order, e.g. `B2:A1`
+            let start = (parts[0].0.min(parts[1].0), parts[0].1.min(parts[1].1));
+            let end = (parts[0].0.max(parts[1].0), parts[0].1.max(parts[1].1));
+            let rows = end.0 - start.0;
+            let columns = end.1 - start.1;
             if rows > MAX_ROWS {
                 warn!("xlsx has more than maximum number of rows ({rows} > {MAX_ROWS})");
             }
             if columns > MAX_COLUMNS {
                 warn!("xlsx has more than maximum number of columns ({columns} > {MAX_COLUMNS})");
             }
-            Ok(Dimensions {
-                start: parts[0],
-                end: parts[1],
-            })
+            Ok(Dimensions { start, end })
         }
         len => Err(XlsxError::DimensionCount(len)),
     }
 }
 
+/// Parse a space separated list of references (e.g. `"A1:B2 D4"`) into their
+/// dimensions
+///
+/// This is the format of `sqref` attributes, used for instance by conditional
+/// formatting ranges. Single cells give one cell dimensions, whole columns
+/// (`A:B`) and whole rows (`1:2`) span all the rows or columns of a sheet,
+/// corners may be given in any order and absolute markers (`$`) are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use calamine::{parse_sqref, Dimensions};
+///
+/// let dimensions = parse_sqref("A1:B2 $D$4 C:C").unwrap();
+/// assert_eq!(
+///     dimensions,
+///     vec![
+///         Dimensions::new((0, 0), (1, 1)),
+///         Dimensions::new((3, 3), (3, 3)),
+///         Dimensions::new((0, 2), (1_048_575, 2)),
+///     ]
+/// );
+/// ```
+pub fn parse_sqref(sqref: &str) -> Result<Vec<Dimensions>, XlsxError> {
+    sqref.split_whitespace().map(parse_sqref_area).collect()
+}
+
+/// Parse one area of a `sqref` attribute, e.g. `A1:B2`, `A:A` or `$1:$1`
+fn parse_sqref_area(reference: &str) -> Result<Dimensions, XlsxError> {
+    let reference = reference.replace('$', "");
+    let corners = reference
+        .split(':')
+        .map(|corner| get_optional_row_column(corner.as_bytes()))
+        .collect::<Result<Vec<_>, XlsxError>>()?;
+    let (first, last) = match corners[..] {
+        [corner] => (corner, corner),
+        [first, last] => (first, last),
+        _ => return Err(XlsxError::DimensionCount(corners.len())),
+    };
+    let (start_row, end_row) = match (first.0, last.0) {
+        (Some(a), Some(b)) => (a.min(b), a.max(b)),
+        (None, None) => (0, MAX_ROWS - 1),
+        _ => return Err(XlsxError::RangeWithoutRowComponent),
+    };
+    let (start_col, end_col) = match (first.1, last.1) {
+        (Some(a), Some(b)) => (a.min(b), a.max(b)),
+        (None, None) => (0, MAX_COLUMNS - 1),
+        _ => return Err(XlsxError::RangeWithoutColumnComponent),
+    };
+    Ok(Dimensions::new((start_row, start_col), (end_row, end_col)))
+}
+
+/// Converts a text cell, column (`A`) or row (`1`) name into its optional
+/// row and column (0 based index)
+fn get_optional_row_column(range: &[u8]) -> Result<(Option<u32>, Option<u32>), XlsxError> {
+    if range.iter().all(u8::is_ascii_digit) {
+        return Ok((Some(get_row(range)?), None));
+    }
+    if range.iter().all(u8::is_ascii_alphabetic) {
+        let col = range.iter().fold(0u32, |col, c| {
+            col.saturating_mul(26)
+                .saturating_add((c.to_ascii_uppercase() - b'A') as u32 + 1)
+        });
+        return Ok((None, Some(col - 1)));
+    }
+    let (row, col) = get_row_column(range)?;
+    Ok((Some(row), Some(col)))
+}
+
 /// Converts a text range name into its position (row, column) (0 based index).
 /// If the row or column component in the range is missing, an Error is returned.
 pub(crate) fn get_row_column(range: &[u8]) -> Result<(u32, u32), XlsxError> {
@@ -3314,6 +6078,96 @@ fn check_for_password_protected<RS: Read + Seek>(reader: &mut RS) -> Result<(),
     Ok(())
 }
 
+/// Read the title, the kind and the series of a chart part
+fn read_chart<RS>(
+    xml: &mut XlReader<'_, RS>,
+) -> Result<(Option<String>, ChartType, Vec<SeriesRef>), XlsxError>
+where
+    RS: Read + Seek,
+{
+    let mut title: Option<String> = None;
+    let mut chart_type = None;
+    let mut series: Vec<SeriesRef> = Vec::new();
+    // local names of the open elements
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    let mut buf = Vec::with_capacity(1024);
+    loop {
+        buf.clear();
+        match xml.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let name = e.local_name().as_ref().to_vec();
+                if path.last().map(Vec::as_slice) == Some(b"plotArea") && chart_type.is_none() {
+                    chart_type = match name.as_slice() {
+                        b"barChart" | b"bar3DChart" => Some(ChartType::Bar),
+                        b"lineChart" | b"line3DChart" => Some(ChartType::Line),
+                        b"pieChart" | b"pie3DChart" | b"ofPieChart" => Some(ChartType::Pie),
+                        b"doughnutChart" => Some(ChartType::Doughnut),
+                        b"areaChart" | b"area3DChart" => Some(ChartType::Area),
+                        b"scatterChart" => Some(ChartType::Scatter),
+                        b"radarChart" => Some(ChartType::Radar),
+                        b"bubbleChart" => Some(ChartType::Bubble),
+                        n if n.ends_with(b"Chart") => {
+                            Some(ChartType::Other(String::from_utf8_lossy(n).into_owned()))
+                        }
+                        _ => None,
+                    };
+                }
+                if name == b"ser" {
+                    series.push(SeriesRef::default());
+                }
+                path.push(name);
+            }
+            Ok(Event::End(_)) => {
+                path.pop();
+            }
+            Ok(Event::Text(ref t)) => {
+                let parent = |name: &[u8]| path.iter().any(|p| p == name);
+                let text = t.unescape()?;
+                // title of the chart itself, not of its axes
+                let chart_title = path
+                    .windows(2)
+                    .any(|w| w[0] == b"chart" && w[1] == b"title");
+                match path.last().map(Vec::as_slice) {
+                    Some(b"t" | b"v") if chart_title => {
+                        title.get_or_insert_with(String::new).push_str(&text);
+                    }
+                    Some(b"v") if parent(b"ser") && parent(b"tx") => {
+                        if let Some(ser) = series.last_mut() {
+                            ser.name.get_or_insert_with(String::new).push_str(&text);
+                        }
+                    }
+                    Some(b"f") if parent(b"ser") => {
+                        let Some(ser) = series.last_mut() else {
+                            continue;
+                        };
+                        let (sheet, range) = match text.rsplit_once('!') {
+                            Some((sheet, range)) => (Some(unquote_sheet_name(sheet)), range),
+                            None => (None, text.as_ref()),
+                        };
+                        let Ok(dimensions) = get_dimension(range.replace('$', "").as_bytes())
+                        else {
+                            continue;
+                        };
+                        if parent(b"val") || parent(b"yVal") {
+                            ser.values_sheet = sheet;
+                            ser.values = Some(dimensions);
+                        } else if parent(b"cat") || parent(b"xVal") {
+                            ser.categories_sheet = sheet;
+                            ser.categories = Some(dimensions);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(XlsxError::Xml(e)),
+            _ => (),
+        }
+    }
+    let chart_type = chart_type.unwrap_or_else(|| ChartType::Other(String::new()));
+    Ok((title, chart_type, series))
+}
+
 fn read_merge_cells<RS>(xml: &mut XlReader<'_, RS>) -> Result<Vec<Dimensions>, XlsxError>
 where
     RS: Read + Seek,
@@ -3348,8 +6202,8 @@ where
     Ok(merge_cells)
 }
 
-/// advance the cell name by the offset
-fn offset_cell_name(name: &[char], offset: (i64, i64)) -> Result<Vec<u8>, XlsxError> {
+/// parse a cell name into its (row, col) and whether the row and column are fixed by `$`
+fn parse_cell_name(name: &[char]) -> Result<((u32, u32), bool, bool), XlsxError> {
     if name.is_empty() {
         return Err(XlsxError::Unexpected("empty cell name"));
     }
@@ -3398,7 +6252,13 @@ fn offset_cell_name(name: &[char], offset: (i64, i64)) -> Result<Vec<u8>, XlsxEr
         .collect();
     
     let cell = get_row_column(&clean_name)?;
-    
+    Ok((cell, row_fixed, col_fixed))
+}
+
+/// advance the cell name by the offset
+fn offset_cell_name(name: &[char], offset: (i64, i64)) -> Result<Vec<u8>, XlsxError> {
+    let (cell, row_fixed, col_fixed) = parse_cell_name(name)?;
+
     // Apply offsets only if not fixed
     let new_row = if row_fixed {
         cell.0
@@ -3415,20 +6275,57 @@ fn offset_cell_name(name: &[char], offset: (i64, i64)) -> Result<Vec<u8>, XlsxEr
     coordinate_to_name_with_fixed((new_row, new_col), row_fixed, col_fixed)
 }
 
+/// convert the cell name to its R1C1 form relative to the cell at `pos`
+fn r1c1_cell_name(name: &[char], pos: (u32, u32)) -> Result<Vec<u8>, XlsxError> {
+    let (cell, row_fixed, col_fixed) = parse_cell_name(name)?;
+    if cell.0 >= MAX_ROWS || cell.1 >= MAX_COLUMNS {
+        return Err(XlsxError::Unexpected("cell name out of the worksheet"));
+    }
+    let part = |prefix: char, index: u32, base: u32, fixed: bool| {
+        if fixed {
+            format!("{prefix}{}", index + 1)
+        } else if index == base {
+            prefix.to_string()
+        } else {
+            format!("{prefix}[{}]", index as i64 - base as i64)
+        }
+    };
+    let mut res = part('R', cell.0, pos.0, row_fixed);
+    res.push_str(&part('C', cell.1, pos.1, col_fixed));
+    Ok(res.into_bytes())
+}
+
 /// advance all valid cell names in the string by the offset
 fn replace_cell_names(s: &str, offset: (i64, i64)) -> Result<String, XlsxError> {
+    map_cell_names(s, |name| offset_cell_name(name, offset))
+}
+
+/// rewrite all valid cell names in the string with `f`, leaving invalid ones untouched
+fn map_cell_names<F>(s: &str, f: F) -> Result<String, XlsxError>
+where
+    F: Fn(&[char]) -> Result<Vec<u8>, XlsxError>,
+{
     let mut res: Vec<u8> = Vec::new();
     let mut cell: Vec<char> = Vec::new();
     let mut is_cell_row = false;
     let mut in_quote = false;
+    let mut in_sheet_name = false;
+    let mut utf8 = [0; 4];
     for c in s.chars() {
-        if c == '"' {
+        if c == '"' && !in_sheet_name {
             in_quote = !in_quote;
         }
-        if in_quote {
-            res.push(c as u8);
+        if in_quote || in_sheet_name {
+            if c == '\'' && !in_quote {
+                in_sheet_name = false;
+            }
+            res.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
             continue;
         }
+        if c == '\'' {
+            // quoted sheet names, e.g. 'Q1 Sales'!A1, may look like cell names
+            in_sheet_name = true;
+        }
         if c == '$' {
             // Allow $ before column or row
             cell.push(c);
@@ -3444,18 +6341,20 @@ fn replace_cell_names(s: &str, offset: (i64, i64)) -> Result<String, XlsxError>
             is_cell_row = true;
             cell.push(c);
         } else {
-            if let Ok(cell_name) = offset_cell_name(cell.as_ref(), offset) {
-                res.extend(cell_name);
-            } else {
-                res.extend(cell.iter().map(|c| *c as u8));
+            // sheet names (`Sheet2!A1`) and function names (`LOG10(A1)`) may
+            // look like cell names
+            let is_name = c == '!' || c == '(';
+            match f(cell.as_ref()) {
+                Ok(cell_name) if !is_name => res.extend(cell_name),
+                _ => res.extend(cell.iter().map(|c| *c as u8)),
             }
             cell.clear();
             is_cell_row = false;
-            res.push(c as u8);
+            res.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
         }
     }
     if !cell.is_empty() {
-        if let Ok(cell_name) = offset_cell_name(cell.as_ref(), offset) {
+        if let Ok(cell_name) = f(cell.as_ref()) {
             res.extend(cell_name);
         } else {
             res.extend(cell.iter().map(|c| *c as u8));
@@ -3552,6 +6451,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_relationship_target() {
+        let resolve = resolve_relationship_target;
+        assert_eq!(
+            resolve("xl", "worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+        assert_eq!(resolve("xl", "./styles.xml"), "xl/styles.xml");
+        assert_eq!(resolve("xl/worksheets", "../media/a.png"), "xl/media/a.png");
+        assert_eq!(
+            resolve("xl/worksheets", "/xl/media/a.png"),
+            "xl/media/a.png"
+        );
+        assert_eq!(
+            resolve("xl/charts/sub", "../../media/a.png"),
+            "xl/media/a.png"
+        );
+        assert_eq!(resolve("xl/a", ".././b/../c.xml"), "xl/c.xml");
+        assert_eq!(resolve("xl", "../x"), "x");
+        assert_eq!(resolve("xl", "../../x"), "x");
+        // package relationships (`_rels/.rels`) are relative to the root
+        assert_eq!(resolve("", "xl/workbook.xml"), "xl/workbook.xml");
+        assert_eq!(resolve("", "/xl/workbook.xml"), "xl/workbook.xml");
+    }
+
+    #[test]
+    fn test_parse_sqref() {
+        assert_eq!(
+            parse_sqref("B3").unwrap(),
+            vec![Dimensions::new((2, 1), (2, 1))]
+        );
+        assert_eq!(
+            parse_sqref("A1:B2 D4:E5").unwrap(),
+            vec![
+                Dimensions::new((0, 0), (1, 1)),
+                Dimensions::new((3, 3), (4, 4))
+            ]
+        );
+        assert_eq!(
+            parse_sqref(" $A$1:$B$2  C7 ").unwrap(),
+            vec![
+                Dimensions::new((0, 0), (1, 1)),
+                Dimensions::new((6, 2), (6, 2))
+            ]
+        );
+        assert!(parse_sqref("").unwrap().is_empty());
+        assert_eq!(
+            parse_sqref("B2:A1 A:B $3:$2").unwrap(),
+            vec![
+                Dimensions::new((0, 0), (1, 1)),
+                Dimensions::new((0, 0), (MAX_ROWS - 1, 1)),
+                Dimensions::new((1, 0), (2, MAX_COLUMNS - 1)),
+            ]
+        );
+        assert!(parse_sqref("A1:B2 A:1").is_err());
+        assert!(parse_sqref("A1:B2:C3").is_err());
+        assert!(parse_sqref("A1:").is_err());
+        assert!(parse_sqref("1A").is_err());
+    }
+
     #[test]
     fn test_parse_error() {
         assert_eq!(
@@ -3579,6 +6538,30 @@ mod tests {
             CellErrorType::from_str("#VALUE!").unwrap(),
             CellErrorType::Value
         );
+        assert_eq!(
+            CellErrorType::from_str("#SPILL!").unwrap(),
+            CellErrorType::Spill
+        );
+        assert_eq!(
+            CellErrorType::from_str("#CALC!").unwrap(),
+            CellErrorType::Calc
+        );
+    }
+
+    #[test]
+    fn test_parse_error_code() {
+        for (code, error) in [
+            ("0", CellErrorType::Null),
+            ("7", CellErrorType::Div0),
+            ("15", CellErrorType::Value),
+            ("23", CellErrorType::Ref),
+            ("29", CellErrorType::Name),
+            ("36", CellErrorType::Num),
+            ("42", CellErrorType::NA),
+        ] {
+            assert_eq!(CellErrorType::from_str(code).unwrap(), error);
+        }
+        assert!(CellErrorType::from_str("2").is_err());
     }
 
     #[test]
@@ -3626,6 +6609,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_replace_cell_names_quoted_sheet() {
+        assert_eq!(
+            replace_cell_names("'Q1 Sales'!A1*2", (1, 0)).unwrap(),
+            "'Q1 Sales'!A2*2".to_owned()
+        );
+        assert_eq!(
+            replace_cell_names("SUM('Données 2024'!B1:B3)+'It''s A1'!$C1", (2, 1)).unwrap(),
+            "SUM('Données 2024'!C3:C5)+'It''s A1'!$C3".to_owned()
+        );
+        assert_eq!(
+            replace_cell_names("IF(A1=\"it's\",'B2'!A1,\"\")", (1, 0)).unwrap(),
+            "IF(A2=\"it's\",'B2'!A2,\"\")".to_owned()
+        );
+    }
+
     #[test]
     fn test_replace_cell_names_absolute() {
         // Test absolute column reference
@@ -3687,8 +6686,17 @@ mod tests {
             tables: None,
             formats: vec![],
             styles: vec![],
+            cell_xfs: vec![],
+            named_styles: vec![],
+            table_styles: vec![],
+            default_table_style: None,
+            default_pivot_style: None,
+            default_font: Font::default(),
             format_interner: FormatStringInterner::new(),
             is_1904: false,
+            workbook_protection: None,
+            custom_view_names: HashMap::new(),
+            defined_names: Vec::new(),
             metadata: Metadata::default(),
             #[cfg(feature = "picture")]
             pictures: None,
@@ -3697,6 +6705,9 @@ mod tests {
             dxf_formats: vec![],
             conditional_formats: BTreeMap::new(),
             theme: None,
+            indexed_colors: vec![],
+            mru_colors: vec![],
+            buffers: ReaderBuffers::default(),
         };
 
         assert!(xlsx.read_shared_strings().is_ok());
@@ -3705,6 +6716,49 @@ mod tests {
         assert_eq!("String 2", &xlsx.strings[1]);
         assert_eq!("String 3", &xlsx.strings[2]);
     }
+
+    #[test]
+    fn test_limited_reader() {
+        let xml = b"<sheetData><row r=\"1\"/></sheetData>";
+        let reader = |max| {
+            let inner = LimitedReader {
+                inner: &xml[..],
+                max,
+                read: 0,
+            };
+            XmlReader::from_reader(PartReader::with_buf(inner, Vec::new()))
+        };
+        let read_all = |mut xml: XmlReader<_>| -> Result<(), XlsxError> {
+            let mut buf = Vec::new();
+            while xml.read_event_into(&mut buf)? != Event::Eof {}
+            Ok(())
+        };
+
+        assert!(read_all(reader(None)).is_ok());
+        assert!(read_all(reader(Some(xml.len() as u64))).is_ok());
+        assert!(matches!(
+            read_all(reader(Some(10))),
+            Err(XlsxError::LimitExceeded {
+                limit: "max_decompressed_bytes",
+                max: 10
+            })
+        ));
+
+        // parts read as bytes, e.g. images, fail the same way
+        let mut inner = LimitedReader {
+            inner: &xml[..],
+            max: Some(10),
+            read: 0,
+        };
+        let e = inner.read_to_end(&mut Vec::new()).unwrap_err();
+        assert!(matches!(
+            XlsxError::from(e),
+            XlsxError::LimitExceeded {
+                limit: "max_decompressed_bytes",
+                max: 10
+            }
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -3720,6 +6774,7 @@ mod comprehensive_formatting_tests {
         let formatting = CellStyle {
             number_format: CellFormat::Other,
             format_string: None,
+            number_format_id: None,
             font: Some(Arc::new(Font {
                 name: Some(Arc::from("Arial")),
                 size: Some(12.0),
@@ -0,0 +1,13 @@
+/// A named cell style (`<cellStyle>` in `styles.xml`), e.g. "Heading 1"
+///
+/// Cell formats (`cellXfs`) point to a named style with their `xfId`, the
+/// index of the style's format in `cellStyleXfs`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NamedStyle {
+    /// Name of the style, as shown in Excel (`name`)
+    pub name: String,
+    /// Index of the style's format in `cellStyleXfs` (`xfId`)
+    pub xf_id: u32,
+    /// Id of the builtin style, e.g. `0` for "Normal" (`builtinId`)
+    pub builtin_id: Option<u32>,
+}
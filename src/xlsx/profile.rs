@@ -0,0 +1,47 @@
+use crate::DataRef;
+
+/// Count of each cell value type in a worksheet column
+///
+/// Counts cover the rows between the first and the last non-empty row of the
+/// worksheet, so that every column of a profile adds up to the same total.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ColumnProfile {
+    /// Absolute column index, zero indexed from `A`
+    pub column: u32,
+    /// Number of string cells
+    pub strings: usize,
+    /// Number of float cells
+    pub floats: usize,
+    /// Number of integer cells
+    pub ints: usize,
+    /// Number of boolean cells
+    pub bools: usize,
+    /// Number of date, time and duration cells
+    pub dates: usize,
+    /// Number of error cells
+    pub errors: usize,
+    /// Number of empty cells
+    pub empty: usize,
+}
+
+impl ColumnProfile {
+    /// Total number of cells of the column
+    pub fn total(&self) -> usize {
+        self.strings + self.floats + self.ints + self.bools + self.dates + self.errors + self.empty
+    }
+
+    /// Count one cell value
+    pub(crate) fn add(&mut self, value: &DataRef<'_>) {
+        match value {
+            DataRef::String(_) | DataRef::SharedString(_) => self.strings += 1,
+            DataRef::Float(_) => self.floats += 1,
+            DataRef::Int(_) => self.ints += 1,
+            DataRef::Bool(_) => self.bools += 1,
+            DataRef::DateTime(_) | DataRef::DateTimeIso(_) | DataRef::DurationIso(_) => {
+                self.dates += 1;
+            }
+            DataRef::Error(_) => self.errors += 1,
+            DataRef::Empty => self.empty += 1,
+        }
+    }
+}
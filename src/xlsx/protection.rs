@@ -0,0 +1,50 @@
+use crate::Dimensions;
+
+/// Workbook protection settings (`<workbookProtection>` in `workbook.xml`)
+///
+/// A protected structure prevents users from adding, removing, renaming or
+/// reordering sheets. The password itself is never stored, only its hash.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WorkbookProtection {
+    /// Sheets can't be added, removed, renamed or moved (`lockStructure`)
+    pub lock_structure: bool,
+    /// Workbook windows can't be moved or resized (`lockWindows`)
+    pub lock_windows: bool,
+    /// Change tracking can't be turned off (`lockRevision`)
+    pub lock_revision: bool,
+    /// Legacy 16-bit password hash, in hexadecimal (`workbookPassword`)
+    pub password_hash: Option<String>,
+    /// Hash algorithm of the password, e.g. `SHA-512` (`workbookAlgorithmName`)
+    pub algorithm_name: Option<String>,
+    /// Base64 encoded password hash (`workbookHashValue`)
+    pub hash_value: Option<String>,
+    /// Base64 encoded salt of the password hash (`workbookSaltValue`)
+    pub salt_value: Option<String>,
+    /// Number of hashing iterations (`workbookSpinCount`)
+    pub spin_count: Option<u32>,
+}
+
+/// A range users can edit when the worksheet is protected (`<protectedRange>`)
+///
+/// These are the "Allow Edit Ranges" of Excel. A range may require its own
+/// password, of which only the hash is stored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProtectedRange {
+    /// Name of the range (`name`)
+    pub name: String,
+    /// Cell ranges covered by the range (`sqref`)
+    pub sqref: Vec<Dimensions>,
+    /// Legacy 16-bit password hash, in hexadecimal (`password`)
+    pub password_hash: Option<String>,
+    /// Hash algorithm of the password, e.g. `SHA-512` (`algorithmName`)
+    pub algorithm_name: Option<String>,
+    /// Base64 encoded password hash (`hashValue`)
+    pub hash_value: Option<String>,
+    /// Base64 encoded salt of the password hash (`saltValue`)
+    pub salt_value: Option<String>,
+    /// Number of hashing iterations (`spinCount`)
+    pub spin_count: Option<u32>,
+    /// Windows security descriptor of the users allowed to edit the range
+    /// without a password (`securityDescriptor`)
+    pub security_descriptor: Option<String>,
+}
@@ -0,0 +1,24 @@
+/// A relationship of a package part (`<Relationship>` in a `.rels` part)
+///
+/// Parts refer to each other by relationship id (`r:id`), resolved through
+/// the `.rels` part next to the source part.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Relationship {
+    /// Relationship type URI, e.g. `.../relationships/worksheet` (`Type`)
+    pub rel_type: String,
+    /// Target of the relationship (`Target`)
+    ///
+    /// Internal targets are resolved to their path in the archive, e.g.
+    /// `xl/tables/table1.xml`. External targets, such as hyperlinks, are
+    /// kept as is.
+    pub target: String,
+    /// `External` for targets outside of the package (`TargetMode`)
+    pub target_mode: Option<String>,
+}
+
+impl Relationship {
+    /// Whether the target is outside of the package
+    pub fn is_external(&self) -> bool {
+        self.target_mode.as_deref() == Some("External")
+    }
+}
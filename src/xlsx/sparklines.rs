@@ -0,0 +1,36 @@
+use crate::Dimensions;
+
+/// Kind of chart drawn by a sparkline group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SparklineType {
+    /// Line chart (default)
+    #[default]
+    Line,
+    /// Column chart
+    Column,
+    /// Win/loss chart (`stacked` in the file)
+    WinLoss,
+}
+
+/// A single sparkline: the data it plots and the cell it is drawn in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparkline {
+    /// Sheet of the data range, `None` if the reference has no sheet name
+    pub data_sheet: Option<String>,
+    /// Data range
+    pub data: Dimensions,
+    /// Cell position (row, col) the sparkline is drawn in
+    pub location: (u32, u32),
+}
+
+/// A group of sparklines sharing the same settings
+///
+/// Sparkline groups are stored in the `x14:sparklineGroups` worksheet
+/// extension.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SparklineGroup {
+    /// Chart type of the group
+    pub sparkline_type: SparklineType,
+    /// Sparklines of the group
+    pub sparklines: Vec<Sparkline>,
+}
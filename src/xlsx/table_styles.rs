@@ -0,0 +1,41 @@
+/// Style applied to a table (`<tableStyleInfo>` in a table part)
+///
+/// The style is either a builtin one, e.g. "TableStyleMedium2", or a custom
+/// style defined in `styles.xml` (see [`TableStyle`]).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TableStyleInfo {
+    /// Name of the table style (`name`)
+    pub name: Option<String>,
+    /// First column is formatted differently (`showFirstColumn`)
+    pub show_first_column: bool,
+    /// Last column is formatted differently (`showLastColumn`)
+    pub show_last_column: bool,
+    /// Rows are banded (`showRowStripes`)
+    pub show_row_stripes: bool,
+    /// Columns are banded (`showColumnStripes`)
+    pub show_column_stripes: bool,
+}
+
+/// A custom table style (`<tableStyle>` in `styles.xml`)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TableStyle {
+    /// Name of the style (`name`)
+    pub name: String,
+    /// Style can be applied to pivot tables (`pivot`)
+    pub pivot: bool,
+    /// Style can be applied to tables (`table`)
+    pub table: bool,
+    /// Formatted parts of the table (`<tableStyleElement>`)
+    pub elements: Vec<TableStyleElement>,
+}
+
+/// Formatting of one part of a table style (`<tableStyleElement>`)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TableStyleElement {
+    /// Part of the table, e.g. `wholeTable` or `headerRow` (`type`)
+    pub element_type: String,
+    /// Index of the differential format in `dxfs` (`dxfId`)
+    pub dxf_id: Option<u32>,
+    /// Number of rows or columns in a stripe (`size`)
+    pub size: u32,
+}
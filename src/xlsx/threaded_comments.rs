@@ -0,0 +1,24 @@
+/// A threaded comment (or reply) attached to a worksheet cell
+///
+/// Threaded comments are stored in `xl/threadedComments/threadedComment{n}.xml`
+/// and their authors in `xl/persons/person.xml`. A reply has the `id` of the
+/// first comment of its thread as `parent_id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadedComment {
+    /// Cell position (row, col) the comment is attached to
+    pub cell: (u32, u32),
+    /// Unique id of the comment
+    pub id: String,
+    /// Id of the comment this one replies to, `None` for the first comment of a thread
+    pub parent_id: Option<String>,
+    /// Id of the author in the person list
+    pub person_id: String,
+    /// Display name of the author, empty if the person is not in the person list
+    pub author_display_name: String,
+    /// Comment text
+    pub text: String,
+    /// Creation date and time, as an ISO 8601 string
+    pub timestamp: Option<String>,
+    /// Whether the thread has been resolved
+    pub done: bool,
+}
@@ -3,6 +3,7 @@ use calamine::{
     open_workbook, open_workbook_auto, CellFormat, Color, ColumnDefinition, ColumnWidths, DataRef,
     DataWithFormatting, Dimensions, ExcelDateTime, ExcelDateTimeType, HeaderRow, Ods, PatternType,
     Range, Reader, ReaderRef, Sheet, SheetType, SheetVisible, UnderlineStyle, Xls, Xlsb, Xlsx,
+    XlsxOptions,
 };
 use calamine::{CellErrorType::*, Data};
 use rstest::rstest;
@@ -3348,4 +3349,2596 @@ fn test_translated_formulas() {
     
     assert_eq!(formula_a3, expected_a3, "A3 formula mismatch");
     assert_eq!(formula_b3, expected_b3, "B3 formula mismatch");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_range_2d_vec_round_trip() {
+    let rows = vec![
+        vec![Int(1), Float(2.5)],
+        vec![],
+        vec![Bool(true), Empty, Int(3)],
+    ];
+
+    let range = Range::from_2d_vec(rows);
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((2, 2)));
+    range_eq!(
+        range,
+        [
+            [Int(1), Float(2.5), Empty],
+            [Empty, Empty, Empty],
+            [Bool(true), Empty, Int(3)],
+        ]
+    );
+
+    // The padded rows are returned rectangular.
+    let back = range.clone().into_2d_vec();
+    assert_eq!(
+        back,
+        vec![
+            vec![Int(1), Float(2.5), Empty],
+            vec![Empty, Empty, Empty],
+            vec![Bool(true), Empty, Int(3)],
+        ]
+    );
+    let again = Range::from_2d_vec(back);
+    assert_eq!(again.get_size(), range.get_size());
+    assert!(again.cells().zip(range.cells()).all(|(a, b)| a == b));
+
+    assert!(Range::<Data>::from_2d_vec(vec![]).is_empty());
+    assert!(Range::<Data>::from_2d_vec(vec![vec![], vec![]]).is_empty());
+    assert!(Range::<Data>::empty().into_2d_vec().is_empty());
+}
+
+#[test]
+fn test_custom_indexed_palette() {
+    let mut excel: Xlsx<_> = wb("custom_palette.xlsx");
+
+    // The workbook redefines index 10 (red by default) to #123456
+    assert_eq!(excel.indexed_palette().len(), 64);
+    assert_eq!(
+        excel.indexed_color(10),
+        Some(&Color::Argb {
+            a: 0xFF,
+            r: 0x12,
+            g: 0x34,
+            b: 0x56
+        })
+    );
+    assert_eq!(
+        excel.indexed_color(2),
+        Some(&Color::Argb {
+            a: 0xFF,
+            r: 0xFF,
+            g: 0x00,
+            b: 0x00
+        })
+    );
+    assert_eq!(excel.indexed_color(64), None);
+
+    let styles = excel.worksheet_formats("Sheet1").unwrap();
+    let font = styles.get((0, 0)).unwrap().font.as_ref().unwrap();
+    match font.color {
+        Some(Color::Indexed(idx)) => assert_eq!(
+            excel.indexed_color(idx),
+            Some(&Color::Argb {
+                a: 0xFF,
+                r: 0x12,
+                g: 0x34,
+                b: 0x56
+            })
+        ),
+        ref c => panic!("expected an indexed font color, got {c:?}"),
+    }
+
+    assert_eq!(
+        excel.mru_colors(),
+        &[
+            Color::Argb {
+                a: 0xFF,
+                r: 0xAB,
+                g: 0xCD,
+                b: 0xEF
+            },
+            Color::Argb {
+                a: 0xFF,
+                r: 0x00,
+                g: 0xB0,
+                b: 0x50
+            }
+        ]
+    );
+
+    // Workbooks without a custom palette use the default one
+    let excel: Xlsx<_> = wb("colortest.xlsx");
+    assert_eq!(excel.indexed_palette().len(), 64);
+    assert_eq!(
+        excel.indexed_color(10),
+        Some(&Color::Argb {
+            a: 0xFF,
+            r: 0xFF,
+            g: 0x00,
+            b: 0x00
+        })
+    );
+    assert!(excel.mru_colors().is_empty());
+}
+
+#[test]
+fn test_xlsx_reused_buffers() {
+    fn cells(range: &Range<DataWithFormatting>) -> Vec<(usize, usize, Data, bool)> {
+        range
+            .used_cells()
+            .map(|(r, c, v)| (r, c, v.data.clone(), v.is_spilled))
+            .collect()
+    }
+
+    let files = [
+        "issues.xlsx",
+        "formula.issue.xlsx",
+        "spill.xlsx",
+        "merged_range.xlsx",
+    ];
+    let mut buffers = calamine::ReaderBuffers::new();
+    for file in files {
+        let mut fresh: Xlsx<_> = wb(file);
+        let mut pooled: Xlsx<_> = wb(file);
+        pooled.with_buffers(buffers);
+        for name in fresh.sheet_names() {
+            let expected = fresh.worksheet_range(&name).unwrap();
+            let actual = pooled.worksheet_range(&name).unwrap();
+            assert_eq!(expected.start(), actual.start(), "{file}: {name}");
+            assert_eq!(cells(&expected), cells(&actual), "{file}: {name}");
+
+            let expected = fresh.worksheet_formula(&name).unwrap();
+            let actual = pooled.worksheet_formula(&name).unwrap();
+            assert_eq!(expected.start(), actual.start(), "{file}: {name}");
+            assert_eq!(cells(&expected), cells(&actual), "{file}: {name}");
+
+            let expected = fresh.worksheet_formats(&name).unwrap();
+            let actual = pooled.worksheet_formats(&name).unwrap();
+            assert_eq!(expected.get_size(), actual.get_size(), "{file}: {name}");
+            assert!(expected.cells().eq(actual.cells()), "{file}: {name}");
+        }
+        buffers = pooled.into_buffers();
+    }
+}
+
+#[test]
+fn test_range_merge_map() {
+    let mut range: Range<Data> = Range::new((0, 0), (3, 3));
+    range.set_value((1, 1), String("anchor".to_string()));
+
+    // 2x2 merge on B2:C3, plus one merge outside of the range
+    let merges = [
+        Dimensions::new((1, 1), (2, 2)),
+        Dimensions::new((10, 0), (11, 0)),
+    ];
+    let merge_map = range.merge_map(&merges);
+    assert_eq!(merge_map.merges(), &merges[..1]);
+
+    for pos in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+        assert!(merge_map.is_merged(pos), "{pos:?}");
+        assert_eq!(merge_map.anchor_of(pos), Some((1, 1)), "{pos:?}");
+    }
+    for pos in [(0, 0), (0, 1), (1, 0), (1, 3), (3, 2), (3, 3)] {
+        assert!(!merge_map.is_merged(pos), "{pos:?}");
+        assert_eq!(merge_map.anchor_of(pos), None, "{pos:?}");
+    }
+
+    let anchor = merge_map.anchor_of((2, 2)).unwrap();
+    assert_eq!(range.get_value(anchor), Some(&String("anchor".to_string())));
+    assert_eq!(range.get_value((2, 2)), Some(&Empty));
+}
+
+#[test]
+fn test_range_fill_merges() {
+    let mut range: Range<Data> = Range::new((0, 0), (2, 3));
+    range.set_value((0, 0), String("Region".to_string()));
+    range.set_value((0, 1), String("Sales".to_string()));
+    range.set_value((1, 0), String("North".to_string()));
+    range.set_value((1, 1), Int(1));
+    range.set_value((1, 2), Int(2));
+    range.set_value((1, 3), Int(3));
+
+    // B1:D1 header, a merge going past the range end and one outside of it
+    let merges = [
+        Dimensions::new((0, 1), (0, 3)),
+        Dimensions::new((2, 3), (4, 5)),
+        Dimensions::new((10, 0), (11, 0)),
+    ];
+    let filled = range.with_merges_filled(&merges);
+    let sales = String("Sales".to_string());
+    range_eq!(
+        filled,
+        [
+            [
+                String("Region".to_string()),
+                sales.clone(),
+                sales.clone(),
+                sales.clone()
+            ],
+            [String("North".to_string()), Int(1), Int(2), Int(3)],
+            [Empty, Empty, Empty, Empty],
+        ]
+    );
+    assert_eq!(range.get_value((0, 2)), Some(&Empty));
+
+    range.fill_merges(&merges);
+    assert_eq!(range.get_value((0, 3)), Some(&String("Sales".to_string())));
+}
+
+#[test]
+fn test_range_iter_effective() {
+    let mut range: Range<Data> = Range::new((1, 1), (2, 3));
+    range.set_value((1, 1), String("Item".to_string()));
+    range.set_value((1, 2), String("Quarter".to_string()));
+    range.set_value((2, 1), String("pens".to_string()));
+    range.set_value((2, 2), Int(4));
+
+    // C2:D2 header and a merge whose anchor is outside of the range
+    let merges = [
+        Dimensions::new((1, 2), (1, 3)),
+        Dimensions::new((0, 3), (2, 3)),
+    ];
+    let quarter = String("Quarter".to_string());
+    let cells: Vec<_> = range.iter_effective(&merges).collect();
+    assert_eq!(cells[1], (0, 1, quarter.clone()));
+    assert_eq!(cells[2], (0, 2, quarter));
+    assert_eq!(cells[5], (1, 2, Empty));
+
+    // same values as the filled copy, the range itself is untouched
+    let filled = range.with_merges_filled(&merges);
+    assert!(filled
+        .cells()
+        .map(|(r, c, v)| (r, c, v.clone()))
+        .eq(range.iter_effective(&merges)));
+    assert_eq!(range.get_value((1, 3)), Some(&Empty));
+    assert_eq!(Range::<Data>::empty().iter_effective(&merges).count(), 0);
+}
+
+#[test]
+fn test_xlsx_shared_strings() {
+    // rich text runs are flattened, phonetic runs are left out
+    let excel: Xlsx<_> = wb("rph.xlsx");
+    assert_eq!(excel.shared_strings(), ["課きく　毛こ"]);
+
+    let excel: Xlsx<_> = wb("richtext-namespaced.xlsx");
+    assert!(excel
+        .shared_strings()
+        .iter()
+        .any(|s| s == "shared string\r\nLine 2\r\nLine 3"));
+}
+
+#[test]
+fn test_xlsx_worksheet_range_at_index() {
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    let names = excel.sheet_names();
+
+    for (index, name) in names.iter().enumerate().take(3) {
+        let by_index = excel.worksheet_range_at_index(index).unwrap();
+        let by_name = excel.worksheet_range(name).unwrap();
+        assert_eq!(by_index.start(), by_name.start(), "{name}");
+        assert_eq!(by_index.end(), by_name.end(), "{name}");
+        assert!(
+            by_index
+                .cells()
+                .zip(by_name.cells())
+                .all(|(a, b)| a.0 == b.0 && a.1 == b.1 && a.2 == b.2.get_data()),
+            "{name}"
+        );
+    }
+
+    assert!(matches!(
+        excel.worksheet_range_at_index(names.len()),
+        Err(calamine::XlsxError::WorksheetNotFound(_))
+    ));
+
+    excel.with_header_row(HeaderRow::Row(2));
+    let by_index = excel.worksheet_range_at_index(0).unwrap();
+    let by_name = excel.worksheet_range(&names[0]).unwrap();
+    assert_eq!(by_index.start(), by_name.start());
+    assert_eq!(by_index.end(), by_name.end());
+
+    // chartsheets and other sheets which are not worksheets are empty
+    let mut excel: Xlsx<_> = wb("any_sheets.xlsx");
+    let chartsheet = excel
+        .sheets_metadata()
+        .iter()
+        .position(|sheet| sheet.typ == SheetType::ChartSheet)
+        .unwrap();
+    assert!(excel
+        .worksheet_range_at_index(chartsheet)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_xlsx_bom_and_declared_encoding() {
+    // the worksheet part starts with a UTF-8 BOM and the shared strings part
+    // is declared and encoded as windows-1252
+    let mut excel: Xlsx<_> = wb("bom_encoding.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [[
+            String("café crème".to_string()),
+            String("inline café".to_string()),
+            Float(42.)
+        ]]
+    );
+}
+
+#[test]
+fn test_xlsx_all_print_areas() {
+    let excel: Xlsx<_> = wb("print_areas.xlsx");
+    let print_areas = excel.all_print_areas();
+
+    assert_eq!(print_areas.len(), 2);
+    assert_eq!(
+        print_areas["Sheet1"],
+        vec![
+            Dimensions::new((0, 0), (1, 1)),
+            Dimensions::new((3, 3), (4, 4))
+        ]
+    );
+    assert_eq!(
+        print_areas["Report, Q1"],
+        vec![Dimensions::new((0, 0), (9, 2))]
+    );
+    assert!(!print_areas.contains_key("Notes"));
+}
+
+#[test]
+fn test_range_value_counts() {
+    let mut range: Range<Data> = Range::new((1, 1), (6, 2));
+    range.set_value((1, 2), String("a".to_string()));
+    range.set_value((2, 2), Int(1));
+    range.set_value((3, 2), String("a".to_string()));
+    range.set_value((4, 2), Float(1.));
+    range.set_value((5, 2), String("a".to_string()));
+    range.set_value((6, 2), Int(1));
+    range.set_value((6, 1), String("other column".to_string()));
+
+    let counts = range.value_counts(2);
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[&String("a".to_string())], 3);
+    assert_eq!(counts[&Int(1)], 2);
+    assert_eq!(counts[&Float(1.)], 1);
+    assert!(!counts.contains_key(&Empty));
+
+    assert!(range.value_counts(5).is_empty());
+}
+
+#[test]
+fn test_range_eq_displayed() {
+    let percent = calamine::CellStyle {
+        number_format_id: Some(9),
+        ..Default::default()
+    };
+
+    let mut a: Range<Data> = Range::new((0, 0), (1, 2));
+    a.set_value((0, 0), Float(3.));
+    a.set_value((0, 1), Float(45292.));
+    a.set_value((0, 2), Float(0.5));
+    a.set_value((1, 0), Bool(true));
+
+    let mut b: Range<Data> = Range::new((0, 0), (3, 2));
+    b.set_value((0, 0), Int(3));
+    b.set_value((0, 1), String("1/1/2024".to_string()));
+    b.set_value((0, 2), String("50%".to_string()));
+    b.set_value((1, 0), String("TRUE".to_string()));
+
+    let codes = ["General", "m/d/yyyy", percent.format_code()];
+    assert!(a.eq_displayed(&b, &codes, &[], false));
+    assert!(b.eq_displayed(&a, &[], &codes, false));
+    assert!(!a.eq_displayed(&b, &[], &[], false));
+    // in the 1904 date system, 45292 is 1/2/2028
+    assert!(!a.eq_displayed(&b, &codes, &[], true));
+    b.set_value((0, 1), String("1/2/2028".to_string()));
+    assert!(a.eq_displayed(&b, &codes, &[], true));
+
+    b.set_value((3, 2), String("extra".to_string()));
+    assert!(!a.eq_displayed(&b, &codes, &[], true));
+}
+
+#[test]
+fn test_xlsx_worksheet_cell_images() {
+    let mut excel: Xlsx<_> = wb("cell_images.xlsx");
+    let images = excel.worksheet_cell_images("Sheet1").unwrap();
+
+    let positions = images.iter().map(|(pos, _)| *pos).collect::<Vec<_>>();
+    assert_eq!(positions, vec![(0, 1), (2, 1), (2, 2)]);
+    for (_, data) in &images {
+        assert!(data.starts_with(b"\x89PNG"));
+    }
+    // B1 and C3 share the same image, B3 has another one
+    assert_eq!(images[0].1, images[2].1);
+    assert_ne!(images[0].1, images[1].1);
+
+    assert!(excel.worksheet_cell_images("Sheet2").unwrap().is_empty());
+}
+
+#[test]
+fn test_strict_ooxml() {
+    let mut excel: Xlsx<_> = wb("strict.xlsx");
+    assert_eq!(excel.sheet_names(), vec!["Strict"]);
+
+    let range = excel.worksheet_range("Strict").unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("Name".to_string()),
+                String("Date".to_string()),
+                String("Flag".to_string())
+            ],
+            [
+                String("alpha".to_string()),
+                DateTimeIso("2021-06-30T12:30:00".to_string()),
+                Bool(true)
+            ],
+        ]
+    );
+
+    excel.load_tables().unwrap();
+    assert_eq!(excel.table_names(), vec!["Table1"]);
+    let table = excel.table_by_name("Table1").unwrap();
+    assert_eq!(table.sheet_name(), "Strict");
+    assert_eq!(table.columns(), ["Name", "Date", "Flag"]);
+}
+
+#[rstest]
+#[case(Div0, "#DIV/0!")]
+#[case(NA, "#N/A")]
+#[case(Name, "#NAME?")]
+#[case(Null, "#NULL!")]
+#[case(Num, "#NUM!")]
+#[case(Ref, "#REF!")]
+#[case(Value, "#VALUE!")]
+#[case(GettingData, "#DATA!")]
+#[case(Spill, "#SPILL!")]
+#[case(Calc, "#CALC!")]
+fn test_cell_error_type_as_excel_string(
+    #[case] error: calamine::CellErrorType,
+    #[case] expected: &str,
+) {
+    use calamine::DataType;
+
+    assert_eq!(error.as_excel_string(), expected);
+    assert_eq!(error.to_string(), expected);
+
+    let data = Error(error.clone());
+    assert!(data.is_error());
+    assert_eq!(data.error_kind(), Some(error));
+}
+
+#[test]
+fn test_data_error_kind_not_error() {
+    use calamine::DataType;
+
+    for data in [
+        Empty,
+        Int(1),
+        Float(1.5),
+        Bool(false),
+        String("#N/A".into()),
+    ] {
+        assert!(!data.is_error());
+        assert_eq!(data.error_kind(), None);
+    }
+}
+
+#[test]
+fn test_data_precision_warning() {
+    use calamine::DataType;
+
+    // 16 significant digits, like an id stored as a number
+    assert!(Float(1234567890123456.0).precision_warning());
+    assert!(Float(-0.1234567890123456).precision_warning());
+    assert!(Float(0.1 + 0.2).precision_warning());
+
+    assert!(!Float(123.45).precision_warning());
+    assert!(!Float(123456789012345.0).precision_warning());
+    assert!(!Float(1e300).precision_warning());
+    assert!(!Float(f64::NAN).precision_warning());
+    assert!(!Int(1234567890123456).precision_warning());
+    assert!(!String("1234567890123456".into()).precision_warning());
+}
+
+#[test]
+fn test_spill_and_calc_errors() {
+    let mut excel: Xlsx<_> = wb("spill_calc_errors.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [Float(1.), Float(1.), Float(3.)],
+            [Error(Spill), Error(Calc), Error(NA)],
+        ]
+    );
+}
+
+#[test]
+fn test_worksheet_dimension() {
+    let mut excel: Xlsx<_> = wb("header-row.xlsx");
+    assert_eq!(
+        excel.worksheet_dimension("Sheet1").unwrap(),
+        Dimensions::new((2, 0), (9, 3))
+    );
+
+    // no <dimension> element, the cells are scanned instead
+    let mut excel: Xlsx<_> = wb("temperature.xlsx");
+    let name = excel.sheet_names()[0].clone();
+    assert_eq!(
+        excel.worksheet_dimension(&name).unwrap(),
+        Dimensions::new((0, 0), (2, 1))
+    );
+
+    assert!(matches!(
+        excel.worksheet_dimension("missing"),
+        Err(calamine::XlsxError::WorksheetNotFound(_))
+    ));
+}
+
+#[test]
+fn test_xml_space_preserve() {
+    let mut excel: Xlsx<_> = wb("preserve_space.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("  indented".to_string()),
+                String("trailing   ".to_string()),
+                String("   ".to_string()),
+                String("rich  text".to_string())
+            ],
+            [
+                String("  inline ".to_string()),
+                String("  formula".to_string()),
+                Empty,
+                Empty
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_xlsx_for_each_cell() {
+    use std::ops::ControlFlow;
+
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+
+    let mut visited = Vec::new();
+    excel
+        .for_each_cell("issue2", |cell| {
+            let found = cell.get_value() == &String("b".to_string());
+            visited.push((cell.get_position(), cell.get_value().clone()));
+            if found {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+    assert_eq!(
+        visited,
+        vec![
+            ((0, 0), Float(1.)),
+            ((0, 1), String("a".to_string())),
+            ((1, 0), Float(2.)),
+            ((1, 1), String("b".to_string())),
+        ]
+    );
+
+    let mut count = 0;
+    excel
+        .for_each_cell("issue2", |_| {
+            count += 1;
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+    assert_eq!(count, 6);
+}
+
+#[test]
+fn test_range_row_and_column() {
+    use calamine::Cell;
+
+    let cells = vec![
+        Cell::new((2, 3), Int(1)),
+        Cell::new((2, 5), Int(2)),
+        Cell::new((4, 3), Int(3)),
+        Cell::new((5, 4), Int(4)),
+    ];
+    let range = Range::from_sparse(cells);
+    assert_eq!(range.start(), Some((2, 3)));
+
+    assert_eq!(range.row(2), &[Int(1), Empty, Int(2)]);
+    assert_eq!(range.row(3), &[Empty, Empty, Empty]);
+    assert_eq!(range.row(5), &[Empty, Int(4), Empty]);
+    assert!(range.row(1).is_empty());
+    assert!(range.row(6).is_empty());
+
+    assert_eq!(range.column(3), vec![&Int(1), &Empty, &Int(3), &Empty]);
+    assert_eq!(range.column(4), vec![&Empty, &Empty, &Empty, &Int(4)]);
+    assert!(range.column(2).is_empty());
+    assert!(range.column(6).is_empty());
+
+    let empty: Range<Data> = Range::empty();
+    assert!(empty.row(0).is_empty());
+    assert!(empty.column(0).is_empty());
+}
+
+#[test]
+fn test_xlsx_threaded_comments() {
+    let mut excel: Xlsx<_> = wb("threaded_comments.xlsx");
+    let comments = excel.worksheet_threaded_comments("Sheet1").unwrap();
+    assert_eq!(comments.len(), 3);
+
+    let first = &comments[0];
+    assert_eq!(first.cell, (2, 1));
+    assert_eq!(first.author_display_name, "Alice Smith");
+    assert_eq!(first.text, "Is this total right?");
+    assert_eq!(first.timestamp.as_deref(), Some("2024-03-01T09:15:00.00"));
+    assert_eq!(first.parent_id, None);
+    assert!(!first.done);
+
+    let reply = &comments[1];
+    assert_eq!(reply.cell, (2, 1));
+    assert_eq!(reply.author_display_name, "Bob Jones");
+    assert_eq!(reply.text, "Yes, checked & fixed.");
+    assert_eq!(reply.parent_id.as_ref(), Some(&first.id));
+
+    let other = &comments[2];
+    assert_eq!(other.cell, (0, 0));
+    assert_eq!(other.author_display_name, "Bob Jones");
+    assert_eq!(other.parent_id, None);
+    assert!(other.done);
+
+    assert!(excel
+        .worksheet_threaded_comments("Sheet2")
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        excel.worksheet_threaded_comments("missing"),
+        Err(calamine::XlsxError::WorksheetNotFound(_))
+    ));
+}
+
+#[test]
+fn test_xlsx_worksheet_text() {
+    let mut excel: Xlsx<_> = wb("worksheet_text.xlsx");
+    let range = excel.worksheet_text("Sheet1").unwrap();
+
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((2, 2)));
+    assert_eq!(
+        range.rows().collect::<Vec<_>>(),
+        vec![
+            &["6/30/2021", "2021-06-30 12:00", "$1,234.50"][..],
+            &["25.60%", "3.5", "#DIV/0!"][..],
+            &["TRUE", "", "text"][..],
+        ]
+    );
+}
+
+#[test]
+fn test_xlsx_worksheet_sparklines() {
+    use calamine::{Sparkline, SparklineType};
+
+    let mut excel: Xlsx<_> = wb("sparklines.xlsx");
+    let groups = excel.worksheet_sparklines("Sheet1").unwrap();
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].sparkline_type, SparklineType::Line);
+    assert_eq!(
+        groups[0].sparklines,
+        vec![
+            Sparkline {
+                data_sheet: Some("Sheet1".to_string()),
+                data: Dimensions::new((0, 0), (0, 4)),
+                location: (0, 5),
+            },
+            Sparkline {
+                data_sheet: Some("Sheet1".to_string()),
+                data: Dimensions::new((1, 0), (1, 4)),
+                location: (1, 5),
+            },
+        ]
+    );
+
+    // the sparkline of the `SalesData` defined name is skipped
+    assert_eq!(groups[1].sparkline_type, SparklineType::WinLoss);
+    assert_eq!(
+        groups[1].sparklines,
+        vec![Sparkline {
+            data_sheet: Some("My Data".to_string()),
+            data: Dimensions::new((0, 0), (0, 2)),
+            location: (1, 6),
+        }]
+    );
+
+    assert!(excel.worksheet_sparklines("My Data").unwrap().is_empty());
+}
+
+#[test]
+fn test_xlsx_default_font() {
+    let excel: Xlsx<_> = wb("issues.xlsx");
+    let font = excel.default_font();
+    assert_eq!(font.name.as_deref(), Some("Calibri"));
+    assert_eq!(font.size, Some(11.0));
+    assert_eq!(font.bold, None);
+    assert_eq!(
+        font.color,
+        Some(Color::Theme {
+            theme: 1,
+            tint: None
+        })
+    );
+}
+
+#[test]
+fn test_range_from_cells() {
+    let range = Range::from_cells(vec![
+        ((3, 5), Float(1.5)),
+        ((1, 2), Int(1)),
+        ((2, 4), String("x".to_string())),
+    ]);
+    assert_eq!(range.start(), Some((1, 2)));
+    assert_eq!(range.end(), Some((3, 5)));
+    assert_eq!(range.get_size(), (3, 4));
+    range_eq!(
+        range,
+        [
+            [Int(1), Empty, Empty, Empty],
+            [Empty, Empty, String("x".to_string()), Empty],
+            [Empty, Empty, Empty, Float(1.5)],
+        ]
+    );
+
+    let empty: Range<Data> = Range::from_cells(std::iter::empty());
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_xlsx_worksheet_formula_typed() {
+    use calamine::CellResultType;
+
+    let mut excel: Xlsx<_> = wb("formula_result_types.xlsx");
+    let range = excel.worksheet_formula_typed("Sheet1").unwrap();
+
+    let formulas = range
+        .used_cells()
+        .map(|(r, c, (f, t))| ((r, c), f.as_str(), *t))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        formulas,
+        vec![
+            ((0, 1), "A1*3", CellResultType::Number),
+            ((0, 2), "\"n=\"&A1", CellResultType::Text),
+            ((0, 3), "A1>1", CellResultType::Bool),
+            ((1, 0), "1/0", CellResultType::Error),
+            ((1, 1), "A1+1", CellResultType::Empty),
+        ]
+    );
+}
+
+#[test]
+fn test_xlsx_workbook_protection() {
+    let excel: Xlsx<_> = wb("workbook_protection.xlsx");
+    let protection = excel.workbook_protection().unwrap();
+
+    assert!(protection.lock_structure);
+    assert!(!protection.lock_windows);
+    assert!(!protection.lock_revision);
+    assert_eq!(protection.password_hash, None);
+    assert_eq!(protection.algorithm_name.as_deref(), Some("SHA-512"));
+    assert_eq!(
+        protection.salt_value.as_deref(),
+        Some("JxwwVq2UG+5pT4lJ59nK1Q==")
+    );
+    assert!(protection.hash_value.is_some());
+    assert_eq!(protection.spin_count, Some(100_000));
+
+    let excel: Xlsx<_> = wb("issues.xlsx");
+    assert_eq!(excel.workbook_protection(), None);
+}
+
+#[test]
+fn test_xlsx_worksheet_profile() {
+    let mut excel: Xlsx<_> = wb("worksheet_profile.xlsx");
+    let profile = excel.worksheet_profile("Sheet1").unwrap();
+
+    let columns = profile.iter().map(|p| p.column).collect::<Vec<_>>();
+    assert_eq!(columns, [0, 1, 2, 3]);
+    assert!(profile.iter().all(|p| p.total() == 5));
+
+    // name: header and values are all strings
+    assert_eq!(profile[0].strings, 5);
+
+    // price: floats, with a header and a "n/a" string
+    assert_eq!((profile[1].floats, profile[1].strings), (3, 2));
+
+    // date: dates under a string header
+    assert_eq!((profile[2].dates, profile[2].strings), (4, 1));
+    assert_eq!(profile[2].floats, 0);
+
+    // paid: booleans, an error and a missing value
+    assert_eq!(profile[3].bools, 2);
+    assert_eq!(profile[3].errors, 1);
+    assert_eq!(profile[3].empty, 1);
+    assert_eq!(profile[3].strings, 1);
+}
+
+#[test]
+fn test_xlsx_worksheet_range_with_header_hint() {
+    let mut excel: Xlsx<_> = wb("header_hint.xlsx");
+
+    // the last frozen row is the header, even with a number in the first row
+    let (header, range) = excel.worksheet_range_with_header_hint("Frozen").unwrap();
+    assert_eq!(header, Some(1));
+    assert_eq!(range.get_value((1, 0)), Some(&String("name".to_string())));
+
+    // an unfrozen split pane is ignored, the first row only holds strings
+    let (header, range) = excel.worksheet_range_with_header_hint("Strings").unwrap();
+    assert_eq!(header, Some(1));
+    assert_eq!(range.start(), Some((1, 1)));
+
+    let (header, range) = excel.worksheet_range_with_header_hint("Numbers").unwrap();
+    assert_eq!(header, None);
+    assert_eq!(range.get_size(), (2, 2));
+
+    assert!(excel.worksheet_range_with_header_hint("Missing").is_err());
+}
+
+#[test]
+fn test_cell_style_format_category() {
+    use calamine::{CellStyle, FormatCategory};
+
+    let category = |format: &str| {
+        let style = CellStyle {
+            number_format: calamine::detect_custom_number_format(format),
+            format_string: Some(format.into()),
+            ..Default::default()
+        };
+        style.format_category()
+    };
+
+    assert_eq!(category("0.00%"), FormatCategory::Percent);
+    assert_eq!(category("$#,##0.00"), FormatCategory::Currency);
+    assert_eq!(category("[$€-407]#,##0.00"), FormatCategory::Currency);
+    assert_eq!(category("yyyy-mm-dd"), FormatCategory::Date);
+    assert_eq!(category("[$-409]mmmm d, yyyy"), FormatCategory::Date);
+    assert_eq!(category("@"), FormatCategory::Text);
+    assert_eq!(category("h:mm AM/PM"), FormatCategory::Time);
+    assert_eq!(category("[h]:mm:ss"), FormatCategory::Time);
+    assert_eq!(category("yyyy-mm-dd hh:mm"), FormatCategory::DateTime);
+    assert_eq!(category("0.00E+00"), FormatCategory::Scientific);
+    assert_eq!(category("# ?/?"), FormatCategory::Fraction);
+    assert_eq!(
+        category("#,##0.00_);[Red](#,##0.00)"),
+        FormatCategory::Number
+    );
+    assert_eq!(category("General"), FormatCategory::General);
+
+    let builtin_date = CellStyle {
+        number_format: CellFormat::DateTime,
+        ..Default::default()
+    };
+    assert_eq!(builtin_date.format_category(), FormatCategory::DateTime);
+    assert_eq!(
+        CellStyle::default().format_category(),
+        FormatCategory::General
+    );
+}
+
+#[test]
+fn test_xlsx_custom_properties() {
+    let mut excel: Xlsx<_> = wb("custom_properties.xlsx");
+    let properties = excel.custom_properties().unwrap();
+    assert_eq!(
+        properties,
+        [
+            (
+                "DocStatus".to_string(),
+                String("Final & approved".to_string())
+            ),
+            ("Revision".to_string(), Int(7)),
+            ("Budget".to_string(), Float(1250.5)),
+            ("Reviewed".to_string(), Bool(true)),
+            (
+                "Due".to_string(),
+                DateTimeIso("2024-03-01T00:00:00Z".to_string())
+            ),
+        ]
+    );
+
+    let mut excel: Xlsx<_> = wb("issues.xlsx");
+    assert!(excel.custom_properties().unwrap().is_empty());
+}
+
+#[test]
+fn test_xlsx_effective_cell_style() {
+    let mut excel: Xlsx<_> = wb("effective_style.xlsx");
+
+    // B1 has no style: the date format comes from its column
+    let style = excel.effective_cell_style("Sheet1", (0, 1)).unwrap();
+    assert_eq!(style.number_format, CellFormat::DateTime);
+
+    // B4 has no cell: the column style still applies
+    let style = excel.effective_cell_style("Sheet1", (3, 1)).unwrap();
+    assert_eq!(style.number_format, CellFormat::DateTime);
+
+    // B2 has its own style, which wins over the column style
+    let style = excel.effective_cell_style("Sheet1", (1, 1)).unwrap();
+    assert_eq!(style.number_format, CellFormat::Other);
+
+    // the row style wins over the column style
+    let style = excel.effective_cell_style("Sheet1", (2, 1)).unwrap();
+    assert_eq!(style.format_string.as_deref(), Some("0.0%"));
+    let style = excel.effective_cell_style("Sheet1", (2, 0)).unwrap();
+    assert_eq!(style.format_string.as_deref(), Some("0.0%"));
+
+    // A1 falls back to the default style
+    let style = excel.effective_cell_style("Sheet1", (0, 0)).unwrap();
+    assert_eq!(style.number_format, CellFormat::Other);
+    assert_eq!(style.format_string, None);
+
+    // row 4 has a style without `customFormat`, which doesn't apply
+    let style = excel.effective_cell_style("Sheet1", (3, 0)).unwrap();
+    assert_eq!(style.format_string, None);
+    let style = excel.effective_cell_style("Sheet1", (3, 2)).unwrap();
+    assert_eq!(style.format_string, None);
+}
+
+#[test]
+fn test_xlsx_read_limits() {
+    let mut excel: Xlsx<_> = wb("limits.xlsx");
+    assert_eq!(excel.worksheet_range("Sheet1").unwrap().get_size(), (50, 3));
+
+    excel.with_max_rows(10);
+    assert!(matches!(
+        excel.worksheet_range("Sheet1"),
+        Err(calamine::XlsxError::LimitExceeded {
+            limit: "max_rows",
+            max: 10
+        })
+    ));
+
+    let mut excel: Xlsx<_> = wb("limits.xlsx");
+    excel.with_max_rows(50).with_max_cols(2);
+    assert!(matches!(
+        excel.worksheet_range("Sheet1"),
+        Err(calamine::XlsxError::LimitExceeded {
+            limit: "max_cols",
+            max: 2
+        })
+    ));
+
+    let path = format!("{}/tests/limits.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut options = XlsxOptions::default();
+    options.max_decompressed_bytes = Some(1024);
+    let mut excel =
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options).unwrap();
+    assert!(matches!(
+        excel.worksheet_range("Sheet1"),
+        Err(calamine::XlsxError::LimitExceeded {
+            limit: "max_decompressed_bytes",
+            max: 1024
+        })
+    ));
+
+    let mut options = XlsxOptions::default();
+    options.max_rows = Some(50);
+    options.max_cols = Some(3);
+    options.max_decompressed_bytes = Some(1 << 20);
+    let mut excel =
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options).unwrap();
+    assert!(excel.worksheet_range("Sheet1").is_ok());
+}
+
+#[test]
+fn test_xlsx_max_decompressed_bytes_shared_strings() {
+    // the shared strings part, read when opening the file, is 135kB
+    let path = format!("{}/tests/limits_strings.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut options = XlsxOptions::default();
+    options.max_decompressed_bytes = Some(64 * 1024);
+    assert!(matches!(
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options),
+        Err(calamine::XlsxError::LimitExceeded {
+            limit: "max_decompressed_bytes",
+            max: 65536
+        })
+    ));
+
+    let mut options = XlsxOptions::default();
+    options.max_decompressed_bytes = Some(1 << 20);
+    let mut excel =
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options).unwrap();
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    assert_eq!(
+        range.get_value((0, 1)).map(|c| &c.data),
+        Some(&String("string number 3999".to_string()))
+    );
+}
+
+#[cfg(feature = "picture")]
+#[test]
+fn test_xlsx_max_decompressed_bytes_pictures() {
+    // the images, read when opening the file, are 20kB and 23kB, the largest
+    // other part is 8kB
+    let path = format!("{}/tests/picture.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut options = XlsxOptions::default();
+    options.max_decompressed_bytes = Some(16 * 1024);
+    assert!(matches!(
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options),
+        Err(calamine::XlsxError::LimitExceeded {
+            limit: "max_decompressed_bytes",
+            max: 16384
+        })
+    ));
+
+    let mut options = XlsxOptions::default();
+    options.max_decompressed_bytes = Some(32 * 1024);
+    let excel =
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options).unwrap();
+    assert_eq!(excel.pictures().map(|pics| pics.len()), Some(2));
+}
+
+#[test]
+fn test_xlsx_shared_strings_split_in_runs() {
+    let mut excel: Xlsx<_> = wb("split_runs.xlsx");
+    assert_eq!(
+        excel.shared_strings(),
+        ["Hello, World", "  leading and trailing  ", "a\tb&c"]
+    );
+
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [String("Hello, World".to_string())],
+            [String("  leading and trailing  ".to_string())],
+            [String("a\tb&c".to_string())]
+        ]
+    );
+}
+
+#[test]
+fn test_range_into_iter() {
+    let mut range: Range<Data> = Range::new((2, 1), (4, 2));
+    range.set_value((2, 1), String("a".to_string()));
+    range.set_value((3, 2), Float(1.5));
+    range.set_value((4, 1), Bool(true));
+    let expected = range.rows().map(<[Data]>::to_vec).collect::<Vec<_>>();
+
+    let rows = range.clone().into_iter();
+    assert_eq!(rows.len(), 3);
+    let rows: Vec<Vec<Data>> = rows.collect();
+    assert_eq!(rows, expected);
+    assert_eq!(
+        rows,
+        [
+            vec![String("a".to_string()), Empty],
+            vec![Empty, Float(1.5)],
+            vec![Bool(true), Empty],
+        ]
+    );
+
+    let reversed = range.into_iter().rev().collect::<Vec<_>>();
+    assert_eq!(reversed[0], [Bool(true), Empty]);
+    assert_eq!(reversed[2], [String("a".to_string()), Empty]);
+
+    let empty: Range<Data> = Range::empty();
+    assert_eq!(empty.into_iter().count(), 0);
+}
+
+#[test]
+fn test_xlsx_errors_as() {
+    use calamine::ErrorPolicy;
+
+    let mut excel: Xlsx<_> = wb("errors.xlsx");
+    excel.with_errors_as(ErrorPolicy::Keep);
+    let range = excel.worksheet_range("Feuil1").unwrap();
+    assert_eq!(range.get_value((6, 0)).unwrap(), &Error(NA));
+
+    excel.with_errors_as(ErrorPolicy::Empty);
+    let range = excel.worksheet_range("Feuil1").unwrap();
+    assert!(range.is_empty());
+
+    excel.with_errors_as(ErrorPolicy::String);
+    let range = excel.worksheet_range("Feuil1").unwrap();
+    range_eq!(
+        range,
+        [
+            [String("#DIV/0!".to_string())],
+            [String("#NAME?".to_string())],
+            [String("#VALUE!".to_string())],
+            [String("#NULL!".to_string())],
+            [String("#REF!".to_string())],
+            [String("#NUM!".to_string())],
+            [String("#N/A".to_string())]
+        ]
+    );
+
+    let mut excel: Xlsx<_> = wb("worksheet_profile.xlsx");
+    excel.with_errors_as(ErrorPolicy::Empty);
+    let profile = excel.worksheet_profile("Sheet1").unwrap();
+    assert_eq!((profile[3].errors, profile[3].empty), (0, 2));
+}
+
+#[test]
+fn test_xlsx_row_spans() {
+    let mut excel: Xlsx<_> = wb("row_spans.xlsx");
+    let mut reader = excel.worksheet_cells_reader("Sheet1").unwrap();
+
+    // the parsed (first, last) columns of each row, with its declared span
+    let mut rows = Vec::new();
+    while let Some(cell) = reader.next_cell().unwrap() {
+        let (row, col) = cell.get_position();
+        let spans = reader.row_spans();
+        match rows.get_mut(row as usize) {
+            Some((_, last, _)) => *last = col,
+            None => rows.push((col, col, spans)),
+        }
+    }
+
+    assert_eq!(
+        rows,
+        [
+            (0, 2, Some((0, 2))),
+            (1, 3, Some((1, 3))),
+            (0, 0, None),
+            (0, 5, Some((0, 5))),
+        ]
+    );
+}
+
+#[test]
+fn test_range_find_table() {
+    let mut range: Range<Data> = Range::new((1, 1), (9, 7));
+    // two title rows
+    range.set_value((1, 1), String("Quarterly sales".to_string()));
+    range.set_value((2, 1), String("Generated on Monday".to_string()));
+    // the table, with a header cell left of the keywords
+    for (col, header) in ["Id", "Sale date", "Region", "Total amount"]
+        .iter()
+        .enumerate()
+    {
+        range.set_value((4, 2 + col as u32), String(header.to_string()));
+    }
+    for row in 5..8 {
+        range.set_value((row, 2), Int(row as i64));
+        range.set_value((row, 3), String("2024-01-01".to_string()));
+        range.set_value((row, 5), Float(1.5));
+    }
+    // a value beyond the header width, still adjacent to the table
+    range.set_value((6, 6), String("note".to_string()));
+    // separated by an empty row and column, not part of the table
+    range.set_value((9, 3), String("Footer".to_string()));
+    range.set_value((1, 7), String("aside".to_string()));
+
+    assert_eq!(
+        range.find_table(&["DATE", "amount"]),
+        Some(Dimensions::new((4, 2), (7, 6)))
+    );
+    assert_eq!(
+        range.find_table(&["region"]),
+        Some(Dimensions::new((4, 2), (7, 6)))
+    );
+    assert_eq!(range.find_table(&["date", "missing"]), None);
+    assert_eq!(range.find_table(&[]), None);
+}
+
+#[test]
+fn test_xlsx_worksheet_rich_values() {
+    let mut excel: Xlsx<_> = wb("rich_values.xlsx");
+    let values = excel.worksheet_rich_values("Sheet1").unwrap();
+
+    let mut positions = values.keys().copied().collect::<Vec<_>>();
+    positions.sort();
+    assert_eq!(positions, [(1, 0), (2, 0)]);
+
+    let msft = &values[&(1, 0)];
+    assert_eq!(msft.len(), 8);
+    assert_eq!(msft["Ticker symbol"], String("MSFT".to_string()));
+    assert_eq!(msft["Name"], String("Microsoft Corporation".to_string()));
+    assert_eq!(msft["Price"], Float(415.5));
+    assert_eq!(msft["%EntityServiceId"], Int(268435456));
+    assert_eq!(msft["%IsRefreshable"], Bool(true));
+
+    let aapl = &values[&(2, 0)];
+    assert_eq!(aapl["Ticker symbol"], String("AAPL".to_string()));
+    assert_eq!(aapl["Price"], Float(189.25));
+
+    // in-cell images are rich values too
+    let mut excel: Xlsx<_> = wb("cell_images.xlsx");
+    let values = excel.worksheet_rich_values("Sheet1").unwrap();
+    assert_eq!(values[&(2, 1)]["Text"], String("blue square".to_string()));
+    assert!(excel.worksheet_rich_values("Sheet2").unwrap().is_empty());
+}
+
+#[test]
+fn test_xlsx_worksheet_formula_r1c1() {
+    let mut excel: Xlsx<_> = wb("formula_r1c1.xlsx");
+    let formulas = excel.worksheet_formula_r1c1("Sheet1").unwrap();
+    assert_eq!(formulas.start(), Some((2, 2)));
+    assert_eq!(
+        formulas.get_value((2, 2)).map(|f| f.as_str()),
+        Some("R[-2]C[-2]+R2C2")
+    );
+    assert_eq!(
+        formulas.get_value((2, 3)).map(|f| f.as_str()),
+        Some("SUM(R1C[-3]:R[-1]C2)&\"A1\"")
+    );
+    // sheet and function names are not cell names
+    assert_eq!(
+        formulas.get_value((2, 4)).map(|f| f.as_str()),
+        Some("Sheet2!R[-2]C[-4]+LOG10(R[-2]C[-4])+Sheet2!R2C2")
+    );
+    assert_eq!(
+        formulas.get_value((2, 5)).map(|f| f.as_str()),
+        Some("ATAN2(R[-2]C[-5],R[-2]C[-4])+DAYS360(R[-2]C[-5],R[-2]C[-4])")
+    );
+    // shared formulas read the same in R1C1 notation
+    assert_eq!(
+        formulas.get_value((3, 2)).map(|f| f.as_str()),
+        Some("R[-1]C*2")
+    );
+    assert_eq!(
+        formulas.get_value((4, 2)).map(|f| f.as_str()),
+        Some("R[-1]C*2")
+    );
+}
+
+#[test]
+fn test_xlsx_find_text() {
+    let mut excel: Xlsx<_> = wb("find_text.xlsx");
+    // B2 is the number 7 displayed as "Hello 7"
+    assert_eq!(
+        excel.find_text("hello", false, None).unwrap(),
+        vec![
+            ("Sheet1".to_string(), 0, 0),
+            ("Sheet1".to_string(), 1, 1),
+            ("Sheet2".to_string(), 2, 2),
+        ]
+    );
+    assert_eq!(
+        excel.find_text("HELLO", true, None).unwrap(),
+        vec![("Sheet2".to_string(), 2, 2)]
+    );
+    assert_eq!(
+        excel.find_text("hello", false, Some(2)).unwrap(),
+        vec![("Sheet1".to_string(), 0, 0), ("Sheet1".to_string(), 1, 1)]
+    );
+    assert_eq!(
+        excel.find_text("true", false, None).unwrap(),
+        vec![("Sheet2".to_string(), 3, 0)]
+    );
+    assert!(excel.find_text("missing", false, None).unwrap().is_empty());
+}
+
+#[test]
+fn test_xlsx_relationships() {
+    let mut excel: Xlsx<_> = wb("temperature-table.xlsx");
+    let rels = excel.relationships("xl/worksheets/sheet1.xml").unwrap();
+    assert_eq!(rels.len(), 1);
+    let table = &rels["rId1"];
+    assert!(table.rel_type.ends_with("/relationships/table"));
+    assert_eq!(table.target, "xl/tables/table1.xml");
+    assert!(!table.is_external());
+
+    let rels = excel.relationships("xl/workbook.xml").unwrap();
+    assert!(rels
+        .values()
+        .any(|r| r.rel_type.ends_with("/worksheet") && r.target == "xl/worksheets/sheet1.xml"));
+
+    let rels = excel.relationships("").unwrap();
+    assert!(rels.values().any(|r| r.target == "xl/workbook.xml"));
+
+    assert!(excel.relationships("xl/styles.xml").unwrap().is_empty());
+
+    let mut excel: Xlsx<_> = wb("relationships.xlsx");
+    let rels = excel.relationships("xl/worksheets/sheet1.xml").unwrap();
+    let link = &rels["rId1"];
+    assert!(link.is_external());
+    assert_eq!(link.target, "https://example.com/a?b=1&c=2");
+}
+
+#[test]
+fn test_float_format() {
+    use calamine::FloatFormat;
+
+    let mut excel: Xlsx<_> = wb("float_format.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    let column = |float_format: FloatFormat| {
+        range
+            .rows()
+            .map(|row| match row[0].data {
+                Float(f) => float_format.format(f, row[0].formatting.as_ref(), excel.is_1904()),
+                ref data => panic!("expected a float, got {data:?}"),
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        column(FloatFormat::default()),
+        ["3.0000000001", "0.12549", "2.5", "0.1234"]
+    );
+    assert_eq!(
+        column(FloatFormat::Fixed(2)),
+        ["3.00", "0.13", "2.50", "0.12"]
+    );
+    // A4 has the builtin `0.00%` format
+    assert_eq!(
+        column(FloatFormat::Excel),
+        ["3.000", "12.5%", "2.5", "12.34%"]
+    );
+
+    // date formats depend on the date system of the workbook
+    let date = calamine::CellStyle {
+        format_string: Some("yyyy-mm-dd".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        FloatFormat::Excel.format(45292., Some(&date), false),
+        "2024-01-01"
+    );
+    assert_eq!(
+        FloatFormat::Excel.format(45292., Some(&date), true),
+        "2028-01-02"
+    );
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_range_to_ndarray_f64() {
+    let mut range = Range::new((1, 1), (3, 3));
+    for row in 1..=3 {
+        for col in 1..=3 {
+            range.set_value((row, col), Float((row * 10 + col) as f64));
+        }
+    }
+    range.set_value((2, 2), Int(-4));
+    range.set_value((3, 1), String("x".to_string()));
+    range.set_value((3, 3), Empty);
+
+    let matrix = range.to_ndarray_f64(f64::NAN);
+    assert_eq!(matrix.shape(), &[3, 3]);
+    assert_eq!(matrix.row(0).to_vec(), [11., 12., 13.]);
+    assert_eq!(matrix.row(1).to_vec(), [21., -4., 23.]);
+    assert!(matrix[[2, 0]].is_nan());
+    assert_eq!(matrix[[2, 1]], 32.);
+    assert!(matrix[[2, 2]].is_nan());
+    assert_eq!(
+        range.to_ndarray_f64(0.).sum(),
+        11. + 12. + 13. + 21. - 4. + 23. + 32.
+    );
+
+    assert_eq!(Range::<Data>::empty().to_ndarray_f64(0.).shape(), &[0, 0]);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_range_to_json_rows() {
+    use serde_json::json;
+
+    let mut range = Range::new((1, 1), (2, 2));
+    range.set_value((1, 1), Int(7));
+    range.set_value((1, 2), String("text".to_string()));
+    range.set_value((2, 1), Bool(true));
+    range.set_value((2, 2), Error(NA));
+    assert_eq!(
+        range.to_json_rows(),
+        [[json!(7), json!("text")], [json!(true), json!("#N/A")]]
+    );
+
+    range.set_value((1, 1), Float(f64::NAN));
+    range.set_value((2, 2), Empty);
+    assert_eq!(
+        range.to_json_rows(),
+        [[json!(null), json!("text")], [json!(true), json!(null)]]
+    );
+
+    let mut range = Range::new((0, 0), (0, 1));
+    range.set_value(
+        (0, 0),
+        DateTime(ExcelDateTime::new(
+            44197.5,
+            ExcelDateTimeType::DateTime,
+            false,
+        )),
+    );
+    range.set_value(
+        (0, 1),
+        DateTime(ExcelDateTime::new(1.5, ExcelDateTimeType::TimeDelta, false)),
+    );
+    #[cfg(feature = "dates")]
+    assert_eq!(
+        range.to_json_rows(),
+        [[json!("2021-01-01T12:00:00"), json!("PT129600S")]]
+    );
+    #[cfg(not(feature = "dates"))]
+    assert_eq!(range.to_json_rows(), [[json!(44197.5), json!(1.5)]]);
+
+    assert!(Range::<Data>::empty().to_json_rows().is_empty());
+}
+
+#[test]
+fn test_format_value() {
+    use calamine::format_value;
+
+    let currency = "\"$\"#,##0.00;[Red]\\-\"$\"#,##0.00";
+    assert_eq!(format_value(&Float(1234.5), currency, false), "$1,234.50");
+    assert_eq!(format_value(&Int(-12), currency, false), "-$12.00");
+
+    let date = "dd/mm/yyyy";
+    assert_eq!(format_value(&Float(45000.), date, false), "15/03/2023");
+    assert_eq!(format_value(&Float(45000.), date, true), "16/03/2027");
+    let iso = DateTimeIso("2023-03-15T00:00:00".to_string());
+    assert_eq!(format_value(&iso, date, false), "15/03/2023");
+
+    assert_eq!(format_value(&Float(0.0425), "0.0%", false), "4.3%");
+    assert_eq!(format_value(&Float(1.), "0%", false), "100%");
+
+    assert_eq!(
+        format_value(&String("text".to_string()), "0.0%", false),
+        "text"
+    );
+}
+
+#[test]
+fn test_xlsx_named_styles() {
+    use calamine::NamedStyle;
+
+    let mut excel: Xlsx<_> = wb("named_styles.xlsx");
+    assert_eq!(
+        excel.named_styles(),
+        [
+            NamedStyle {
+                name: "Heading 1".to_string(),
+                xf_id: 1,
+                builtin_id: Some(16),
+            },
+            NamedStyle {
+                name: "Normal".to_string(),
+                xf_id: 0,
+                builtin_id: Some(0),
+            },
+            NamedStyle {
+                name: "Rate & Share".to_string(),
+                xf_id: 2,
+                builtin_id: None,
+            },
+        ]
+    );
+
+    let style_name = |excel: &mut Xlsx<_>, pos| {
+        excel
+            .cell_named_style("Sheet1", pos)
+            .unwrap()
+            .map(|s| s.name.clone())
+    };
+    assert_eq!(style_name(&mut excel, (0, 0)).as_deref(), Some("Heading 1"));
+    assert_eq!(style_name(&mut excel, (1, 0)).as_deref(), Some("Normal"));
+    assert_eq!(
+        style_name(&mut excel, (1, 1)).as_deref(),
+        Some("Rate & Share")
+    );
+    // cells without a `<c>` element get the default format
+    assert_eq!(style_name(&mut excel, (5, 5)).as_deref(), Some("Normal"));
+}
+
+#[test]
+fn test_xlsx_worksheet_range_raw_and_formatted() {
+    let mut excel: Xlsx<_> = wb("worksheet_text.xlsx");
+    let range = excel.worksheet_range_raw_and_formatted("Sheet1").unwrap();
+
+    assert_eq!(range.start(), Some((0, 0)));
+    assert_eq!(range.end(), Some((2, 2)));
+    let (date, text) = range.get((0, 0)).unwrap();
+    assert_eq!(
+        date,
+        &DateTime(ExcelDateTime::new(
+            44377.,
+            ExcelDateTimeType::DateTime,
+            false
+        ))
+    );
+    assert_eq!(text, "6/30/2021");
+    assert_eq!(
+        range.get((1, 0)),
+        Some(&(Float(0.256), "25.60%".to_string()))
+    );
+    assert_eq!(range.get((2, 1)), Some(&(Empty, "".to_string())));
+    assert_eq!(
+        range.get((2, 2)),
+        Some(&(String("text".to_string()), "text".to_string()))
+    );
+}
+
+#[test]
+fn test_xlsx_worksheet_layout() {
+    use calamine::{PaneState, SheetPane};
+
+    let mut excel: Xlsx<_> = wb("sheet_layout.xlsx");
+    let layout = excel.worksheet_layout("Sheet1").unwrap();
+
+    let columns = layout.column_widths.find_definitions_for_column(3);
+    assert_eq!(columns.len(), 1);
+    assert_eq!(columns[0].width, Some(20.5));
+    let row = layout.row_definitions.find_definition_for_row(1).unwrap();
+    assert_eq!(row.height, Some(30.));
+    assert_eq!(
+        layout.column_widths.sheet_format.default_row_height,
+        Some(15.)
+    );
+    assert_eq!(
+        layout.merged_cells,
+        vec![
+            Dimensions::new((0, 0), (0, 2)),
+            Dimensions::new((1, 1), (1, 2))
+        ]
+    );
+    assert_eq!(
+        layout.pane,
+        Some(SheetPane {
+            x_split: 1.,
+            y_split: 2.,
+            top_left_cell: Some((2, 1)),
+            state: PaneState::Frozen,
+        })
+    );
+
+    let layout = excel.worksheet_layout("Sheet2").unwrap();
+    assert!(layout.merged_cells.is_empty());
+    assert!(layout.pane.is_none());
+
+    // invalid pane attributes are skipped
+    let layout = excel.worksheet_layout("Sheet3").unwrap();
+    assert_eq!(
+        layout.pane,
+        Some(SheetPane {
+            x_split: 0.,
+            y_split: 2.5,
+            top_left_cell: None,
+            state: PaneState::Frozen,
+        })
+    );
+    let range = excel.worksheet_range("Sheet3").unwrap();
+    assert_eq!(range.get_value((0, 0)).map(|c| &c.data), Some(&Float(1.)));
+
+    let mut excel: Xlsx<_> = wb("merged_range.xlsx");
+    let merge_cells = excel.worksheet_merge_cells("Sheet1").unwrap().unwrap();
+    assert!(!merge_cells.is_empty());
+    let layout = excel.worksheet_layout("Sheet1").unwrap();
+    assert_eq!(layout.merged_cells, merge_cells);
+}
+
+#[test]
+fn test_xlsx_worksheet_range_limited() {
+    let mut excel: Xlsx<_> = wb("range_limited.xlsx");
+    let full = excel.worksheet_range_at_index(0).unwrap();
+    assert_eq!(full.height(), 38);
+
+    // rows 2 to 6, row 4 being empty
+    let range = excel.worksheet_range_limited("Sheet1", 5).unwrap();
+    assert_eq!(range.start(), Some((1, 1)));
+    assert_eq!(range.end(), Some((5, 7)));
+    assert_eq!(range.get_value((2, 1)), Some(&Float(3.)));
+    assert_eq!(range.get_value((3, 1)), Some(&Empty));
+    assert_eq!(range.get_value((5, 2)), Some(&Float(60.)));
+    assert_eq!(range.get_value((5, 7)), Some(&Float(1.)));
+
+    let range = excel.worksheet_range_limited("Sheet1", 100).unwrap();
+    assert_eq!(range.height(), 38);
+    let range = excel.worksheet_range_limited("Sheet1", 0).unwrap();
+    assert!(range.is_empty());
+}
+
+#[test]
+fn test_xlsx_worksheet_pictures_alt_text() {
+    let mut excel: Xlsx<_> = wb("picture_alt_text.xlsx");
+    let pictures = excel.worksheet_pictures("Sheet1").unwrap();
+
+    // the shape between the first two pictures is skipped
+    assert_eq!(pictures.len(), 3);
+    assert_eq!(pictures[0].name, "Picture 1");
+    assert_eq!(pictures[0].alt_text.as_deref(), Some("Blue square & logo"));
+    assert_eq!(pictures[0].from, (2, 1));
+    assert_eq!(pictures[0].path, "xl/media/image1.png");
+    assert!(pictures[0].data.starts_with(b"\x89PNG"));
+    assert_eq!(pictures[1].name, "Picture 3");
+    assert_eq!(pictures[1].alt_text, None);
+    assert_eq!(pictures[1].from, (10, 0));
+    assert_eq!(pictures[1].path, "xl/media/image2.png");
+
+    assert!(excel.worksheet_pictures("Sheet2").unwrap().is_empty());
+
+    let mut excel: Xlsx<_> = wb("picture.xlsx");
+    let pictures = excel.worksheet_pictures("Sheet1").unwrap();
+    assert_eq!(pictures.len(), 1);
+    assert_eq!(pictures[0].alt_text, None);
+    assert_eq!(pictures[0].path, "xl/media/image1.jpg");
+}
+
+// with the `picture` feature, images are already read when opening the file
+#[cfg(not(feature = "picture"))]
+#[test]
+fn test_xlsx_worksheet_pictures_max_decompressed_bytes() {
+    // the image of the first sheet is 20kB, the largest other part is 8kB
+    let path = format!("{}/tests/picture.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut options = XlsxOptions::default();
+    options.max_decompressed_bytes = Some(16 * 1024);
+    let mut excel =
+        Xlsx::new_with_options(BufReader::new(File::open(&path).unwrap()), options).unwrap();
+    assert!(matches!(
+        excel.worksheet_pictures("Sheet1"),
+        Err(calamine::XlsxError::LimitExceeded {
+            limit: "max_decompressed_bytes",
+            max: 16384
+        })
+    ));
+}
+
+#[test]
+fn test_xlsx_worksheet_pictures_anchor() {
+    use calamine::AnchorKind;
+
+    let mut excel: Xlsx<_> = wb("picture_alt_text.xlsx");
+    let pictures = excel.worksheet_pictures("Sheet1").unwrap();
+
+    // two cell anchor, spanning B3:D7
+    assert_eq!(pictures[0].anchor_kind, AnchorKind::TwoCell);
+    assert_eq!(pictures[0].from, (2, 1));
+    assert_eq!(pictures[0].from_offset, (0, 0));
+    assert_eq!(pictures[0].to, Some((6, 3)));
+    assert_eq!(pictures[0].to_offset, (0, 0));
+
+    assert_eq!(pictures[0].extent, None);
+    assert_eq!(pictures[0].position, None);
+
+    // one cell anchor, pinned to A11, 100x50 pixels
+    assert_eq!(pictures[1].anchor_kind, AnchorKind::OneCell);
+    assert_eq!(pictures[1].from, (10, 0));
+    assert_eq!(pictures[1].to, None);
+    assert_eq!(pictures[1].extent, Some((952_500, 476_250)));
+    assert_eq!(pictures[1].position, None);
+
+    // absolute anchor, 200 pixels right and 10 pixels down of A1
+    assert_eq!(pictures[2].anchor_kind, AnchorKind::Absolute);
+    assert_eq!(pictures[2].from, (0, 0));
+    assert_eq!(pictures[2].to, None);
+    assert_eq!(pictures[2].extent, Some((190_500, 285_750)));
+    assert_eq!(pictures[2].position, Some((1_905_000, 95_250)));
+
+    let mut excel: Xlsx<_> = wb("picture.xlsx");
+    let pictures = excel.worksheet_pictures("Sheet1").unwrap();
+    assert_eq!(pictures[0].anchor_kind, AnchorKind::TwoCell);
+    assert_eq!(pictures[0].to, Some((29, 7)));
+    assert_eq!(pictures[0].to_offset, (45720, 579120));
+}
+
+#[test]
+fn test_range_sort_by_column_excel_order() {
+    let s = |v: &str| String(v.to_string());
+    let date = DateTime(ExcelDateTime::new(
+        45000.,
+        ExcelDateTimeType::DateTime,
+        false,
+    ));
+    let column = vec![
+        s("banana"),
+        Error(Value),
+        Int(3),
+        Empty,
+        Bool(false),
+        s("Apple"),
+        Float(-1.5),
+        Error(Div0),
+        Bool(true),
+        date.clone(),
+        s("apple"),
+        Float(3.),
+        s("10"),
+    ];
+    let rows = column
+        .iter()
+        .enumerate()
+        .map(|(i, v)| vec![Int(i as i64), v.clone()])
+        .collect();
+    let mut range = Range::from_2d_vec(rows);
+
+    // order of the same column sorted A to Z in Excel, text numbers being text
+    let excel_order = vec![
+        Float(-1.5),
+        Int(3),
+        Float(3.),
+        date,
+        s("10"),
+        s("Apple"),
+        s("apple"),
+        s("banana"),
+        Bool(false),
+        Bool(true),
+        Error(Div0),
+        Error(Value),
+        Empty,
+    ];
+    range.sort_by_column(1, true);
+    let sorted: Vec<_> = range.column(1).into_iter().cloned().collect();
+    assert_eq!(sorted, excel_order);
+    // whole rows are moved
+    assert_eq!(range.get((0, 0)), Some(&Int(6)));
+
+    let mut values = column.clone();
+    values.sort();
+    assert_eq!(values, excel_order);
+
+    range.sort_by_column(1, false);
+    let sorted: Vec<_> = range.column(1).into_iter().cloned().collect();
+    let mut expected = excel_order[..12].to_vec();
+    expected.reverse();
+    expected.push(Empty);
+    assert_eq!(sorted, expected);
+
+    // a column outside of the range leaves it unchanged
+    range.sort_by_column(5, true);
+    assert_eq!(
+        range.column(1).into_iter().cloned().collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+fn test_range_merged_header_names() {
+    // A1:A2 "Region", B1:E1 "2023" over Q1 to Q4, F1:G1 2024 over Q1 and Q2,
+    // H1:H2 "Total" and an unnamed column I
+    let mut range: Range<Data> = Range::new((0, 0), (2, 8));
+    range.set_value((0, 0), String("Region".to_string()));
+    range.set_value((0, 1), String("2023".to_string()));
+    range.set_value((0, 5), Int(2024));
+    range.set_value((0, 7), String("Total".to_string()));
+    for (col, quarter) in ["Q1", "Q2", "Q3", "Q4", "Q1", "Q2"].iter().enumerate() {
+        range.set_value((1, col as u32 + 1), String(quarter.to_string()));
+    }
+    range.set_value((2, 0), String("North".to_string()));
+    let merges = [
+        Dimensions::new((0, 0), (1, 0)),
+        Dimensions::new((0, 1), (0, 4)),
+        Dimensions::new((0, 5), (0, 6)),
+        Dimensions::new((0, 7), (1, 7)),
+    ];
+
+    assert_eq!(
+        range.merged_header_names(0..2, &merges),
+        ["Region", "2023.Q1", "2023.Q2", "2023.Q3", "2023.Q4", "2024.Q1", "2024.Q2", "Total", ""]
+    );
+
+    // a single header row only uses the anchor values
+    assert_eq!(
+        range.merged_header_names(0..1, &merges),
+        ["Region", "2023", "2023", "2023", "2023", "2024", "2024", "Total", ""]
+    );
+
+    // the second row alone still sees the vertically merged headers
+    assert_eq!(
+        range.merged_header_names(1..2, &merges)[..2],
+        ["Region", "Q1"]
+    );
+
+    // header rows past the range end are ignored
+    assert_eq!(
+        range.merged_header_names(0..10, &merges)[..2],
+        ["Region.North", "2023.Q1"]
+    );
+    assert!(Range::<Data>::empty()
+        .merged_header_names(0..2, &merges)
+        .is_empty());
+}
+
+#[test]
+fn test_xlsx_worksheet_protected_ranges() {
+    use calamine::ProtectedRange;
+
+    let mut excel: Xlsx<_> = wb("protected_ranges.xlsx");
+    let ranges = excel.worksheet_protected_ranges("Sheet1").unwrap();
+    assert_eq!(
+        ranges,
+        [
+            ProtectedRange {
+                name: "Inputs & notes".to_string(),
+                sqref: vec![
+                    Dimensions::new((0, 0), (9, 1)),
+                    Dimensions::new((3, 3), (3, 3)),
+                ],
+                password_hash: None,
+                algorithm_name: Some("SHA-512".to_string()),
+                hash_value: Some("cmFuZ2U=".to_string()),
+                salt_value: Some("cGVwcGVy".to_string()),
+                spin_count: Some(100_000),
+                security_descriptor: None,
+            },
+            ProtectedRange {
+                name: "Ids".to_string(),
+                // the invalid `B1:1X` area is skipped
+                sqref: vec![Dimensions::new((0, 0), (1_048_575, 0))],
+                ..Default::default()
+            }
+        ]
+    );
+
+    assert!(excel
+        .worksheet_protected_ranges("Sheet2")
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        excel.worksheet_protected_ranges("Missing"),
+        Err(calamine::XlsxError::WorksheetNotFound(_))
+    ));
+}
+
+#[test]
+fn test_xlsx_worksheet_events() {
+    use calamine::SheetEvent;
+
+    let mut excel: Xlsx<_> = wb("sheet_events.xlsx");
+    // row 2 only has an empty styled cell
+    let events: Vec<_> = excel
+        .worksheet_events("Sheet1")
+        .unwrap()
+        .map(|event| match event.unwrap() {
+            SheetEvent::RowStart(row) => format!("start {row}"),
+            SheetEvent::Cell(cell) => {
+                format!("cell {:?} {:?}", cell.get_position(), cell.get_value())
+            }
+            SheetEvent::RowEnd(row) => format!("end {row}"),
+            SheetEvent::End => "end".to_string(),
+        })
+        .collect();
+    assert_eq!(
+        events,
+        [
+            "start 0",
+            "cell (0, 0) String(\"name\")",
+            "cell (0, 1) Float(1.5)",
+            "end 0",
+            "start 2",
+            "cell (2, 1) Bool(true)",
+            "end 2",
+            "end",
+        ]
+    );
+
+    let events: Vec<_> = excel.worksheet_events("Sheet2").unwrap().collect();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], Ok(SheetEvent::End)));
+    assert!(excel.worksheet_events("Missing").is_err());
+}
+
+#[test]
+fn test_xlsx_worksheet_view() {
+    use calamine::{PaneState, SheetPane, SheetView, SheetViewType};
+
+    let mut excel: Xlsx<_> = wb("sheet_view.xlsx");
+    // gridlines and headings hidden, frozen first row
+    assert_eq!(
+        excel.worksheet_view("Sheet1").unwrap(),
+        SheetView {
+            show_grid_lines: false,
+            show_row_col_headers: false,
+            zoom_scale: 85,
+            view_type: SheetViewType::PageBreakPreview,
+            pane: Some(SheetPane {
+                x_split: 0.,
+                y_split: 1.,
+                top_left_cell: Some((1, 0)),
+                state: PaneState::Frozen,
+            }),
+        }
+    );
+
+    let view = excel.worksheet_view("Sheet2").unwrap();
+    assert_eq!(view, SheetView::default());
+    assert!(view.show_grid_lines);
+    assert_eq!(view.zoom_scale, 100);
+    assert_eq!(
+        excel.worksheet_view("Sheet3").unwrap(),
+        SheetView::default()
+    );
+    // invalid zooms are skipped
+    assert_eq!(
+        excel.worksheet_view("Sheet4").unwrap(),
+        SheetView::default()
+    );
+    let view = excel.worksheet_view("Sheet5").unwrap();
+    assert_eq!(view.zoom_scale, 100);
+    assert!(!view.show_grid_lines);
+    assert!(excel.worksheet_range("Sheet5").is_ok());
+    assert!(excel.worksheet_view("Missing").is_err());
+}
+
+#[test]
+fn test_xlsx_worksheet_density() {
+    let mut excel: Xlsx<_> = wb("density.xlsx");
+    // 22 values in the declared A1:J100, the styled empty cell isn't counted
+    assert_eq!(excel.worksheet_density("Sheet1").unwrap(), 0.022);
+    // no `<dimension>`, 3 values in the used area B2:C3
+    assert_eq!(excel.worksheet_density("Sheet2").unwrap(), 0.75);
+    assert_eq!(
+        excel.worksheet_dimension("Sheet2").unwrap(),
+        Dimensions::new((1, 1), (2, 2))
+    );
+    assert_eq!(excel.worksheet_density("Sheet3").unwrap(), 0.);
+    assert!(excel.worksheet_density("Missing").is_err());
+}
+
+#[test]
+fn test_xlsx_numeric_error_codes() {
+    use calamine::ErrorPolicy;
+
+    let mut excel: Xlsx<_> = wb("error_codes.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [Error(Div0), Error(NA), Error(Ref)],
+            [Error(Div0), Empty, Empty],
+        ]
+    );
+
+    // codes are read as the Excel error text
+    excel.with_errors_as(ErrorPolicy::String);
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [
+                String("#DIV/0!".to_string()),
+                String("#N/A".to_string()),
+                String("#REF!".to_string())
+            ],
+            [String("#DIV/0!".to_string()), Empty, Empty],
+        ]
+    );
+}
+
+#[test]
+fn test_range_windows() {
+    // 5 rows, 2 columns, starting at B3
+    let mut range: Range<Data> = Range::new((2, 1), (6, 2));
+    for row in 2..=6 {
+        range.set_value((row, 1), Int(row as i64));
+    }
+
+    let windows: Vec<_> = range.windows(3, false).collect();
+    assert_eq!(windows.len(), 3);
+    for (i, window) in windows.iter().enumerate() {
+        let first = 2 + i as u32;
+        let value = first as i64;
+        assert_eq!(window.start(), Some((first, 1)));
+        assert_eq!(window.end(), Some((first + 2, 2)));
+        assert_eq!(
+            window.column(1),
+            [&Int(value), &Int(value + 1), &Int(value + 2)]
+        );
+    }
+
+    // partial windows at the tail
+    let heights: Vec<_> = range.windows(3, true).map(|w| w.height()).collect();
+    assert_eq!(heights, [3, 3, 3, 2, 1]);
+    let last = range.windows(3, true).last().unwrap();
+    assert_eq!(last.start(), Some((6, 1)));
+    assert_eq!(last.end(), Some((6, 2)));
+
+    assert_eq!(range.windows(6, false).count(), 0);
+    assert_eq!(range.windows(6, true).count(), 5);
+    assert_eq!(range.windows(0, true).count(), 0);
+    assert_eq!(Range::<Data>::empty().windows(3, true).count(), 0);
+}
+
+#[test]
+fn test_xlsx_table_styles() {
+    use calamine::{TableStyle, TableStyleElement, TableStyleInfo};
+
+    let mut xls: Xlsx<_> = wb("temperature-table.xlsx");
+    xls.load_tables().unwrap();
+    let table = xls.table_by_name("Temperature").unwrap();
+    assert_eq!(table.style_name(), Some("TableStyleMedium2"));
+    assert!(xls.table_styles().is_empty());
+    assert_eq!(xls.default_table_style(), Some("TableStyleMedium2"));
+    assert_eq!(xls.default_pivot_style(), Some("PivotStyleLight16"));
+
+    let mut xls: Xlsx<_> = wb("table_styles.xlsx");
+    xls.load_tables().unwrap();
+    let table = xls.table_by_name_ref("Stock").unwrap();
+    assert_eq!(
+        table.style(),
+        Some(&TableStyleInfo {
+            name: Some("Banded".to_string()),
+            show_first_column: true,
+            show_last_column: false,
+            show_row_stripes: true,
+            show_column_stripes: false,
+        })
+    );
+    assert_eq!(xls.default_table_style(), Some("TableStyleMedium2"));
+    assert_eq!(xls.default_pivot_style(), Some("PivotStyleLight16"));
+    assert_eq!(
+        xls.table_styles(),
+        [TableStyle {
+            name: "Banded".to_string(),
+            pivot: false,
+            table: true,
+            elements: vec![
+                TableStyleElement {
+                    element_type: "headerRow".to_string(),
+                    dxf_id: Some(0),
+                    size: 1,
+                },
+                TableStyleElement {
+                    element_type: "firstRowStripe".to_string(),
+                    dxf_id: Some(1),
+                    size: 2,
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn test_xlsx_shared_formula_quoted_sheet() {
+    let mut excel: Xlsx<_> = wb("shared_formula_quoted_sheet.xlsx");
+    let formulas = excel.worksheet_formula("Report").unwrap();
+    range_eq!(
+        formulas,
+        [
+            ["'Q1 Sales'!A1*2".to_string()],
+            ["'Q1 Sales'!A2*2".to_string()],
+            ["'Q1 Sales'!A3*2".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_xlsx_style_apply_flags() {
+    use calamine::ApplyFlags;
+
+    let mut excel: Xlsx<_> = wb("apply_flags.xlsx");
+
+    // A1 references a red fill but doesn't apply it
+    let style = excel.effective_cell_style("Sheet1", (0, 0)).unwrap();
+    assert!(style.fill.is_some());
+    assert_eq!(
+        excel.cell_apply_flags("Sheet1", (0, 0)).unwrap(),
+        ApplyFlags {
+            font: Some(true),
+            fill: Some(false),
+            protection: Some(true),
+            ..Default::default()
+        }
+    );
+
+    assert_eq!(
+        excel.cell_apply_flags("Sheet1", (0, 1)).unwrap(),
+        ApplyFlags::default()
+    );
+}
+
+#[test]
+fn test_xlsx_text_format_as_string() {
+    let mut excel: Xlsx<_> = wb("text_format.xlsx");
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    assert_eq!(range.get_value((1, 0)).unwrap().get_data(), &Float(90210.));
+    assert_eq!(range.get_value((3, 0)).unwrap().get_data(), &Float(2134.));
+
+    // ZIP codes typed as numbers in the text column are read as displayed
+    excel.with_text_format_as_string(true);
+    let range = excel.worksheet_range("Sheet1").unwrap();
+    range_eq!(
+        range,
+        [
+            [String("zip".to_string()), String("count".to_string())],
+            [String("90210".to_string()), Float(1.)],
+            [String("01234".to_string()), Float(2.5)],
+            [String("2134".to_string()), Float(3.)],
+        ]
+    );
+}
+
+#[test]
+fn test_xlsx_diff_workbook() {
+    use calamine::{CellDiff, WorkbookDiff};
+
+    let mut excel: Xlsx<_> = wb("diff_workbook.xlsx");
+    assert_eq!(
+        excel.diff_workbook(&mut wb("diff_workbook.xlsx")).unwrap(),
+        []
+    );
+
+    let mut modified: Xlsx<_> = wb("diff_workbook_modified.xlsx");
+    let diffs = excel.diff_workbook(&mut modified).unwrap();
+    let cell = |pos, left, right| WorkbookDiff::Cell {
+        sheet: "Data".to_string(),
+        diff: CellDiff { pos, left, right },
+    };
+    assert_eq!(
+        diffs,
+        [
+            cell((1, 1), Float(10.), Float(12.)),
+            cell((2, 0), Empty, String("pears".to_string())),
+            WorkbookDiff::SheetOnlyInLeft("Notes".to_string()),
+            WorkbookDiff::SheetOnlyInRight("Summary".to_string()),
+        ]
+    );
+
+    // chartsheets are compared as empty worksheets
+    let mut left: Xlsx<_> = wb("any_sheets.xlsx");
+    let mut right: Xlsx<_> = wb("any_sheets.xlsx");
+    assert_eq!(left.diff_workbook(&mut right).unwrap(), []);
+}
+
+#[test]
+fn test_xlsx_worksheet_columns() {
+    let mut excel: Xlsx<_> = wb("wide_columns.xlsx");
+
+    // columns E and B, E3 being empty
+    let range = excel.worksheet_columns("Sheet1", &[4, 1]).unwrap();
+    assert_eq!(range.start(), Some((0, 0)));
+    range_eq!(
+        range,
+        [
+            [Float(14.), Float(11.)],
+            [Float(24.), Float(21.)],
+            [Empty, Float(31.)],
+            [Float(44.), Float(41.)],
+        ]
+    );
+
+    assert!(excel.worksheet_columns("Sheet1", &[20]).unwrap().is_empty());
+}
+
+#[test]
+fn test_xlsx_worksheet_charts() {
+    use calamine::{ChartType, SeriesRef};
+
+    let mut excel: Xlsx<_> = wb("charts.xlsx");
+    let charts = excel.worksheet_charts("Data").unwrap();
+    assert_eq!(charts.len(), 2);
+
+    let bar = &charts[0];
+    assert_eq!(bar.path, "xl/charts/chart1.xml");
+    assert_eq!(bar.title.as_deref(), Some("Sales by region"));
+    assert_eq!(bar.chart_type, ChartType::Bar);
+    let regions = Some(Dimensions::new((1, 0), (3, 0)));
+    assert_eq!(
+        bar.series,
+        [
+            SeriesRef {
+                name: Some("2023".to_string()),
+                values_sheet: Some("Data".to_string()),
+                values: Some(Dimensions::new((1, 1), (3, 1))),
+                categories_sheet: Some("Data".to_string()),
+                categories: regions,
+            },
+            SeriesRef {
+                name: Some("2024".to_string()),
+                values_sheet: Some("Data".to_string()),
+                values: Some(Dimensions::new((1, 2), (3, 2))),
+                categories_sheet: Some("Data".to_string()),
+                categories: regions,
+            },
+        ]
+    );
+
+    // no title, a single series without name nor categories
+    let line = &charts[1];
+    assert_eq!(line.title, None);
+    assert_eq!(line.chart_type, ChartType::Line);
+    assert_eq!(line.series.len(), 1);
+    assert_eq!(line.series[0].name, None);
+    assert_eq!(line.series[0].categories, None);
+    assert_eq!(line.series[0].values, Some(Dimensions::new((1, 1), (3, 1))));
+
+    assert_eq!(excel.worksheet_charts("Empty").unwrap(), []);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn test_xlsx_open_workbook_with_password() {
+    use calamine::open_workbook_with_password;
+
+    let path = format!("{}/tests/pass_protected.xlsx", env!("CARGO_MANIFEST_DIR"));
+    assert!(matches!(
+        open_workbook_with_password(&path, "wrong"),
+        Err(calamine::XlsxError::Password)
+    ));
+
+    let mut excel = open_workbook_with_password(&path, "123").unwrap();
+    assert_eq!(excel.sheet_names(), ["Sheet1"]);
+    assert!(excel.worksheet_range("Sheet1").unwrap().is_empty());
+
+    // workbooks without encryption are read as is
+    let path = format!("{}/tests/apply_flags.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut excel = open_workbook_with_password(&path, "123").unwrap();
+    assert_eq!(excel.sheet_names(), ["Sheet1"]);
+    assert!(!excel.worksheet_range("Sheet1").unwrap().is_empty());
+}
+
+#[test]
+fn test_xlsx_defined_name_value() {
+    let excel: Xlsx<_> = wb("constant_names.xlsx");
+
+    assert_eq!(excel.defined_name_value("TaxRate"), Some(Float(0.2)));
+    assert_eq!(excel.defined_name_value("taxrate"), Some(Float(0.2)));
+    assert_eq!(excel.defined_name_value("Offset"), Some(Float(-150.)));
+    assert_eq!(
+        excel.defined_name_value("Quarter"),
+        Some(String("Q1".to_string()))
+    );
+    assert_eq!(
+        excel.defined_name_value("Greeting"),
+        Some(String("say \"hi\"".to_string()))
+    );
+    assert_eq!(excel.defined_name_value("Enabled"), Some(Bool(true)));
+    assert_eq!(excel.defined_name_value("Missing"), Some(Error(NA)));
+
+    // references, other formulas and unknown names
+    assert_eq!(excel.defined_name_value("Area"), None);
+    assert_eq!(excel.defined_name_value("Total"), None);
+    assert_eq!(excel.defined_name_value("Unknown"), None);
+}
+
+#[test]
+fn test_xlsx_computed_dimensions() {
+    let mut excel: Xlsx<_> = wb("no_dimension.xlsx");
+
+    // no <dimension> element: the declared dimensions default to A1
+    let mut reader = excel.worksheet_cells_reader("Sheet1").unwrap();
+    assert_eq!(reader.dimensions(), Dimensions::default());
+    assert_eq!(reader.computed_dimensions(), None);
+    reader.next_cell().unwrap();
+    assert_eq!(
+        reader.computed_dimensions(),
+        Some(Dimensions::new((1, 1), (1, 1)))
+    );
+    while reader.next_cell().unwrap().is_some() {}
+    assert_eq!(
+        reader.computed_dimensions(),
+        Some(Dimensions::new((1, 1), (4, 3)))
+    );
+    drop(reader);
+
+    assert_eq!(
+        excel.worksheet_dimension("Sheet1").unwrap(),
+        Dimensions::new((1, 1), (4, 3))
+    );
+    let formats = excel.worksheet_formats("Sheet1").unwrap();
+    assert_eq!(formats.start(), Some((1, 1)));
+    assert_eq!(formats.end(), Some((4, 3)));
+}
+
+#[test]
+fn test_xlsx_shared_formula_masters() {
+    let mut excel: Xlsx<_> = wb("shared_formulas.xlsx");
+    let mut reader = excel.worksheet_cells_reader("Sheet1").unwrap();
+    assert_eq!(reader.shared_formula_count(), 0);
+    while reader.next_formula().unwrap().is_some() {}
+
+    assert_eq!(reader.shared_formula_count(), 2);
+    assert_eq!(
+        reader.shared_formulas().collect::<Vec<_>>(),
+        [(0, "A1*2", 3), (1, "A1+1", 2)]
+    );
+}
+
+#[test]
+fn test_xlsx_worksheet_custom_views() {
+    use calamine::CustomSheetView;
+
+    let mut excel: Xlsx<_> = wb("custom_views.xlsx");
+    assert_eq!(
+        excel.worksheet_custom_views("Sheet1").unwrap(),
+        [CustomSheetView {
+            guid: "{8F2D6A1B-3C4E-4F50-9A61-7B8C9D0E1F23}".to_string(),
+            name: Some("Summary only".to_string()),
+            state: SheetVisible::Visible,
+            hidden_rows: false,
+            hidden_columns: true,
+            filter: true,
+            filter_unique: false,
+            auto_filter: Some(Dimensions::new((0, 0), (2, 1))),
+        }]
+    );
+    assert_eq!(excel.worksheet_custom_views("Sheet2").unwrap(), []);
+}
+
+#[test]
+fn test_range_to_html() {
+    use calamine::{CellStyle, Fill, Font, HtmlOptions};
+
+    let range = Range::from_2d_vec(vec![
+        vec![String("Item".to_string()), String("Note".to_string())],
+        vec![String("<script>".to_string()), Error(Div0)],
+        vec![Float(1.5), Empty],
+    ]);
+
+    let html = range.to_html(HtmlOptions::default());
+    assert_eq!(
+        html,
+        "<table>\n\
+         <tr><td>Item</td><td>Note</td></tr>\n\
+         <tr><td>&lt;script&gt;</td><td>#DIV/0!</td></tr>\n\
+         <tr><td>1.5</td><td></td></tr>\n\
+         </table>\n"
+    );
+
+    let highlight = CellStyle {
+        fill: Some(Arc::new(Fill {
+            pattern_type: PatternType::Solid,
+            foreground_color: Some(Color::Rgb {
+                r: 0xFF,
+                g: 0xFF,
+                b: 0x00,
+            }),
+            background_color: None,
+        })),
+        font: Some(Arc::new(Font {
+            color: Some(Color::Indexed(2)),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    let mut formats = vec![CellStyle::default(); 6];
+    formats[2] = highlight;
+    let opts = HtmlOptions {
+        header: true,
+        formats: &formats,
+    };
+    let html = range.to_html(opts);
+    assert!(html.starts_with("<table>\n<tr><th>Item</th><th>Note</th></tr>\n"));
+    assert!(html
+        .contains("<tr><td style=\"background-color:#FFFF00;color:#FF0000\">&lt;script&gt;</td>"));
+}
+
+#[test]
+fn test_xlsx_defined_names_with_attributes() {
+    use calamine::DefinedName;
+
+    let excel: Xlsx<_> = wb("function_names.xlsx");
+    assert_eq!(
+        excel.defined_names_with_attributes(),
+        [
+            DefinedName {
+                name: "Rate".to_string(),
+                formula: "0.05".to_string(),
+                is_function: false,
+                function_group_id: None,
+            },
+            DefinedName {
+                name: "Data".to_string(),
+                formula: "Sheet1!$A$1:$A$2".to_string(),
+                is_function: false,
+                function_group_id: None,
+            },
+            DefinedName {
+                name: "Discount".to_string(),
+                formula: "Sheet1!$B$1".to_string(),
+                is_function: true,
+                function_group_id: Some(14),
+            },
+            DefinedName {
+                name: "_xll.Convert".to_string(),
+                formula: "#REF!".to_string(),
+                is_function: true,
+                function_group_id: None,
+            },
+            // the unknown entity of the comment and the invalid group are skipped
+            DefinedName {
+                name: "Fees".to_string(),
+                formula: "Sheet1!$A$2".to_string(),
+                is_function: false,
+                function_group_id: None,
+            },
+        ]
+    );
+    assert_eq!(excel.defined_names().len(), 5);
+}
+
+#[test]
+fn test_range_column_stats() {
+    use calamine::ColumnStats;
+
+    let range = Range::from_2d_vec(vec![
+        vec![String("Name".to_string()), String("Amount".to_string())],
+        vec![String("a".to_string()), Float(2.5)],
+        vec![String("b".to_string()), String("n/a".to_string())],
+        vec![Empty, Int(-4)],
+        vec![String("c".to_string()), Empty],
+        vec![String("d".to_string()), Float(7.5)],
+    ]);
+
+    assert_eq!(
+        range.column_stats(1),
+        ColumnStats {
+            count: 5,
+            numeric_count: 3,
+            min: Some(-4.0),
+            max: Some(7.5),
+            sum: 6.0,
+            mean: Some(2.0),
+            null_count: 1,
+        }
+    );
+    assert_eq!(
+        range.column_stats(0),
+        ColumnStats {
+            count: 5,
+            null_count: 1,
+            ..ColumnStats::default()
+        }
+    );
+    assert_eq!(range.column_stats(2), ColumnStats::default());
+}
+
+#[test]
+fn test_auto_detect_encrypted_workbook() {
+    use calamine::{open_workbook_auto_from_rs, Error, XlsxError};
+
+    let path = format!("{}/tests/pass_protected.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let data = std::fs::read(&path).unwrap();
+    assert!(matches!(
+        open_workbook_auto_from_rs(Cursor::new(data)),
+        Err(Error::Xlsx(XlsxError::Password))
+    ));
+
+    // an encrypted package is a compound document, as xls files
+    let xls_path = std::env::temp_dir().join("calamine_pass_protected.xls");
+    std::fs::copy(&path, &xls_path).unwrap();
+    let result = open_workbook_auto(&xls_path);
+    std::fs::remove_file(&xls_path).unwrap();
+    assert!(matches!(result, Err(Error::Xlsx(XlsxError::Password))));
+
+    // legacy workbooks are still detected without an extension
+    let path = format!("{}/tests/issues.xls", env!("CARGO_MANIFEST_DIR"));
+    let data = std::fs::read(path).unwrap();
+    assert!(matches!(
+        open_workbook_auto_from_rs(Cursor::new(data)),
+        Ok(calamine::Sheets::Xls(_))
+    ));
+}
+
+#[test]
+fn test_xlsx_worksheet_to_csv() {
+    use calamine::CsvOptions;
+
+    let mut excel: Xlsx<_> = wb("csv_export.xlsx");
+    let mut streamed = Vec::new();
+    excel
+        .worksheet_to_csv("Sheet1", &mut streamed, CsvOptions::default())
+        .unwrap();
+    assert_eq!(
+        std::str::from_utf8(&streamed).unwrap(),
+        "Name,Note,1.5,true\r\n\
+         \"Fish, chips\",\"Say \"\"hi\"\"\",42,\r\n\
+         ,,,\r\n\
+         \"multi\nline\",,,#N/A\r\n\
+         ,last,,\r\n"
+    );
+
+    let range = excel.worksheet_range_at_index(0).unwrap();
+    let mut expected = Vec::new();
+    range
+        .write_csv(&mut expected, CsvOptions::default())
+        .unwrap();
+    assert_eq!(streamed, expected);
+
+    let opts = CsvOptions {
+        delimiter: ';',
+        crlf: false,
+    };
+    let mut streamed = Vec::new();
+    excel
+        .worksheet_to_csv("Sheet1", &mut streamed, opts)
+        .unwrap();
+    let mut expected = Vec::new();
+    range.write_csv(&mut expected, opts).unwrap();
+    assert_eq!(streamed, expected);
+    assert!(streamed.starts_with(b"Name;Note;1.5;true\nFish, chips;"));
+
+    // worksheets whose dimension or header row differs from the used area
+    for (name, header_row) in [
+        ("no-header.xlsx", HeaderRow::FirstNonEmptyRow),
+        ("no-header.xlsx", HeaderRow::Row(0)),
+        ("merged_range.xlsx", HeaderRow::FirstNonEmptyRow),
+        ("cell_images.xlsx", HeaderRow::FirstNonEmptyRow),
+    ] {
+        let mut excel: Xlsx<_> = wb(name);
+        excel.with_header_row(header_row);
+        let sheet = excel.sheet_names()[0].clone();
+        let mut streamed = Vec::new();
+        excel
+            .worksheet_to_csv(&sheet, &mut streamed, CsvOptions::default())
+            .unwrap();
+        let mut expected = Vec::new();
+        excel
+            .worksheet_range_at_index(0)
+            .unwrap()
+            .write_csv(&mut expected, CsvOptions::default())
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&streamed).unwrap(),
+            std::str::from_utf8(&expected).unwrap(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_range_group_by() {
+    use calamine::AggregationFunction;
+
+    let range = Range::from_2d_vec(vec![
+        vec![String("north".to_string()), Float(10.0)],
+        vec![String("south".to_string()), Int(4)],
+        vec![String("north".to_string()), String("n/a".to_string())],
+        vec![Empty, Float(100.0)],
+        vec![String("east".to_string()), Empty],
+        vec![String("north".to_string()), Float(2.5)],
+        vec![String("south".to_string()), Int(-1)],
+    ]);
+
+    let key = |k: &str| String(k.to_string());
+    assert_eq!(
+        range.group_by(0, 1, AggregationFunction::Sum),
+        [
+            (key("north"), Float(12.5)),
+            (key("south"), Float(3.0)),
+            (key("east"), Float(0.0)),
+        ]
+    );
+    assert_eq!(
+        range.group_by(0, 1, AggregationFunction::Count),
+        [
+            (key("north"), Int(3)),
+            (key("south"), Int(2)),
+            (key("east"), Int(0))
+        ]
+    );
+    assert_eq!(
+        range.group_by(0, 1, AggregationFunction::Average),
+        [
+            (key("north"), Float(6.25)),
+            (key("south"), Float(1.5)),
+            (key("east"), Error(Div0)),
+        ]
+    );
+    assert_eq!(
+        range.group_by(0, 1, AggregationFunction::Max),
+        [
+            (key("north"), Float(10.0)),
+            (key("south"), Float(4.0)),
+            (key("east"), Empty),
+        ]
+    );
+    assert!(range.group_by(0, 2, AggregationFunction::Sum).is_empty());
+}
+
+#[test]
+fn test_format_value_out_of_range_dates() {
+    use calamine::format_value;
+
+    // dates out of the range of Excel are written as is, without panicking
+    let huge = DateTimeIso("9223372036854775807-12-31T00:00:00".to_string());
+    assert_eq!(
+        format_value(&huge, "yyyy-mm-dd", false),
+        "9223372036854775807-12-31T00:00:00"
+    );
+    let invalid = DateTimeIso("2020-00-00".to_string());
+    assert_eq!(format_value(&invalid, "yyyy-mm-dd", false), "2020-00-00");
+    for value in [1e300, -1e300, f64::NAN, f64::INFINITY] {
+        format_value(&Float(value), "yyyy-mm-dd hh:mm", false);
+        format_value(&Float(value), "yyyy-mm-dd hh:mm", true);
+    }
+}
@@ -0,0 +1,101 @@
+use calamine::{open_workbook, Reader, ReaderBuffers, Xlsx};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs::File;
+use std::io::BufReader;
+
+/// Counts the bytes allocated by the current thread
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count(size: usize) {
+    let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + size));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocated_by<F: FnOnce()>(f: F) -> usize {
+    let start = ALLOCATED.with(Cell::get);
+    f();
+    ALLOCATED.with(Cell::get) - start
+}
+
+#[test]
+fn test_xlsx_reused_buffers_allocations() {
+    let path = format!("{}/tests/many_sheets.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut excel: Xlsx<BufReader<File>> = open_workbook(&path).unwrap();
+    let sheets = excel.sheet_names();
+    assert_eq!(sheets.len(), 40);
+
+    let fresh = allocated_by(|| {
+        for name in &sheets {
+            excel.with_buffers(ReaderBuffers::new());
+            excel.worksheet_range(name).unwrap();
+        }
+    });
+    let reused = allocated_by(|| {
+        for name in &sheets {
+            excel.worksheet_range(name).unwrap();
+        }
+    });
+
+    // every worksheet part is decompressed through the same 8 KiB buffer
+    assert!(
+        fresh >= reused + sheets.len() * 8 * 1024,
+        "fresh: {fresh} bytes, reused: {reused} bytes"
+    );
+}
+
+#[test]
+fn test_xlsx_buffers_kept_after_errors_and_events() {
+    let path = format!("{}/tests/many_sheets.xlsx", env!("CARGO_MANIFEST_DIR"));
+    let mut excel: Xlsx<BufReader<File>> = open_workbook(&path).unwrap();
+    let sheets = excel.sheet_names();
+
+    let fresh = allocated_by(|| {
+        for name in &sheets {
+            excel.with_buffers(ReaderBuffers::new());
+            excel.worksheet_range(name).unwrap();
+        }
+    });
+
+    // failed reads and event iterators give the buffers back to the workbook
+    excel.with_max_rows(0);
+    for name in &sheets {
+        assert!(excel.worksheet_range(name).is_err());
+    }
+    excel.with_max_rows(u32::MAX);
+    for name in &sheets {
+        excel.worksheet_events(name).unwrap().for_each(drop);
+    }
+    let reused = allocated_by(|| {
+        for name in &sheets {
+            excel.worksheet_range(name).unwrap();
+        }
+    });
+
+    assert!(
+        fresh >= reused + sheets.len() * 8 * 1024,
+        "fresh: {fresh} bytes, reused: {reused} bytes"
+    );
+}
@@ -1,6 +1,6 @@
 use calamine::{
     open_workbook, CfvoType, Color, ComparisonOperator, ConditionalFormatRule,
-    ConditionalFormatType, IconSetType, TimePeriod, Xlsx,
+    ConditionalFormatType, Dimensions, IconSetType, TimePeriod, Xlsx,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -206,10 +206,7 @@ fn test_conditional_formatting_parsing() {
     let rule10 = rules_by_priority.get(&10).unwrap();
     match &rule10.rule_type {
         ConditionalFormatType::DataBar(data_bar) => {
-            // Note: Parser limitation - showValue attribute on outer dataBar element
-            // is not parsed when dataBar is called as a child element
-            // The parser defaults to show_value=true
-            assert!(data_bar.show_value); // Parser default
+            assert!(!data_bar.show_value);
             assert_eq!(data_bar.min_length, 10);
             assert_eq!(data_bar.max_length, 90);
             assert_eq!(data_bar.min_cfvo.value_type, CfvoType::Number);
@@ -235,8 +232,18 @@ fn test_conditional_formatting_parsing() {
     let rule11 = rules_by_priority.get(&11).unwrap();
     match &rule11.rule_type {
         ConditionalFormatType::IconSet(icon_set) => {
-            // Note: The parser seems to default to Arrows3 when it can't recognize the icon set
-            // This is a known limitation we should document
+            // 3 traffic lights, the default icon set
+            assert_eq!(icon_set.icon_set, IconSetType::TrafficLights3);
+            assert!(!icon_set.show_value);
+            assert!(icon_set.reverse);
+            assert_eq!(
+                icon_set.thresholds().collect::<Vec<_>>(),
+                [
+                    (CfvoType::Number, Some(100.0)),
+                    (CfvoType::Number, Some(200.0)),
+                    (CfvoType::Number, Some(300.0)),
+                ]
+            );
             assert_eq!(icon_set.cfvos.len(), 3);
             assert_eq!(icon_set.cfvos[0].value_type, CfvoType::Number);
             assert_eq!(icon_set.cfvos[0].value, Some("100".to_string()));
@@ -321,10 +328,9 @@ fn test_conditional_formatting_parsing() {
     let rule16 = rules_by_priority.get(&16).unwrap();
     match &rule16.rule_type {
         ConditionalFormatType::IconSet(icon_set) => {
-            // Note: The icon set type might not be recognized as 5Quarters
+            assert_eq!(icon_set.icon_set, IconSetType::Quarters5);
             assert_eq!(icon_set.cfvos.len(), 5);
-            // Note: Parser limitation - percent attribute may not be parsed correctly
-            // assert!(icon_set.percent);
+            assert!(icon_set.percent);
             for (i, cfvo) in icon_set.cfvos.iter().enumerate() {
                 assert_eq!(cfvo.value_type, CfvoType::Percent);
                 assert_eq!(cfvo.value, Some((i * 20).to_string()));
@@ -423,9 +429,6 @@ fn test_conditional_formatting_parsing() {
 
     // Document known parser limitations discovered during testing:
     println!("\nKnown parser limitations:");
-    println!(
-        "- DataBar showValue attribute on outer element is not parsed when dataBar is a child"
-    );
     println!("- IconSet percent attribute on iconSet element is not parsed");
     println!("- DXF formats are not parsed when Excel optimizes them away");
     println!("- Some icon set types default to Arrows3 when not recognized");
@@ -469,3 +472,143 @@ fn test_conditional_format_type_display() {
         "allDatesInPeriodQuarter1"
     );
 }
+
+#[test]
+fn test_conditional_formatting_differential_format() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/conditional_formatting.xlsx");
+    let mut workbook: Xlsx<_> = open_workbook(&path).expect("Cannot open file");
+
+    let cf_rules = workbook
+        .worksheet_conditional_formatting("Sheet1")
+        .expect("Failed to get conditional formatting")
+        .to_vec();
+    let rule = &cf_rules[0].rules[0];
+    let dxf_id = rule.dxf_id.expect("rule should reference a dxf") as usize;
+
+    let dxf = &workbook.dxf_formats()[dxf_id];
+    let fill = dxf.fill.as_ref().expect("dxf should override the fill");
+    assert_eq!(
+        fill.pattern_fill.bg_color,
+        Some(Color::Argb {
+            a: 0xFF,
+            r: 0xFF,
+            g: 0xC7,
+            b: 0xCE
+        })
+    );
+    let font = dxf.font.as_ref().expect("dxf should override the font");
+    assert_eq!(
+        font.color,
+        Some(Color::Argb {
+            a: 0xFF,
+            r: 0x9C,
+            g: 0x00,
+            b: 0x06
+        })
+    );
+    assert!(dxf.border.is_none());
+}
+
+#[test]
+fn test_conditional_formatting_color_scale_stops() {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/test_conditional_formatting.xlsx");
+    let mut workbook: Xlsx<_> = open_workbook(&path).expect("Cannot open file");
+
+    let cf_rules = workbook
+        .worksheet_conditional_formatting("Conditional Formatting")
+        .expect("Failed to get conditional formatting");
+    let rule = cf_rules
+        .iter()
+        .flat_map(|cf| &cf.rules)
+        .find(|rule| rule.priority == 20)
+        .unwrap();
+    let ConditionalFormatType::ColorScale(color_scale) = &rule.rule_type else {
+        panic!("Expected ColorScale for priority 20");
+    };
+
+    let stops = color_scale
+        .stops()
+        .map(|(cfvo, color)| (cfvo.value_type, cfvo.number(), color.clone()))
+        .collect::<Vec<_>>();
+    let argb = |r, g, b| Color::Argb { a: 255, r, g, b };
+    assert_eq!(
+        stops,
+        vec![
+            (CfvoType::Percentile, Some(10.), argb(255, 0, 0)),
+            (CfvoType::Percentile, Some(50.), argb(255, 255, 0)),
+            (CfvoType::Percentile, Some(90.), argb(0, 255, 0)),
+        ]
+    );
+
+    // `min` and `max` thresholds have no value
+    let rule = cf_rules
+        .iter()
+        .flat_map(|cf| &cf.rules)
+        .find(|rule| rule.priority == 2)
+        .unwrap();
+    let ConditionalFormatType::ColorScale(color_scale) = &rule.rule_type else {
+        panic!("Expected ColorScale for priority 2");
+    };
+    assert_eq!(color_scale.cfvos[0].number(), None);
+    assert_eq!(color_scale.cfvos[1].number(), Some(50.));
+}
+
+#[test]
+fn test_conditional_formatting_data_bar_extension() {
+    use calamine::AxisPosition;
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data_bar_negative.xlsx");
+    let mut workbook: Xlsx<_> = open_workbook(&path).expect("Cannot open file");
+
+    let cf_rules = workbook
+        .worksheet_conditional_formatting("Sheet1")
+        .expect("Failed to get conditional formatting");
+    // the extension is merged into the rule, not read as another format
+    assert_eq!(cf_rules.len(), 1);
+    assert_eq!(cf_rules[0].rules.len(), 1);
+    let ConditionalFormatType::DataBar(data_bar) = &cf_rules[0].rules[0].rule_type else {
+        panic!("Expected DataBar");
+    };
+
+    let argb = |r, g, b| Color::Argb { a: 255, r, g, b };
+    // thresholds and color of the rule
+    assert_eq!(data_bar.min_cfvo.value_type, CfvoType::Min);
+    assert_eq!(data_bar.max_cfvo.value_type, CfvoType::Max);
+    assert_eq!(data_bar.color, argb(0x63, 0x8E, 0xC6));
+    assert!(data_bar.show_value);
+    // settings of the extension
+    assert_eq!(data_bar.min_length, 0);
+    assert_eq!(data_bar.max_length, 100);
+    assert!(!data_bar.gradient);
+    assert!(data_bar.border);
+    assert_eq!(data_bar.border_color, Some(argb(0x63, 0x8E, 0xC6)));
+    assert_eq!(data_bar.negative_color, Some(argb(0xFF, 0, 0)));
+    assert_eq!(data_bar.negative_border_color, Some(argb(0xC0, 0, 0)));
+    assert_eq!(data_bar.axis_position, Some(AxisPosition::Midpoint));
+    assert_eq!(data_bar.axis_color, Some(argb(0, 0, 0)));
+}
+
+#[test]
+fn test_conditional_formatting_whole_column_and_row() {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/conditional_formatting_sqref.xlsx");
+    let mut workbook: Xlsx<_> = open_workbook(&path).expect("Cannot open file");
+
+    let cf_rules = workbook
+        .worksheet_conditional_formatting("Sheet1")
+        .expect("Failed to get conditional formatting");
+    assert_eq!(cf_rules.len(), 1);
+    // the invalid `B3:XX` area is skipped, the other ones are kept
+    assert_eq!(
+        cf_rules[0].ranges,
+        [
+            Dimensions::new((0, 2), (1_048_575, 2)),
+            Dimensions::new((1, 0), (1, 16_383)),
+        ]
+    );
+    assert_eq!(
+        cf_rules[0].rules[0].rule_type,
+        ConditionalFormatType::ContainsBlanks
+    );
+}
@@ -1,4 +1,7 @@
-use calamine::{open_workbook, open_workbook_auto, Reader, Theme, Xlsx};
+use calamine::{
+    open_workbook, open_workbook_auto, Error, Ods, OdsError, Reader, Theme, Xls, XlsError, Xlsb,
+    XlsbError, Xlsx,
+};
 use std::fs::File;
 use std::io::BufReader;
 
@@ -262,3 +265,41 @@ fn test_auto_reader_theme() {
         }
     }
 }
+
+#[test]
+fn test_xls_theme_unsupported() {
+    let mut excel: Xls<_> = wb("issues.xls");
+
+    match excel.theme() {
+        Err(XlsError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        other => panic!("expected an unsupported error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_xlsb_ods_theme_unsupported() {
+    let mut excel: Xlsb<_> = wb("issues.xlsb");
+    match excel.theme() {
+        Err(XlsbError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        other => panic!("expected an unsupported error, got {other:?}"),
+    }
+
+    let mut excel: Ods<_> = wb("issues.ods");
+    match excel.theme() {
+        Err(OdsError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::Unsupported),
+        other => panic!("expected an unsupported error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_auto_reader_theme_unsupported() {
+    let path = format!("{}/tests/issues.xls", env!("CARGO_MANIFEST_DIR"));
+    let mut workbook = open_workbook_auto(&path).expect("Cannot open issues.xls with auto reader");
+
+    match workbook.theme() {
+        Err(Error::Xls(XlsError::Io(e))) => {
+            assert_eq!(e.kind(), std::io::ErrorKind::Unsupported)
+        }
+        other => panic!("expected an unsupported error, got {other:?}"),
+    }
+}